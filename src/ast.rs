@@ -1,5 +1,110 @@
 use crate::symbol::Symbol;
 
+/// A byte-offset range into the source file identified by `file`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub file: Symbol,
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Wraps a node with the `Span` of source text it was parsed from.
+#[derive(Debug, Clone, Copy)]
+pub struct Spanned<T> {
+    pub node: T,
+    pub span: Span,
+}
+
+impl<T> Spanned<T> {
+    pub fn new(node: T, span: Span) -> Self {
+        Spanned { node, span }
+    }
+}
+
+/// The severity of a `Diagnostic`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A diagnostic message tied to a location in source, with optional
+/// secondary labels pointing at related spans (e.g. the vector being
+/// indexed, in addition to the out-of-range index itself).
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub span: Span,
+    pub labels: Vec<(Span, String)>,
+}
+
+impl Diagnostic {
+    pub fn error(message: impl Into<String>, span: Span) -> Self {
+        Diagnostic {
+            severity: Severity::Error,
+            message: message.into(),
+            span,
+            labels: Vec::new(),
+        }
+    }
+
+    pub fn with_label(mut self, span: Span, label: impl Into<String>) -> Self {
+        self.labels.push((span, label.into()));
+        self
+    }
+}
+
+/// Renders `diagnostic` against `source`, printing the offending line with a
+/// caret underline beneath the span. Line/column are computed on demand from
+/// the byte offset rather than being stored on `Span`.
+pub fn render_diagnostic(diagnostic: &Diagnostic, source: &str) -> String {
+    let (line, column, line_text) = locate(source, diagnostic.span.start as usize);
+    let underline_len = (diagnostic.span.end - diagnostic.span.start).max(1) as usize;
+    let mut out = format!(
+        "{:?}: {}\n {:>4} | {}\n      | {}{}\n",
+        diagnostic.severity,
+        diagnostic.message,
+        line,
+        line_text,
+        " ".repeat(column.saturating_sub(1)),
+        "^".repeat(underline_len),
+    );
+    for (span, label) in &diagnostic.labels {
+        let (line, column, line_text) = locate(source, span.start as usize);
+        out.push_str(&format!(
+            " {:>4} | {}\n      | {}note: {}\n",
+            line,
+            line_text,
+            " ".repeat(column.saturating_sub(1)),
+            label,
+        ));
+    }
+    out
+}
+
+/// Finds the 1-based line/column and the full text of the line containing
+/// `offset`.
+fn locate(source: &str, offset: usize) -> (usize, usize, &str) {
+    let mut line = 1;
+    let mut line_start = 0;
+    for (i, c) in source.char_indices() {
+        if i >= offset {
+            break;
+        }
+        if c == '\n' {
+            line += 1;
+            line_start = i + 1;
+        }
+    }
+    let line_text = source[line_start..]
+        .split('\n')
+        .next()
+        .unwrap_or_default();
+    let column = offset - line_start + 1;
+    (line, column, line_text)
+}
+
 pub enum PatternTupleMember<'ast> {
     Positional(Pattern<'ast>),
     Named(Symbol, Pattern<'ast>),
@@ -27,6 +132,7 @@ pub enum Statement<'ast> {
     Expr(Expr<'ast>),
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BuiltIn {
     Add,
     Sub,
@@ -35,6 +141,7 @@ pub enum BuiltIn {
     Mod,
     Exp,
     Index,
+    Convert(crate::convert::Conversion),
 }
 
 pub enum Expr<'ast> {
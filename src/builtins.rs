@@ -0,0 +1,224 @@
+//! Built-in function registry backing `Expr::Call`. Each entry is a plain
+//! Rust function over already-evaluated `Value` args; `eval_call` looks a
+//! name up here only after checking it isn't shadowed by a user-defined
+//! binding.
+
+use crate::eval::{EvalError, EvalErrorKind, Result, Value};
+use crate::source::Span;
+
+pub type BuiltIn = fn(&[Value], Span) -> Result<Value>;
+
+/// Looks up a built-in by name, returning `None` for anything not in the
+/// registry so the caller can fall back to an `UnboundIdentifier` error.
+pub fn lookup(name: &str) -> Option<BuiltIn> {
+    match name {
+        "len" => Some(len),
+        "is_empty" => Some(is_empty),
+        "min" => Some(min),
+        "max" => Some(max),
+        "concat" => Some(concat),
+        "fst" => Some(fst),
+        "snd" => Some(snd),
+        "append" => Some(append),
+        "prepend" => Some(prepend),
+        _ => None,
+    }
+}
+
+fn type_mismatch(span: Span) -> EvalError {
+    EvalError {
+        kind: EvalErrorKind::TypeMismatch,
+        span,
+    }
+}
+
+fn len(args: &[Value], span: Span) -> Result<Value> {
+    match args {
+        [Value::Vector(values)] => Ok(Value::Integer(values.len() as i64)),
+        [Value::String(string)] => Ok(Value::Integer(string.chars().count() as i64)),
+        [Value::Tuple(tuple)] => Ok(Value::Integer(tuple.positional.len() as i64)),
+        _ => Err(type_mismatch(span)),
+    }
+}
+
+fn is_empty(args: &[Value], span: Span) -> Result<Value> {
+    match args {
+        [Value::Vector(values)] => Ok(Value::Bool(values.is_empty())),
+        [Value::String(string)] => Ok(Value::Bool(string.is_empty())),
+        [Value::Tuple(tuple)] => Ok(Value::Bool(tuple.positional.is_empty())),
+        _ => Err(type_mismatch(span)),
+    }
+}
+
+/// Shared walk for `min`/`max` over a single vector argument: folds the
+/// elements pairwise with `partial_cmp`, so a non-numeric or mixed-type
+/// element anywhere in the vector is a `TypeMismatch`.
+fn extreme(args: &[Value], span: Span, want_greater: bool) -> Result<Value> {
+    let [Value::Vector(elements)] = args else {
+        return Err(type_mismatch(span));
+    };
+    let mut elements = elements.iter();
+    let mut best = elements.next().ok_or_else(|| type_mismatch(span))?.clone();
+    if !matches!(best, Value::Integer(_) | Value::Float(_)) {
+        return Err(type_mismatch(span));
+    }
+    for element in elements {
+        let ordering = match (&best, element) {
+            (Value::Integer(a), Value::Integer(b)) => a.partial_cmp(b),
+            (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+            _ => return Err(type_mismatch(span)),
+        }
+        .ok_or_else(|| type_mismatch(span))?;
+        if ordering.is_lt() == want_greater {
+            best = element.clone();
+        }
+    }
+    Ok(best)
+}
+
+fn min(args: &[Value], span: Span) -> Result<Value> {
+    extreme(args, span, false)
+}
+
+fn max(args: &[Value], span: Span) -> Result<Value> {
+    extreme(args, span, true)
+}
+
+/// Joins two vectors end-to-end: `concat([1,2],[3,4]) == [1,2,3,4]`.
+fn concat(args: &[Value], span: Span) -> Result<Value> {
+    match args {
+        [Value::Vector(a), Value::Vector(b)] => {
+            let mut joined = a.clone();
+            joined.extend(b.iter().cloned());
+            Ok(Value::Vector(joined))
+        }
+        _ => Err(type_mismatch(span)),
+    }
+}
+
+fn fst(args: &[Value], span: Span) -> Result<Value> {
+    match args {
+        [Value::Tuple(tuple)] => tuple
+            .positional
+            .get(0)
+            .cloned()
+            .ok_or_else(|| type_mismatch(span)),
+        _ => Err(type_mismatch(span)),
+    }
+}
+
+fn snd(args: &[Value], span: Span) -> Result<Value> {
+    match args {
+        [Value::Tuple(tuple)] => tuple
+            .positional
+            .get(1)
+            .cloned()
+            .ok_or_else(|| type_mismatch(span)),
+        _ => Err(type_mismatch(span)),
+    }
+}
+
+fn append(args: &[Value], span: Span) -> Result<Value> {
+    match args {
+        [Value::Tuple(tuple), value] => {
+            let mut tuple = tuple.clone();
+            tuple.positional.push(value.clone());
+            Ok(Value::Tuple(tuple))
+        }
+        _ => Err(type_mismatch(span)),
+    }
+}
+
+fn prepend(args: &[Value], span: Span) -> Result<Value> {
+    match args {
+        [Value::Tuple(tuple), value] => {
+            let mut tuple = tuple.clone();
+            tuple.positional.insert(0, value.clone());
+            Ok(Value::Tuple(tuple))
+        }
+        _ => Err(type_mismatch(span)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::ValueTuple;
+    use crate::source::Position;
+
+    fn span() -> Span {
+        let origin = Position {
+            offset: 0,
+            line: 1,
+            column: 1,
+        };
+        Span {
+            start: origin,
+            end: origin,
+        }
+    }
+
+    #[test]
+    fn test_len_and_is_empty() {
+        let vector = Value::Vector(vec![Value::Integer(1), Value::Integer(2)]);
+        assert_eq!(len(&[vector.clone()], span()), Ok(Value::Integer(2)));
+        assert_eq!(is_empty(&[vector], span()), Ok(Value::Bool(false)));
+        assert_eq!(
+            is_empty(&[Value::Vector(vec![])], span()),
+            Ok(Value::Bool(true))
+        );
+    }
+
+    #[test]
+    fn test_min_max() {
+        let vector = Value::Vector(vec![
+            Value::Integer(3),
+            Value::Integer(1),
+            Value::Integer(2),
+        ]);
+        assert_eq!(min(&[vector.clone()], span()), Ok(Value::Integer(1)));
+        assert_eq!(max(&[vector], span()), Ok(Value::Integer(3)));
+    }
+
+    #[test]
+    fn test_concat() {
+        let a = Value::Vector(vec![Value::Integer(1), Value::Integer(2)]);
+        let b = Value::Vector(vec![Value::Integer(3), Value::Integer(4)]);
+        assert_eq!(
+            concat(&[a, b], span()),
+            Ok(Value::Vector(vec![
+                Value::Integer(1),
+                Value::Integer(2),
+                Value::Integer(3),
+                Value::Integer(4),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_tuple_accessors() {
+        let tuple = Value::Tuple(ValueTuple {
+            tag: None,
+            positional: vec![Value::Integer(1), Value::Integer(2)],
+            named: vec![],
+        });
+        assert_eq!(fst(&[tuple.clone()], span()), Ok(Value::Integer(1)));
+        assert_eq!(snd(&[tuple.clone()], span()), Ok(Value::Integer(2)));
+        assert_eq!(
+            append(&[tuple.clone(), Value::Integer(3)], span()),
+            Ok(Value::Tuple(ValueTuple {
+                tag: None,
+                positional: vec![Value::Integer(1), Value::Integer(2), Value::Integer(3)],
+                named: vec![],
+            }))
+        );
+        assert_eq!(
+            prepend(&[tuple, Value::Integer(0)], span()),
+            Ok(Value::Tuple(ValueTuple {
+                tag: None,
+                positional: vec![Value::Integer(0), Value::Integer(1), Value::Integer(2)],
+                named: vec![],
+            }))
+        );
+    }
+}
@@ -1,50 +1,141 @@
-use crate::ast::{BinOp, Expr};
-use crate::source::Error;
-use crate::vm::{Op, Val};
-use bincode;
-use std::io::Write;
-
-struct Compiler<W: Write> {
-    writer: W,
+//! Lowers the constant int/float arithmetic subset of `parse::Expr` to the
+//! flat bytecode `vm::Vm` runs: literals become `Push`, and
+//! `OperatorKind::{Add,Subtract,Multiply,Divide,Modulo}` become the
+//! matching opcode, with both operands compiled before the operator
+//! (postfix order) so the VM can apply it directly off the stack.
+
+use crate::eval::{EvalError, EvalErrorKind, Result as EvalResult, Value};
+use crate::parse::{Expr, OperatorKind, Spanned};
+use crate::vm::{Opcode, Optype, Vm};
+
+struct Compiler {
+    bytes: Vec<u8>,
 }
 
-impl<W: Write> Compiler<W> {
-    #[inline]
-    fn new(writer: W) -> Self {
-        Compiler { writer }
-    }
-
-    #[inline]
-    fn write(&mut self, op: &Op) {
-        bincode::serialize_into(&mut self.writer, op).unwrap();
-    }
-
-    fn compile(&mut self, ast: &Expr) -> Result<(), Error> {
-        match *ast {
-            Expr::Void => self.write(&Op::Exit),
-            Expr::Nil => self.write(&Op::Push(Val::Nil)),
-            Expr::Int(a) => self.write(&Op::Push(Val::Int(a))),
-            Expr::Float(a) => self.write(&Op::Push(Val::Float(a))),
-            Expr::Symbol(s) => self.write(&Op::Push(Val::Symbol(s))),
-            Expr::String(s) => self.write(&Op::Push(Val::String(s.into()))),
-            Expr::BinOp(bin_op, l, r) => {
-                self.compile(l)?;
-                self.compile(r)?;
-                self.write(&match bin_op {
-                    BinOp::Add => Op::Add,
-                    BinOp::Sub => Op::Sub,
-                    BinOp::Mul => Op::Mul,
-                    BinOp::Div => Op::Div,
-                });
+impl Compiler {
+    fn new() -> Self {
+        Compiler { bytes: Vec::new() }
+    }
+
+    fn push_opcode(&mut self, opcode: Opcode) {
+        self.bytes.push(opcode as u8);
+    }
+
+    fn compile(&mut self, expr: &Spanned<Expr>) -> EvalResult<()> {
+        match &expr.node {
+            Expr::Integer(value) => {
+                self.push_opcode(Opcode::Push);
+                self.bytes.push(Optype::Int as u8);
+                self.bytes.extend_from_slice(&value.to_le_bytes());
+            }
+            Expr::Float(value) => {
+                self.push_opcode(Opcode::Push);
+                self.bytes.push(Optype::Float as u8);
+                self.bytes.extend_from_slice(&value.to_le_bytes());
+            }
+            Expr::Operator(kind, operands) => {
+                let opcode = match kind {
+                    OperatorKind::Add => Opcode::Add,
+                    OperatorKind::Subtract => Opcode::Sub,
+                    OperatorKind::Multiply => Opcode::Mul,
+                    OperatorKind::Divide => Opcode::Div,
+                    OperatorKind::Modulo => Opcode::Mod,
+                    _ => {
+                        return Err(EvalError {
+                            kind: EvalErrorKind::Unsupported(
+                                "compiler only lowers +, -, *, /, % on int/float literals",
+                            ),
+                            span: expr.span,
+                        })
+                    }
+                };
+                self.compile(&operands[0])?;
+                self.compile(&operands[1])?;
+                self.push_opcode(opcode);
+            }
+            _ => {
+                return Err(EvalError {
+                    kind: EvalErrorKind::Unsupported(
+                        "compiler only lowers int/float literals and arithmetic",
+                    ),
+                    span: expr.span,
+                })
             }
-            _ => todo!(),
         }
         Ok(())
     }
 }
 
-pub fn compile(ast: &Expr) -> Result<Vec<u8>, Error> {
-    let mut writer = vec![];
-    Compiler::new(&mut writer).compile(ast)?;
-    Ok(writer)
-}
\ No newline at end of file
+/// Compiles `expr` into a bytecode stream ending in `Opcode::End`. Fails
+/// with `EvalErrorKind::Unsupported` on anything outside the constant
+/// int/float arithmetic subset `Compiler::compile` handles.
+pub fn compile(expr: &Spanned<Expr>) -> EvalResult<Vec<u8>> {
+    let mut compiler = Compiler::new();
+    compiler.compile(expr)?;
+    compiler.push_opcode(Opcode::End);
+    Ok(compiler.bytes)
+}
+
+/// Compiles `expr` and runs it on the `Vm`, as an alternate execution path
+/// to `eval::eval`'s tree-walk for the same constant-expression subset.
+pub fn eval(expr: &Spanned<Expr>) -> EvalResult<Value> {
+    Vm::run(&compile(expr)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::Environment;
+    use crate::parse;
+    use crate::source::Position;
+    use crate::source::Span;
+    use crate::eval as tree_walk;
+
+    fn spanned_expr(source: &str) -> Spanned<Expr> {
+        let expr = parse::parse_expr(source).expect("parse error");
+        let origin = Position {
+            offset: 0,
+            line: 1,
+            column: 1,
+        };
+        Spanned {
+            node: expr,
+            span: Span {
+                start: origin,
+                end: origin,
+            },
+        }
+    }
+
+    /// Compiling and running via the VM should agree with the tree-walked
+    /// result for every constant expression the compiler accepts.
+    fn assert_round_trips(source: &str) {
+        let expr = spanned_expr(source);
+        let vm_result = eval(&expr);
+        let tree_walk_result = tree_walk::eval(&expr, &mut Environment::new());
+        assert_eq!(vm_result, tree_walk_result);
+    }
+
+    #[test]
+    fn test_compile_and_run_literals() {
+        assert_round_trips("42");
+        assert_round_trips("1.5");
+    }
+
+    #[test]
+    fn test_compile_and_run_arithmetic() {
+        assert_round_trips("1 + 2 * 3");
+        assert_round_trips("7 % 2");
+        assert_round_trips("1.0 + 2.0 * 3.0");
+        assert_round_trips("10 - 2 - 3");
+    }
+
+    #[test]
+    fn test_compile_rejects_unsupported_expr() {
+        let expr = spanned_expr("[1, 2, 3]");
+        assert_eq!(
+            compile(&expr).unwrap_err().kind,
+            EvalErrorKind::Unsupported("compiler only lowers int/float literals and arithmetic")
+        );
+    }
+}
@@ -0,0 +1,287 @@
+//! A constant-evaluation pass over `ast::Expr` that catches a handful of
+//! errors statically instead of at runtime: out-of-range constant indexing,
+//! heterogeneous vector literals, and literal arithmetic that overflows or
+//! divides by zero.
+//!
+//! `ast::Expr` nodes don't carry a `Span` (`ast::Span` is only used by
+//! `Diagnostic`), so every diagnostic here points at a placeholder `0..0`
+//! span in the owning file.
+
+use crate::ast::{BuiltIn, Diagnostic, Expr, Span, Statement, TupleMember};
+use crate::symbol::Symbol;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ConstValue {
+    Int(i64),
+    Float(f64),
+}
+
+fn placeholder_span(file: Symbol) -> Span {
+    Span {
+        file,
+        start: 0,
+        end: 0,
+    }
+}
+
+fn literal_type_name(expr: &Expr) -> Option<&'static str> {
+    match expr {
+        Expr::Int(_) => Some("Int"),
+        Expr::Float(_) => Some("Float"),
+        Expr::Symbol(_) => Some("Symbol"),
+        Expr::String(_) => Some("String"),
+        _ => None,
+    }
+}
+
+/// Runs the pass over a single expression, returning every diagnostic found.
+pub fn check_expr(expr: &Expr, file: Symbol) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    eval_expr(expr, file, &mut diagnostics);
+    diagnostics
+}
+
+/// Runs the pass over a module's body and export expressions.
+pub fn check_module(module: &crate::ast::Module, file: Symbol) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    eval_expr(&module.body, file, &mut diagnostics);
+    eval_expr(&module.export, file, &mut diagnostics);
+    diagnostics
+}
+
+fn eval_statement(statement: &Statement, file: Symbol, diagnostics: &mut Vec<Diagnostic>) {
+    match statement {
+        Statement::Use(_, expr) | Statement::Let(_, expr) => {
+            eval_expr(expr, file, diagnostics);
+        }
+        Statement::Expr(expr) => {
+            eval_expr(expr, file, diagnostics);
+        }
+    }
+}
+
+fn eval_member(member: &TupleMember, file: Symbol, diagnostics: &mut Vec<Diagnostic>) -> Option<ConstValue> {
+    match member {
+        TupleMember::Positional(value) => eval_expr(value, file, diagnostics),
+        TupleMember::Named(_, value) => {
+            eval_expr(value, file, diagnostics);
+            None
+        }
+    }
+}
+
+fn eval_expr(expr: &Expr, file: Symbol, diagnostics: &mut Vec<Diagnostic>) -> Option<ConstValue> {
+    match expr {
+        Expr::Int(value) => Some(ConstValue::Int(*value)),
+        Expr::Float(value) => Some(ConstValue::Float(*value)),
+        Expr::Block(statements) => {
+            for statement in *statements {
+                eval_statement(statement, file, diagnostics);
+            }
+            None
+        }
+        Expr::If(condition, statements) | Expr::For(condition, statements) => {
+            eval_expr(condition, file, diagnostics);
+            for statement in *statements {
+                eval_statement(statement, file, diagnostics);
+            }
+            None
+        }
+        Expr::Tuple(members) => {
+            for member in *members {
+                eval_member(member, file, diagnostics);
+            }
+            None
+        }
+        Expr::Vector(elements) => {
+            for element in *elements {
+                eval_expr(element, file, diagnostics);
+            }
+            check_vector_literal(elements, file, diagnostics);
+            None
+        }
+        Expr::Call(callee, args) => {
+            let positional: Vec<Option<ConstValue>> = args
+                .iter()
+                .map(|arg| eval_member(arg, file, diagnostics))
+                .collect();
+            if let Expr::BuiltIn(builtin) = callee {
+                check_index_call(builtin, args, file, diagnostics);
+                return fold_builtin(builtin, &positional, file, diagnostics);
+            }
+            None
+        }
+        Expr::Function(_, body) => {
+            eval_expr(body, file, diagnostics);
+            None
+        }
+        Expr::Void
+        | Expr::Nil
+        | Expr::Identifier(_)
+        | Expr::Symbol(_)
+        | Expr::String(_)
+        | Expr::BuiltIn(_) => None,
+    }
+}
+
+fn check_vector_literal(elements: &[Expr], file: Symbol, diagnostics: &mut Vec<Diagnostic>) {
+    let mut expected = None;
+    for element in elements {
+        let Some(found) = literal_type_name(element) else {
+            continue;
+        };
+        match expected {
+            None => expected = Some(found),
+            Some(expected) if expected != found => {
+                diagnostics.push(Diagnostic::error(
+                    format!("pushing invalid type: expected `{expected}`, found `{found}`"),
+                    placeholder_span(file),
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+}
+
+fn check_index_call(
+    builtin: &BuiltIn,
+    args: &[TupleMember],
+    file: Symbol,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    if *builtin != BuiltIn::Index {
+        return;
+    }
+    let mut positional = args.iter().filter_map(|arg| match arg {
+        TupleMember::Positional(value) => Some(value),
+        TupleMember::Named(_, _) => None,
+    });
+    let (Some(target), Some(index_expr)) = (positional.next(), positional.next()) else {
+        return;
+    };
+    let Expr::Vector(elements) = target else {
+        return;
+    };
+    let Expr::Int(index) = index_expr else {
+        return;
+    };
+    let len = elements.len() as i64;
+    if *index < 0 || *index >= len {
+        diagnostics.push(Diagnostic::error(
+            format!("index out of range: the index is {index} but the vector has {len} elements"),
+            placeholder_span(file),
+        ));
+    }
+}
+
+fn fold_builtin(
+    builtin: &BuiltIn,
+    positional: &[Option<ConstValue>],
+    file: Symbol,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Option<ConstValue> {
+    if !matches!(
+        builtin,
+        BuiltIn::Add | BuiltIn::Sub | BuiltIn::Mul | BuiltIn::Div | BuiltIn::Mod | BuiltIn::Exp
+    ) {
+        return None;
+    }
+    let left = positional.first().copied().flatten()?;
+    let right = positional.get(1).copied().flatten()?;
+    match (left, right) {
+        (ConstValue::Int(left), ConstValue::Int(right)) => {
+            fold_int(builtin, left, right, file, diagnostics).map(ConstValue::Int)
+        }
+        (ConstValue::Float(left), ConstValue::Float(right)) => {
+            fold_float(builtin, left, right, file, diagnostics).map(ConstValue::Float)
+        }
+        _ => None,
+    }
+}
+
+fn fold_int(
+    builtin: &BuiltIn,
+    left: i64,
+    right: i64,
+    file: Symbol,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Option<i64> {
+    let result = match builtin {
+        BuiltIn::Add => left.checked_add(right),
+        BuiltIn::Sub => left.checked_sub(right),
+        BuiltIn::Mul => left.checked_mul(right),
+        BuiltIn::Div => {
+            if right == 0 {
+                diagnostics.push(Diagnostic::error(
+                    "attempt to divide by zero",
+                    placeholder_span(file),
+                ));
+                return None;
+            }
+            left.checked_div(right)
+        }
+        BuiltIn::Mod => {
+            if right == 0 {
+                diagnostics.push(Diagnostic::error(
+                    "attempt to calculate the remainder with a divisor of zero",
+                    placeholder_span(file),
+                ));
+                return None;
+            }
+            left.checked_rem(right)
+        }
+        BuiltIn::Exp => u32::try_from(right)
+            .ok()
+            .and_then(|exponent| left.checked_pow(exponent)),
+        BuiltIn::Index | BuiltIn::Convert(_) => {
+            unreachable!("non-arithmetic builtins are filtered out before fold_int is called")
+        }
+    };
+    if result.is_none() {
+        diagnostics.push(Diagnostic::error(
+            "this arithmetic operation will overflow",
+            placeholder_span(file),
+        ));
+    }
+    result
+}
+
+fn fold_float(
+    builtin: &BuiltIn,
+    left: f64,
+    right: f64,
+    file: Symbol,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Option<f64> {
+    match builtin {
+        BuiltIn::Add => Some(left + right),
+        BuiltIn::Sub => Some(left - right),
+        BuiltIn::Mul => Some(left * right),
+        BuiltIn::Div => {
+            if right == 0.0 {
+                diagnostics.push(Diagnostic::error(
+                    "attempt to divide by zero",
+                    placeholder_span(file),
+                ));
+                None
+            } else {
+                Some(left / right)
+            }
+        }
+        BuiltIn::Mod => {
+            if right == 0.0 {
+                diagnostics.push(Diagnostic::error(
+                    "attempt to calculate the remainder with a divisor of zero",
+                    placeholder_span(file),
+                ));
+                None
+            } else {
+                Some(left % right)
+            }
+        }
+        BuiltIn::Exp => Some(left.powf(right)),
+        BuiltIn::Index | BuiltIn::Convert(_) => {
+            unreachable!("non-arithmetic builtins are filtered out before fold_float is called")
+        }
+    }
+}
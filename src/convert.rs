@@ -0,0 +1,305 @@
+//! Runtime value coercion backing `BuiltIn::Convert`. Converts between the
+//! handful of scalar runtime types a Vamp program can hold, including
+//! string parsing of integers, floats, booleans, and timestamps.
+
+use crate::ast::{Diagnostic, Span};
+use crate::symbol::Symbol;
+use std::str::FromStr;
+
+/// A scalar runtime value, as consumed and produced by conversions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    String(String),
+    Timestamp(Timestamp),
+}
+
+/// A parsed calendar timestamp, optionally carrying a UTC offset in minutes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timestamp {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub offset_minutes: Option<i32>,
+}
+
+/// The target type or format of a value coercion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Conversion {
+    Int,
+    Float,
+    Bool,
+    String,
+    Timestamp,
+    TimestampFmt(String),
+    TimestampTzFmt(String),
+}
+
+impl FromStr for Conversion {
+    type Err = String;
+
+    /// Parses the bare conversion names a program can write, e.g. in
+    /// `as 'int'`. The `TimestampFmt`/`TimestampTzFmt` variants carry a
+    /// format pattern and are constructed directly rather than by name.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "int" | "integer" => Ok(Conversion::Int),
+            "float" => Ok(Conversion::Float),
+            "bool" | "boolean" => Ok(Conversion::Bool),
+            "string" | "bytes" => Ok(Conversion::String),
+            "timestamp" => Ok(Conversion::Timestamp),
+            _ => Err(format!("unknown conversion: `{name}`")),
+        }
+    }
+}
+
+fn placeholder_span(file: Symbol) -> Span {
+    Span {
+        file,
+        start: 0,
+        end: 0,
+    }
+}
+
+/// Applies `conversion` to `value`, producing a typed value or a diagnostic
+/// explaining why the value couldn't be coerced.
+pub fn convert(value: &Value, conversion: &Conversion, file: Symbol) -> Result<Value, Diagnostic> {
+    match conversion {
+        Conversion::Int => to_int(value, file),
+        Conversion::Float => to_float(value, file),
+        Conversion::Bool => to_bool(value, file),
+        Conversion::String => Ok(Value::String(display(value))),
+        Conversion::Timestamp => parse_timestamp(value, None, false, file),
+        Conversion::TimestampFmt(pattern) => parse_timestamp(value, Some(pattern), false, file),
+        Conversion::TimestampTzFmt(pattern) => parse_timestamp(value, Some(pattern), true, file),
+    }
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::Int(n) => n.to_string(),
+        Value::Float(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::String(s) => s.clone(),
+        Value::Timestamp(ts) => format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            ts.year, ts.month, ts.day, ts.hour, ts.minute, ts.second
+        ),
+    }
+}
+
+fn to_int(value: &Value, file: Symbol) -> Result<Value, Diagnostic> {
+    match value {
+        Value::Int(n) => Ok(Value::Int(*n)),
+        Value::Float(n) => Ok(Value::Int(*n as i64)),
+        Value::Bool(b) => Ok(Value::Int(*b as i64)),
+        Value::String(s) => i64::from_str(s.trim()).map(Value::Int).map_err(|_| {
+            Diagnostic::error(format!("cannot convert \"{s}\" to int"), placeholder_span(file))
+        }),
+        Value::Timestamp(_) => Err(Diagnostic::error(
+            "cannot convert a timestamp to int",
+            placeholder_span(file),
+        )),
+    }
+}
+
+fn to_float(value: &Value, file: Symbol) -> Result<Value, Diagnostic> {
+    match value {
+        Value::Int(n) => Ok(Value::Float(*n as f64)),
+        Value::Float(n) => Ok(Value::Float(*n)),
+        Value::Bool(b) => Ok(Value::Float(if *b { 1.0 } else { 0.0 })),
+        Value::String(s) => f64::from_str(s.trim()).map(Value::Float).map_err(|_| {
+            Diagnostic::error(
+                format!("cannot convert \"{s}\" to float"),
+                placeholder_span(file),
+            )
+        }),
+        Value::Timestamp(_) => Err(Diagnostic::error(
+            "cannot convert a timestamp to float",
+            placeholder_span(file),
+        )),
+    }
+}
+
+fn to_bool(value: &Value, file: Symbol) -> Result<Value, Diagnostic> {
+    match value {
+        Value::Bool(b) => Ok(Value::Bool(*b)),
+        Value::Int(n) => Ok(Value::Bool(*n != 0)),
+        Value::String(s) => match s.to_ascii_lowercase().as_str() {
+            "true" | "t" | "yes" | "y" | "1" => Ok(Value::Bool(true)),
+            "false" | "f" | "no" | "n" | "0" => Ok(Value::Bool(false)),
+            _ => Err(Diagnostic::error(
+                format!("cannot convert \"{s}\" to bool"),
+                placeholder_span(file),
+            )),
+        },
+        Value::Float(_) | Value::Timestamp(_) => Err(Diagnostic::error(
+            "cannot convert value to bool",
+            placeholder_span(file),
+        )),
+    }
+}
+
+/// A cursor over the ASCII bytes of a timestamp string, used by both the
+/// default RFC-3339 parser and the format-pattern parser below.
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(text: &'a str) -> Self {
+        Cursor {
+            bytes: text.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn byte(&mut self, expected: u8) -> bool {
+        if self.bytes.get(self.pos) == Some(&expected) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn digits(&mut self, count: usize) -> Option<i64> {
+        let slice = self.bytes.get(self.pos..self.pos + count)?;
+        if !slice.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        self.pos += count;
+        std::str::from_utf8(slice).ok()?.parse().ok()
+    }
+
+    fn offset_minutes(&mut self) -> Option<Option<i32>> {
+        if self.byte(b'Z') {
+            return Some(Some(0));
+        }
+        let sign = if self.byte(b'+') {
+            1
+        } else if self.byte(b'-') {
+            -1
+        } else {
+            return Some(None);
+        };
+        let hours = self.digits(2)?;
+        self.byte(b':');
+        let minutes = self.digits(2)?;
+        Some(Some(sign * (hours * 60 + minutes) as i32))
+    }
+}
+
+fn parse_rfc3339(text: &str, file: Symbol) -> Result<Timestamp, Diagnostic> {
+    let fail = || {
+        Diagnostic::error(
+            format!("cannot parse \"{text}\" as an RFC-3339 timestamp"),
+            placeholder_span(file),
+        )
+    };
+    let mut cursor = Cursor::new(text);
+    let year = cursor.digits(4).ok_or_else(fail)?;
+    if !cursor.byte(b'-') {
+        return Err(fail());
+    }
+    let month = cursor.digits(2).ok_or_else(fail)?;
+    if !cursor.byte(b'-') {
+        return Err(fail());
+    }
+    let day = cursor.digits(2).ok_or_else(fail)?;
+    if !(cursor.byte(b'T') || cursor.byte(b't') || cursor.byte(b' ')) {
+        return Err(fail());
+    }
+    let hour = cursor.digits(2).ok_or_else(fail)?;
+    if !cursor.byte(b':') {
+        return Err(fail());
+    }
+    let minute = cursor.digits(2).ok_or_else(fail)?;
+    if !cursor.byte(b':') {
+        return Err(fail());
+    }
+    let second = cursor.digits(2).ok_or_else(fail)?;
+    let offset_minutes = cursor.offset_minutes().ok_or_else(fail)?;
+    Ok(Timestamp {
+        year: year as i32,
+        month: month as u8,
+        day: day as u8,
+        hour: hour as u8,
+        minute: minute as u8,
+        second: second as u8,
+        offset_minutes,
+    })
+}
+
+/// Parses `text` against a `chrono`-style format pattern made of `%Y` `%m`
+/// `%d` `%H` `%M` `%S` `%z` directives and literal separators.
+fn parse_with_pattern(text: &str, pattern: &str, file: Symbol) -> Result<Timestamp, Diagnostic> {
+    let fail = || {
+        Diagnostic::error(
+            format!("\"{text}\" does not match format \"{pattern}\""),
+            placeholder_span(file),
+        )
+    };
+    let mut cursor = Cursor::new(text);
+    let mut timestamp = Timestamp {
+        year: 0,
+        month: 1,
+        day: 1,
+        hour: 0,
+        minute: 0,
+        second: 0,
+        offset_minutes: None,
+    };
+    let mut directives = pattern.chars().peekable();
+    while let Some(c) = directives.next() {
+        if c == '%' {
+            match directives.next().ok_or_else(fail)? {
+                'Y' => timestamp.year = cursor.digits(4).ok_or_else(fail)? as i32,
+                'm' => timestamp.month = cursor.digits(2).ok_or_else(fail)? as u8,
+                'd' => timestamp.day = cursor.digits(2).ok_or_else(fail)? as u8,
+                'H' => timestamp.hour = cursor.digits(2).ok_or_else(fail)? as u8,
+                'M' => timestamp.minute = cursor.digits(2).ok_or_else(fail)? as u8,
+                'S' => timestamp.second = cursor.digits(2).ok_or_else(fail)? as u8,
+                'z' => timestamp.offset_minutes = cursor.offset_minutes().ok_or_else(fail)?,
+                other => return Err(Diagnostic::error(
+                    format!("unsupported format directive `%{other}`"),
+                    placeholder_span(file),
+                )),
+            }
+        } else if !cursor.byte(c as u8) {
+            return Err(fail());
+        }
+    }
+    Ok(timestamp)
+}
+
+fn parse_timestamp(
+    value: &Value,
+    pattern: Option<&str>,
+    require_offset: bool,
+    file: Symbol,
+) -> Result<Value, Diagnostic> {
+    let Value::String(text) = value else {
+        return Err(Diagnostic::error(
+            "cannot parse a non-string value as a timestamp",
+            placeholder_span(file),
+        ));
+    };
+    let timestamp = match pattern {
+        Some(pattern) => parse_with_pattern(text, pattern, file)?,
+        None => parse_rfc3339(text, file)?,
+    };
+    if require_offset && timestamp.offset_minutes.is_none() {
+        return Err(Diagnostic::error(
+            "timestamp is missing a required UTC offset",
+            placeholder_span(file),
+        ));
+    }
+    Ok(Value::Timestamp(timestamp))
+}
@@ -0,0 +1,59 @@
+//! A directed graph of which modules import which, built from each parsed
+//! `Module`'s `imports` list. `watch()` uses it to turn a changed file into
+//! exactly the set of modules that need to be re-evaluated: the file itself
+//! plus every module that transitively depends on it.
+
+use crate::ast::Import;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+#[derive(Default)]
+pub struct DependencyGraph {
+    // dependents[dependency] = modules that import `dependency`.
+    dependents: HashMap<PathBuf, HashSet<PathBuf>>,
+}
+
+impl DependencyGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the imports a freshly parsed module declares, resolving each
+    /// import path relative to the module's own directory. Replaces any
+    /// edges previously recorded for `module_path`.
+    pub fn record_imports(&mut self, module_path: &Path, imports: &[Import<'_>]) {
+        self.forget(module_path);
+        let base = module_path.parent().unwrap_or_else(|| Path::new(""));
+        for import in imports {
+            let dependency = base.join(import.1);
+            self.dependents
+                .entry(dependency)
+                .or_default()
+                .insert(module_path.to_path_buf());
+        }
+    }
+
+    fn forget(&mut self, module_path: &Path) {
+        for dependents in self.dependents.values_mut() {
+            dependents.remove(module_path);
+        }
+    }
+
+    /// Returns `changed` plus every module that transitively depends on any
+    /// path in it, so a burst of saves can be invalidated in one wave.
+    pub fn invalidate(&self, changed: &[PathBuf]) -> Vec<PathBuf> {
+        let mut wave: HashSet<PathBuf> = changed.iter().cloned().collect();
+        let mut frontier: Vec<PathBuf> = changed.to_vec();
+        while let Some(path) = frontier.pop() {
+            let Some(dependents) = self.dependents.get(&path) else {
+                continue;
+            };
+            for dependent in dependents {
+                if wave.insert(dependent.clone()) {
+                    frontier.push(dependent.clone());
+                }
+            }
+        }
+        wave.into_iter().collect()
+    }
+}
@@ -1,20 +1,752 @@
-use crate::parse::parse;
+use crate::builtins;
+use crate::parse::{self, Expr, Import, Let, OperatorKind, Pattern, PatternTuple, Spanned, Tuple};
+use crate::source::{Position, Span};
+use std::collections::HashMap;
 
-pub struct Environment {}
+/// A zero-width span at the start of the source, used for the implicit
+/// root `Block` `parse::parse` hands back as a bare `Expr` with no span
+/// of its own.
+fn root_span() -> Span {
+    let origin = Position {
+        offset: 0,
+        line: 1,
+        column: 1,
+    };
+    Span {
+        start: origin,
+        end: origin,
+    }
+}
+
+/// A runtime value produced by evaluating an `Expr`. Mirrors the shape of
+/// the `Expr`/`Tuple` nodes it's built from, minus anything (functions,
+/// maps) evaluation doesn't support yet.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Void,
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Tag(String),
+    Vector(Vec<Value>),
+    Tuple(ValueTuple),
+    /// `start..end`, `start..=end`, or the open-ended `start..` (`end`
+    /// is `None`). See `Value::into_vector` to materialize the finite
+    /// forms into a `Vector`.
+    Range {
+        start: i64,
+        end: Option<i64>,
+        inclusive: bool,
+    },
+}
+
+impl Value {
+    /// Expands a finite range into the `Integer`s it denotes, e.g. `0..3`
+    /// into `[0, 1, 2]`. Fails on an open-ended range, which has no finite
+    /// vector form, or on any non-range value.
+    pub fn into_vector(self, span: Span) -> Result<Vec<Value>> {
+        match self {
+            Value::Range {
+                start,
+                end: Some(end),
+                inclusive,
+            } => {
+                let end = if inclusive { end + 1 } else { end };
+                Ok((start..end).map(Value::Integer).collect())
+            }
+            Value::Range { end: None, .. } => Err(EvalError {
+                kind: EvalErrorKind::Unsupported("open-ended range has no finite vector form"),
+                span,
+            }),
+            _ => Err(EvalError {
+                kind: EvalErrorKind::TypeMismatch,
+                span,
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValueTuple {
+    pub tag: Option<String>,
+    pub positional: Vec<Value>,
+    pub named: Vec<(String, Value)>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum EvalErrorKind {
+    /// No binding for this name is visible in the current scope chain.
+    UnboundIdentifier(String),
+    /// An operator or pattern was applied to a `Value` it doesn't support.
+    TypeMismatch,
+    DivisionByZero,
+    /// A `let` pattern's shape (tag, arity, or named fields) didn't match
+    /// the value it was destructuring.
+    PatternMismatch,
+    /// A construct `eval` doesn't evaluate yet (maps, functions, calls to
+    /// anything but a built-in).
+    Unsupported(&'static str),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct EvalError {
+    pub kind: EvalErrorKind,
+    pub span: Span,
+}
+
+pub type Result<T> = std::result::Result<T, EvalError>;
+
+/// A chain of scopes, innermost last: `Block`s push a fresh scope for their
+/// `let`s and pop it once their trailing expressions are evaluated, while
+/// lookups walk the chain from the innermost scope outward.
+pub struct Environment {
+    scopes: Vec<HashMap<String, Value>>,
+}
+
+impl Default for Environment {
+    fn default() -> Self {
+        Environment {
+            scopes: vec![HashMap::new()],
+        }
+    }
+}
 
 impl Environment {
-    pub fn new() -> Environment {
-        Environment {}
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn define(&mut self, name: String, value: Value) {
+        self.scopes
+            .last_mut()
+            .expect("there is always at least one scope")
+            .insert(name, value);
+    }
+
+    fn lookup(&self, name: &str) -> Option<&Value> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
     }
 
     pub fn eval(&mut self, namespace: &str, source: &str) {
-        match parse(source) {
-            Ok(expr) => {
-                println!("parsed: {:?}", expr)
+        let _ = namespace;
+        let (expr, errors) = parse::parse(source);
+        for error in errors {
+            println!("error: {:?}", error)
+        }
+        let root = Spanned {
+            node: expr,
+            span: root_span(),
+        };
+        match eval(&root, self) {
+            Ok(value) => println!("{:?}", value),
+            Err(error) => println!("eval error: {:?}", error),
+        }
+    }
+}
+
+/// Evaluates `expr` against `env`, mutating `env`'s innermost scope as
+/// `Block`s introduce `let` bindings. Errors carry `expr.span` (or, for a
+/// range endpoint, a repeat count, or a call's callee, the span of that
+/// sub-expression) so they point back at the offending source text.
+pub fn eval(expr: &Spanned<Expr>, env: &mut Environment) -> Result<Value> {
+    let span = expr.span;
+    match &expr.node {
+        Expr::Void | Expr::Nil => Ok(Value::Void),
+        Expr::Integer(integer) => Ok(Value::Integer(*integer)),
+        Expr::Float(float) => Ok(Value::Float(*float)),
+        Expr::String(string) => Ok(Value::String(string.clone())),
+        Expr::Tag(tag) => Ok(Value::Tag(tag.clone())),
+        Expr::Identifier(name) => env.lookup(name).cloned().ok_or(EvalError {
+            kind: EvalErrorKind::UnboundIdentifier(name.clone()),
+            span,
+        }),
+        Expr::Vector(elements) => {
+            let mut values = Vec::with_capacity(elements.len());
+            for element in elements {
+                values.push(eval(element, env)?);
+            }
+            Ok(Value::Vector(values))
+        }
+        Expr::Tuple(tuple) => eval_tuple(tuple, env),
+        Expr::Range {
+            start,
+            end,
+            inclusive,
+        } => eval_range(start, end.as_deref(), *inclusive, env),
+        Expr::VectorRepeat { element, count } => eval_vector_repeat(element, count, env),
+        Expr::Operator(kind, operands) => eval_operator(kind, operands, span, env),
+        Expr::Block(imports, lets, exprs) => eval_block(imports, lets, exprs, env),
+        Expr::Map(_) => Err(EvalError {
+            kind: EvalErrorKind::Unsupported("map"),
+            span,
+        }),
+        Expr::Function(..) => Err(EvalError {
+            kind: EvalErrorKind::Unsupported("function"),
+            span,
+        }),
+        Expr::Call(callee, args) => eval_call(callee, args, span, env),
+        Expr::Error => Err(EvalError {
+            kind: EvalErrorKind::Unsupported("error node"),
+            span,
+        }),
+    }
+}
+
+fn eval_tuple(tuple: &Tuple, env: &mut Environment) -> Result<Value> {
+    let mut positional = Vec::with_capacity(tuple.positional.len());
+    for element in &tuple.positional {
+        positional.push(eval(element, env)?);
+    }
+    let mut named = Vec::with_capacity(tuple.named.len());
+    for (key, element) in &tuple.named {
+        named.push((key.clone(), eval(element, env)?));
+    }
+    Ok(Value::Tuple(ValueTuple {
+        tag: tuple.tag.clone(),
+        positional,
+        named,
+    }))
+}
+
+fn eval_range(
+    start_expr: &Spanned<Expr>,
+    end_expr: Option<&Spanned<Expr>>,
+    inclusive: bool,
+    env: &mut Environment,
+) -> Result<Value> {
+    let start = match eval(start_expr, env)? {
+        Value::Integer(start) => start,
+        _ => {
+            return Err(EvalError {
+                kind: EvalErrorKind::TypeMismatch,
+                span: start_expr.span,
+            })
+        }
+    };
+    let end = match end_expr {
+        Some(end_expr) => match eval(end_expr, env)? {
+            Value::Integer(end) => Some(end),
+            _ => {
+                return Err(EvalError {
+                    kind: EvalErrorKind::TypeMismatch,
+                    span: end_expr.span,
+                })
+            }
+        },
+        None => None,
+    };
+    Ok(Value::Range {
+        start,
+        end,
+        inclusive,
+    })
+}
+
+fn eval_vector_repeat(
+    element: &Spanned<Expr>,
+    count: &Spanned<Expr>,
+    env: &mut Environment,
+) -> Result<Value> {
+    let count = match eval(count, env)? {
+        Value::Integer(count) if count >= 0 => count,
+        _ => {
+            return Err(EvalError {
+                kind: EvalErrorKind::TypeMismatch,
+                span: count.span,
+            })
+        }
+    };
+    let value = eval(element, env)?;
+    Ok(Value::Vector(vec![value; count as usize]))
+}
+
+fn eval_operator(
+    kind: &OperatorKind,
+    operands: &[Spanned<Expr>],
+    span: Span,
+    env: &mut Environment,
+) -> Result<Value> {
+    let type_mismatch = EvalError {
+        kind: EvalErrorKind::TypeMismatch,
+        span,
+    };
+    match kind {
+        OperatorKind::Negate => match eval(&operands[0], env)? {
+            Value::Integer(value) => Ok(Value::Integer(-value)),
+            Value::Float(value) => Ok(Value::Float(-value)),
+            _ => Err(type_mismatch),
+        },
+        OperatorKind::Not => match eval(&operands[0], env)? {
+            Value::Bool(value) => Ok(Value::Bool(!value)),
+            _ => Err(type_mismatch),
+        },
+        OperatorKind::And => match eval(&operands[0], env)? {
+            Value::Bool(false) => Ok(Value::Bool(false)),
+            Value::Bool(true) => eval(&operands[1], env),
+            _ => Err(type_mismatch),
+        },
+        OperatorKind::Or => match eval(&operands[0], env)? {
+            Value::Bool(true) => Ok(Value::Bool(true)),
+            Value::Bool(false) => eval(&operands[1], env),
+            _ => Err(type_mismatch),
+        },
+        OperatorKind::Equal | OperatorKind::NotEqual => {
+            let lhs = eval(&operands[0], env)?;
+            let rhs = eval(&operands[1], env)?;
+            let equal = lhs == rhs;
+            Ok(Value::Bool(if *kind == OperatorKind::Equal {
+                equal
+            } else {
+                !equal
+            }))
+        }
+        OperatorKind::Less
+        | OperatorKind::LessEqual
+        | OperatorKind::Greater
+        | OperatorKind::GreaterEqual => {
+            let lhs = eval(&operands[0], env)?;
+            let rhs = eval(&operands[1], env)?;
+            let ordering = match (&lhs, &rhs) {
+                (Value::Integer(a), Value::Integer(b)) => a.partial_cmp(b),
+                (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+                _ => return Err(type_mismatch),
+            }
+            .ok_or(EvalError {
+                kind: EvalErrorKind::TypeMismatch,
+                span,
+            })?;
+            Ok(Value::Bool(match kind {
+                OperatorKind::Less => ordering.is_lt(),
+                OperatorKind::LessEqual => ordering.is_le(),
+                OperatorKind::Greater => ordering.is_gt(),
+                OperatorKind::GreaterEqual => ordering.is_ge(),
+                _ => unreachable!(),
+            }))
+        }
+        OperatorKind::Add
+        | OperatorKind::Subtract
+        | OperatorKind::Multiply
+        | OperatorKind::Divide
+        | OperatorKind::Modulo
+        | OperatorKind::Exponent => {
+            let lhs = eval(&operands[0], env)?;
+            let rhs = eval(&operands[1], env)?;
+            eval_arithmetic(kind, lhs, rhs, span)
+        }
+    }
+}
+
+fn eval_arithmetic(kind: &OperatorKind, lhs: Value, rhs: Value, span: Span) -> Result<Value> {
+    let type_mismatch = EvalError {
+        kind: EvalErrorKind::TypeMismatch,
+        span,
+    };
+    let division_by_zero = EvalError {
+        kind: EvalErrorKind::DivisionByZero,
+        span,
+    };
+    match (lhs, rhs) {
+        (Value::Integer(a), Value::Integer(b)) => Ok(Value::Integer(match kind {
+            OperatorKind::Add => a + b,
+            OperatorKind::Subtract => a - b,
+            OperatorKind::Multiply => a * b,
+            OperatorKind::Divide => {
+                if b == 0 {
+                    return Err(division_by_zero);
+                }
+                a / b
+            }
+            OperatorKind::Modulo => {
+                if b == 0 {
+                    return Err(division_by_zero);
+                }
+                a % b
+            }
+            OperatorKind::Exponent => a.pow(b as u32),
+            _ => unreachable!(),
+        })),
+        (Value::Float(a), Value::Float(b)) => Ok(Value::Float(match kind {
+            OperatorKind::Add => a + b,
+            OperatorKind::Subtract => a - b,
+            OperatorKind::Multiply => a * b,
+            OperatorKind::Divide => a / b,
+            OperatorKind::Modulo => a % b,
+            OperatorKind::Exponent => a.powf(b),
+            _ => unreachable!(),
+        })),
+        _ => Err(type_mismatch),
+    }
+}
+
+/// Pushes a new scope, binds each `let` pattern over its evaluated RHS (see
+/// `bind_pattern`), evaluates the trailing expressions in order, and pops
+/// the scope before returning the last expression's value (or `Value::Void`
+/// if the block has none).
+fn eval_block(
+    imports: &[Import],
+    lets: &[Let],
+    exprs: &[Spanned<Expr>],
+    env: &mut Environment,
+) -> Result<Value> {
+    env.push_scope();
+    // Imports don't resolve to real modules yet, so each one is bound to a
+    // placeholder `Value::Void` under its local name, a stand-in for a
+    // stub module table until cross-module evaluation exists.
+    for Import(name, _path) in imports {
+        env.define(name.clone(), Value::Void);
+    }
+    for Let(pattern, rhs) in lets {
+        let value = match eval(rhs, env) {
+            Ok(value) => value,
+            Err(error) => {
+                env.pop_scope();
+                return Err(error);
             }
+        };
+        if let Err(error) = bind_pattern(&pattern.node, value, pattern.span, env) {
+            env.pop_scope();
+            return Err(error);
+        }
+    }
+    let mut result = Value::Void;
+    for expr in exprs {
+        match eval(expr, env) {
+            Ok(value) => result = value,
             Err(error) => {
-                println!("error: {:?}", error)
+                env.pop_scope();
+                return Err(error);
             }
         }
     }
-}
\ No newline at end of file
+    env.pop_scope();
+    Ok(result)
+}
+
+/// Evaluates a `Call`: only a bare identifier callee is supported today
+/// (user-defined functions aren't values yet, so nothing else can be
+/// called), and it's first checked against `env` so a local binding of the
+/// same name shadows a built-in, then dispatched through `builtins::lookup`.
+fn eval_call(
+    callee: &Spanned<Expr>,
+    args: &[Spanned<Expr>],
+    span: Span,
+    env: &mut Environment,
+) -> Result<Value> {
+    let name = match &callee.node {
+        Expr::Identifier(name) => name,
+        _ => {
+            return Err(EvalError {
+                kind: EvalErrorKind::Unsupported("call to a non-identifier callee"),
+                span: callee.span,
+            })
+        }
+    };
+    if env.lookup(name).is_some() {
+        return Err(EvalError {
+            kind: EvalErrorKind::Unsupported("calling a user-defined binding"),
+            span: callee.span,
+        });
+    }
+    let builtin = builtins::lookup(name).ok_or_else(|| EvalError {
+        kind: EvalErrorKind::UnboundIdentifier(name.clone()),
+        span: callee.span,
+    })?;
+    let mut values = Vec::with_capacity(args.len());
+    for arg in args {
+        values.push(eval(arg, env)?);
+    }
+    builtin(&values, span)
+}
+
+/// Destructures `value` against `pattern`, binding every leaf identifier
+/// (see `parse::bound_idents`) into `env`'s innermost scope. `span` is the
+/// pattern's own span, attached to any `PatternMismatch` this raises.
+fn bind_pattern(pattern: &Pattern, value: Value, span: Span, env: &mut Environment) -> Result<()> {
+    let pattern_mismatch = EvalError {
+        kind: EvalErrorKind::PatternMismatch,
+        span,
+    };
+    match pattern {
+        Pattern::Wildcard => Ok(()),
+        Pattern::Identifier(name) => {
+            env.define(name.clone(), value);
+            Ok(())
+        }
+        Pattern::Tag(tag) => match &value {
+            Value::Tag(value_tag) if value_tag == tag => Ok(()),
+            _ => Err(pattern_mismatch),
+        },
+        Pattern::Tuple(pattern_tuple) => bind_tuple_pattern(pattern_tuple, value, span, env),
+        Pattern::Vector(patterns) => match value {
+            Value::Vector(values) if values.len() == patterns.len() => {
+                for (sub_pattern, sub_value) in patterns.iter().zip(values) {
+                    bind_pattern(&sub_pattern.node, sub_value, sub_pattern.span, env)?;
+                }
+                Ok(())
+            }
+            _ => Err(pattern_mismatch),
+        },
+    }
+}
+
+fn bind_tuple_pattern(
+    pattern_tuple: &PatternTuple,
+    value: Value,
+    span: Span,
+    env: &mut Environment,
+) -> Result<()> {
+    let pattern_mismatch = EvalError {
+        kind: EvalErrorKind::PatternMismatch,
+        span,
+    };
+    let value_tuple = match value {
+        Value::Tuple(value_tuple) => value_tuple,
+        _ => return Err(pattern_mismatch),
+    };
+    if pattern_tuple.tag.is_some() && pattern_tuple.tag != value_tuple.tag {
+        return Err(pattern_mismatch);
+    }
+    if pattern_tuple.positional.len() != value_tuple.positional.len() {
+        return Err(pattern_mismatch);
+    }
+    for (sub_pattern, sub_value) in pattern_tuple.positional.iter().zip(value_tuple.positional) {
+        bind_pattern(&sub_pattern.node, sub_value, sub_pattern.span, env)?;
+    }
+    for (name, sub_pattern) in &pattern_tuple.named {
+        let sub_value = value_tuple
+            .named
+            .iter()
+            .find(|(field_name, _)| field_name == name)
+            .map(|(_, field_value)| field_value.clone())
+            .ok_or(EvalError {
+                kind: EvalErrorKind::PatternMismatch,
+                span,
+            })?;
+        bind_pattern(&sub_pattern.node, sub_value, sub_pattern.span, env)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval_str(source: &str) -> Result<Value> {
+        let (expr, errors) = parse::parse(source);
+        assert!(errors.is_empty(), "parse errors: {:?}", errors);
+        let root = Spanned {
+            node: expr,
+            span: root_span(),
+        };
+        eval(&root, &mut Environment::new())
+    }
+
+    #[test]
+    fn test_eval_literals() {
+        assert_eq!(eval_str("1"), Ok(Value::Integer(1)));
+        assert_eq!(eval_str("1.5"), Ok(Value::Float(1.5)));
+        assert_eq!(eval_str(r#""hi""#), Ok(Value::String("hi".into())));
+        assert_eq!(eval_str("Tag"), Ok(Value::Tag("Tag".into())));
+        assert_eq!(eval_str("[1, 2, 3]"), Ok(Value::Vector(vec![
+            Value::Integer(1),
+            Value::Integer(2),
+            Value::Integer(3),
+        ])));
+    }
+
+    #[test]
+    fn test_eval_range() {
+        assert_eq!(
+            eval_str("0..3"),
+            Ok(Value::Range {
+                start: 0,
+                end: Some(3),
+                inclusive: false
+            })
+        );
+        assert_eq!(
+            eval_str("0..=3"),
+            Ok(Value::Range {
+                start: 0,
+                end: Some(3),
+                inclusive: true
+            })
+        );
+        assert_eq!(
+            eval_str("0.."),
+            Ok(Value::Range {
+                start: 0,
+                end: None,
+                inclusive: false
+            })
+        );
+        assert_eq!(
+            eval_str("{let len = 3, 0..len}"),
+            Ok(Value::Range {
+                start: 0,
+                end: Some(3),
+                inclusive: false
+            })
+        );
+    }
+
+    #[test]
+    fn test_range_into_vector() {
+        let span = root_span();
+        assert_eq!(
+            eval_str("0..3").unwrap().into_vector(span),
+            Ok(vec![Value::Integer(0), Value::Integer(1), Value::Integer(2)])
+        );
+        assert_eq!(
+            eval_str("0..=3").unwrap().into_vector(span),
+            Ok(vec![
+                Value::Integer(0),
+                Value::Integer(1),
+                Value::Integer(2),
+                Value::Integer(3)
+            ])
+        );
+        assert_eq!(
+            eval_str("0..3").unwrap().into_vector(span).unwrap().len(),
+            3
+        );
+        assert_eq!(
+            eval_str("0..").unwrap().into_vector(span).unwrap_err().kind,
+            EvalErrorKind::Unsupported("open-ended range has no finite vector form")
+        );
+    }
+
+    #[test]
+    fn test_eval_vector_repeat() {
+        assert_eq!(
+            eval_str("[1; 3]"),
+            Ok(Value::Vector(vec![
+                Value::Integer(1),
+                Value::Integer(1),
+                Value::Integer(1)
+            ]))
+        );
+        assert_eq!(eval_str("[1; 0]"), Ok(Value::Vector(vec![])));
+        assert_eq!(
+            eval_str("{let width = 2, [0; width]}"),
+            Ok(Value::Vector(vec![Value::Integer(0), Value::Integer(0)]))
+        );
+    }
+
+    #[test]
+    fn test_eval_arithmetic() {
+        assert_eq!(eval_str("1 + 2 * 3"), Ok(Value::Integer(7)));
+        assert_eq!(eval_str("7 % 2"), Ok(Value::Integer(1)));
+        assert_eq!(eval_str("2 ^ 10"), Ok(Value::Integer(1024)));
+        assert_eq!(eval_str("1.0 + 2.0"), Ok(Value::Float(3.0)));
+        assert_eq!(
+            eval_str("1 / 0").unwrap_err().kind,
+            EvalErrorKind::DivisionByZero
+        );
+    }
+
+    #[test]
+    fn test_eval_comparison_and_boolean_operators() {
+        assert_eq!(eval_str("1 < 2"), Ok(Value::Bool(true)));
+        assert_eq!(eval_str("1 == 1"), Ok(Value::Bool(true)));
+        assert_eq!(eval_str("1 != 2"), Ok(Value::Bool(true)));
+        assert_eq!(eval_str("1 < 2 && 2 < 1"), Ok(Value::Bool(false)));
+        assert_eq!(eval_str("1 > 2 || 2 < 3"), Ok(Value::Bool(true)));
+        assert_eq!(eval_str("{let b = 1 < 2, !b}"), Ok(Value::Bool(false)));
+        assert_eq!(eval_str("-5"), Ok(Value::Integer(-5)));
+    }
+
+    #[test]
+    fn test_eval_unbound_identifier() {
+        assert_eq!(
+            eval_str("x").unwrap_err().kind,
+            EvalErrorKind::UnboundIdentifier("x".into())
+        );
+    }
+
+    #[test]
+    fn test_eval_block_with_let() {
+        assert_eq!(
+            eval_str("{let x = 1, let y = 2, x + y}"),
+            Ok(Value::Integer(3))
+        );
+    }
+
+    #[test]
+    fn test_eval_destructuring_let() {
+        assert_eq!(
+            eval_str("{let (a, b) = (1, 2), a + b}"),
+            Ok(Value::Integer(3))
+        );
+        assert_eq!(
+            eval_str("{let [a, b, c] = [1, 2, 3], a + b + c}"),
+            Ok(Value::Integer(6))
+        );
+    }
+
+    #[test]
+    fn test_eval_block_scoping() {
+        // `x` bound inside the nested block doesn't leak into the outer
+        // scope, so looking it up afterward is an unbound identifier.
+        assert_eq!(
+            eval_str("{let y = {let x = 1, x}, x}").unwrap_err().kind,
+            EvalErrorKind::UnboundIdentifier("x".into())
+        );
+    }
+
+    #[test]
+    fn test_eval_builtin_calls() {
+        assert_eq!(eval_str("len([1, 2, 3])"), Ok(Value::Integer(3)));
+        assert_eq!(eval_str("is_empty([])"), Ok(Value::Bool(true)));
+        assert_eq!(eval_str("min([3, 1, 2])"), Ok(Value::Integer(1)));
+        assert_eq!(eval_str("max([3, 1, 2])"), Ok(Value::Integer(3)));
+        assert_eq!(
+            eval_str("concat([1, 2], [3, 4])"),
+            Ok(Value::Vector(vec![
+                Value::Integer(1),
+                Value::Integer(2),
+                Value::Integer(3),
+                Value::Integer(4),
+            ]))
+        );
+        assert_eq!(eval_str("fst((1, 2))"), Ok(Value::Integer(1)));
+        assert_eq!(eval_str("snd((1, 2))"), Ok(Value::Integer(2)));
+        assert_eq!(
+            eval_str("append((1, 2), 3)"),
+            Ok(Value::Tuple(ValueTuple {
+                tag: None,
+                positional: vec![Value::Integer(1), Value::Integer(2), Value::Integer(3)],
+                named: vec![],
+            }))
+        );
+        assert_eq!(
+            eval_str("prepend((1, 2), 0)"),
+            Ok(Value::Tuple(ValueTuple {
+                tag: None,
+                positional: vec![Value::Integer(0), Value::Integer(1), Value::Integer(2)],
+                named: vec![],
+            }))
+        );
+        assert_eq!(
+            eval_str("nope(1)").unwrap_err().kind,
+            EvalErrorKind::UnboundIdentifier("nope".into())
+        );
+    }
+
+    #[test]
+    fn test_eval_pattern_mismatch() {
+        assert_eq!(
+            eval_str("{let (a, b) = (1, 2, 3), a}").unwrap_err().kind,
+            EvalErrorKind::PatternMismatch
+        );
+    }
+}
@@ -1,14 +1,28 @@
+mod ast;
+mod builtins;
+mod compiler;
+mod const_eval;
+mod convert;
+mod depgraph;
 mod eval;
 mod parse;
 mod repl;
 mod source;
 mod symbol;
 mod tokens;
+mod vm;
 mod watch;
+use depgraph::DependencyGraph;
 use eval::Environment;
 use repl::repl;
 use source::SourceEvent;
-use std::{env, fs, io, path::Path, sync::mpsc, thread};
+use std::{
+    env, fs, io,
+    path::Path,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+};
+use symbol::Interner;
 use watch::watch;
 
 fn main() {
@@ -24,18 +38,23 @@ fn main() {
     };
 
     // Source watcher
+    let dependency_graph = Arc::new(Mutex::new(DependencyGraph::new()));
+    let repl_root = root_path.clone();
     thread::spawn({
         let sender = sender.clone();
+        let dependency_graph = dependency_graph.clone();
         move || {
-            if watch(&root_path, sender).is_err() {
+            if watch(&root_path, sender, dependency_graph).is_err() {
                 println!("error: could not watch filesystem events");
             }
         }
     });
 
     // REPL
+    let interner = Arc::new(Mutex::new(Interner::new()));
+    let known_exports = Arc::new(Mutex::new(Vec::new()));
     thread::spawn(move || {
-        repl(sender);
+        repl(sender, repl_root, interner, known_exports);
     });
 
     // Handle all source events.
@@ -1,33 +1,88 @@
-use crate::source::{Error, ErrorKind, Result, Span};
+use crate::source::{Error, ErrorKind, Position, Result, Span};
 use crate::tokens::{tokenize, Token, TokenKind};
 use std::collections::HashMap;
 
+/// Wraps a parsed node together with the span of source text it came from.
+/// Equality only compares `node`: spans exist so later stages (type errors,
+/// runtime traces) can point back at source, not to make every `#[test]`
+/// assertion spell out exact offsets, so two `Spanned<T>` are equal whenever
+/// their nodes are, regardless of where in the source each was parsed from.
+#[derive(Debug)]
+pub struct Spanned<T> {
+    pub node: T,
+    pub span: Span,
+}
+
+impl<T> Spanned<T> {
+    fn new(node: T, span: Span) -> Self {
+        Spanned { node, span }
+    }
+}
+
+impl<T: PartialEq> PartialEq for Spanned<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.node == other.node
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct PatternTuple {
     pub tag: Option<String>,
-    pub positional: Vec<Pattern>,
-    pub named: Vec<(String, Pattern)>,
+    pub positional: Vec<Spanned<Pattern>>,
+    pub named: Vec<(String, Spanned<Pattern>)>,
 }
 
 #[derive(Debug, PartialEq)]
 pub enum Pattern {
     Tuple(PatternTuple),
-    Vector(Vec<Pattern>),
+    Vector(Vec<Spanned<Pattern>>),
     Identifier(String),
     Tag(String),
+    /// `_`, matching anything without binding a name.
+    Wildcard,
+}
+
+/// Recursively collects every identifier a pattern binds, descending into
+/// tuple positional/named sub-patterns and vector elements, so a later
+/// name-resolution pass can enumerate what a `Let` introduces. `Tag` and
+/// `Wildcard` leaves bind nothing and contribute no entries.
+pub fn bound_idents(pattern: &Pattern) -> Vec<String> {
+    let mut idents = Vec::new();
+    collect_bound_idents(pattern, &mut idents);
+    idents
+}
+
+fn collect_bound_idents(pattern: &Pattern, idents: &mut Vec<String>) {
+    match pattern {
+        Pattern::Identifier(name) => idents.push(name.clone()),
+        Pattern::Tag(_) | Pattern::Wildcard => {}
+        Pattern::Tuple(tuple) => {
+            for positional in &tuple.positional {
+                collect_bound_idents(&positional.node, idents);
+            }
+            for (_, named) in &tuple.named {
+                collect_bound_idents(&named.node, idents);
+            }
+        }
+        Pattern::Vector(elements) => {
+            for element in elements {
+                collect_bound_idents(&element.node, idents);
+            }
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
-pub struct Import(String, String);
+pub struct Import(pub String, pub String);
 
 #[derive(Debug, PartialEq)]
-pub struct Let(Pattern, Box<Expr>);
+pub struct Let(pub Spanned<Pattern>, pub Box<Spanned<Expr>>);
 
 #[derive(Debug, PartialEq)]
 pub struct Tuple {
     pub tag: Option<String>,
-    pub positional: Vec<Expr>,
-    pub named: Vec<(String, Expr)>,
+    pub positional: Vec<Spanned<Expr>>,
+    pub named: Vec<(String, Spanned<Expr>)>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -36,30 +91,71 @@ pub enum OperatorKind {
     Subtract,
     Multiply,
     Divide,
+    Modulo,
+    Exponent,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    And,
+    Or,
+    /// Unary `-x`; carries a single operand.
+    Negate,
+    /// Unary `!x`; carries a single operand.
+    Not,
 }
 
 #[derive(Debug, PartialEq)]
 pub enum Expr {
     Void,
     Nil,
-    Block(Vec<Import>, Vec<Let>, Vec<Expr>),
-    Function(Pattern, Box<Expr>),
+    Block(Vec<Import>, Vec<Let>, Vec<Spanned<Expr>>),
+    Function(Spanned<Pattern>, Box<Spanned<Expr>>),
     Tuple(Tuple),
-    Vector(Vec<Expr>),
-    Map(Vec<(Expr, Expr)>),
+    Vector(Vec<Spanned<Expr>>),
+    /// `[element; count]`: `count` copies of `element`, distinct from the
+    /// comma-separated `Vector` form. Both sides are arbitrary
+    /// sub-expressions, so `[0; width]` works as well as `[1; 3]`.
+    VectorRepeat {
+        element: Box<Spanned<Expr>>,
+        count: Box<Spanned<Expr>>,
+    },
+    Map(Vec<(Spanned<Expr>, Spanned<Expr>)>),
     Identifier(String),
     Tag(String),
     String(String),
     Integer(i64),
     Float(f64),
-    Operator(OperatorKind, Vec<Expr>),
-    Call(Box<Expr>, Vec<Expr>),
+    Operator(OperatorKind, Vec<Spanned<Expr>>),
+    /// `start..end`, `start..=end`, or the open-ended `start..`, with
+    /// `inclusive` set for the `..=` form. `end` is `None` only for the
+    /// open-ended form.
+    Range {
+        start: Box<Spanned<Expr>>,
+        end: Option<Box<Spanned<Expr>>>,
+        inclusive: bool,
+    },
+    Call(Box<Spanned<Expr>>, Vec<Spanned<Expr>>),
+    /// Stands in for a node that failed to parse in recovery mode, so the
+    /// surrounding tree stays structurally complete while the real error
+    /// lives in the `Parser`'s error accumulator.
+    Error,
 }
 
 pub struct Parser<'source> {
     source: &'source str,
     tokens: Vec<Token>,
     index: usize,
+    /// When set, a syntax error inside a delimited construct
+    /// (`parse_tuple`, `parse_vector`, `parse_map`, `parse_block`,
+    /// comma-separated `let`s) is pushed onto `errors` and recovered from
+    /// instead of aborting the parse. Off by default so `parse_expr` keeps
+    /// its original fail-fast `Result`.
+    recovering: bool,
+    /// Diagnostics collected while `recovering` is set.
+    errors: Vec<Error>,
 }
 
 impl<'source> Parser<'source> {
@@ -73,105 +169,229 @@ impl<'source> Parser<'source> {
         }
     }
 
-    fn accept_operator(&mut self) -> Option<(OperatorKind, u8, u8)> {
+    /// Binding powers encode precedence and associativity for
+    /// `parse_expr_precedence`'s recursion: a left-associative operator at
+    /// tier `lbp` returns `(lbp, lbp + 1)` so its right operand refuses
+    /// operators at the same tier, forcing them onto this node instead;
+    /// the right-associative exponent returns `(rbp + 1, rbp)` so its
+    /// right operand *does* accept another exponent, letting `2 ^ 3 ^ 2`
+    /// nest as `2 ^ (3 ^ 2)`. Tiers are ordered boolean < comparison <
+    /// additive < multiplicative < exponent.
+    fn accept_operator(&mut self) -> Option<(OperatorKind, Span, u8, u8)> {
         if self.index < self.tokens.len() {
-            let result = match self.tokens[self.index].kind {
-                TokenKind::Plus => (OperatorKind::Add, 1, 2),
-                TokenKind::Minus => (OperatorKind::Subtract, 1, 2),
-                TokenKind::Times => (OperatorKind::Multiply, 3, 4),
-                TokenKind::Divide => (OperatorKind::Divide, 3, 4),
+            let (kind, left_precedence, right_precedence) = match self.tokens[self.index].kind {
+                TokenKind::AmpersandAmpersand => (OperatorKind::And, 1, 2),
+                TokenKind::PipePipe => (OperatorKind::Or, 1, 2),
+                TokenKind::EqualsEquals => (OperatorKind::Equal, 3, 4),
+                TokenKind::BangEquals => (OperatorKind::NotEqual, 3, 4),
+                TokenKind::Less => (OperatorKind::Less, 3, 4),
+                TokenKind::LessEquals => (OperatorKind::LessEqual, 3, 4),
+                TokenKind::Greater => (OperatorKind::Greater, 3, 4),
+                TokenKind::GreaterEquals => (OperatorKind::GreaterEqual, 3, 4),
+                TokenKind::Plus => (OperatorKind::Add, 5, 6),
+                TokenKind::Minus => (OperatorKind::Subtract, 5, 6),
+                TokenKind::Times => (OperatorKind::Multiply, 7, 8),
+                TokenKind::Divide => (OperatorKind::Divide, 7, 8),
+                TokenKind::Percent => (OperatorKind::Modulo, 7, 8),
+                TokenKind::Caret => (OperatorKind::Exponent, 10, 9),
                 _ => return None,
             };
+            let span = self.tokens[self.index].span;
             self.index += 1;
-            Some(result)
+            Some((kind, span, left_precedence, right_precedence))
         } else {
             None
         }
     }
 
-    fn parse_identifier(&mut self) -> Option<String> {
+    /// The span of the token at `index`, or a zero-width span anchored at
+    /// the end of the token stream (or the start of the source, if there
+    /// are no tokens at all) when `index` runs past the end.
+    fn token_span_at(&self, index: usize) -> Span {
+        if let Some(token) = self.tokens.get(index) {
+            token.span
+        } else if let Some(last) = self.tokens.last() {
+            Span {
+                start: last.span.end,
+                end: last.span.end,
+            }
+        } else {
+            let origin = Position {
+                offset: 0,
+                line: 1,
+                column: 1,
+            };
+            Span {
+                start: origin,
+                end: origin,
+            }
+        }
+    }
+
+    /// Discards tokens until a point where parsing can safely resume: a
+    /// `Comma`, one of the `closers` expected by the enclosing delimited
+    /// construct, or a `Let`/`Import` that starts a new statement.
+    fn synchronize(&mut self, closers: &[TokenKind]) {
+        while let Some(token) = self.tokens.get(self.index) {
+            if token.kind == TokenKind::Comma
+                || token.kind == TokenKind::Let
+                || token.kind == TokenKind::Import
+                || closers.contains(&token.kind)
+            {
+                break;
+            }
+            self.index += 1;
+        }
+    }
+
+    /// Records `error` in the accumulator, synchronizes to the next
+    /// recovery point, and returns an `Expr::Error` placeholder so the
+    /// caller can keep building a tree around the gap instead of bailing
+    /// out of the whole parse.
+    fn recover(&mut self, error: Error, closers: &[TokenKind]) -> Spanned<Expr> {
+        let span = error.span;
+        self.errors.push(error);
+        self.synchronize(closers);
+        Spanned::new(Expr::Error, span)
+    }
+
+    /// `recover`s when `self.recovering` is set, otherwise propagates
+    /// `error` unchanged so non-recovering callers (`parse_expr`) keep
+    /// bailing at the first error exactly as before.
+    fn recover_or_err(&mut self, error: Error, closers: &[TokenKind]) -> Result<Spanned<Expr>> {
+        if self.recovering {
+            Ok(self.recover(error, closers))
+        } else {
+            Err(error)
+        }
+    }
+
+    fn parse_identifier(&mut self) -> Option<(String, Span)> {
         self.accept(TokenKind::Identifier)
-            .map(|span| self.source[span].into())
+            .map(|span| (self.source[span].into(), span))
     }
 
-    fn parse_tag(&mut self) -> Option<String> {
+    fn parse_tag(&mut self) -> Option<(String, Span)> {
         self.accept(TokenKind::Tag)
-            .map(|span| self.source[span].into())
+            .map(|span| (self.source[span].into(), span))
     }
 
-    fn parse_string(&mut self) -> Result<Option<String>> {
+    /// Walks the string body a byte at a time instead of decoding every
+    /// character up front: escape delimiters and hex digits are all ASCII,
+    /// so most bytes can be pushed straight through, and a full UTF-8
+    /// decode only happens for the (rarer) non-ASCII byte.
+    fn parse_string(&mut self) -> Result<Option<(String, Span)>> {
         if let Some(span) = self.accept(TokenKind::String) {
             let slice = &self.source[span];
-            let mut string = String::with_capacity(slice.len());
-            let mut chars = slice[1..slice.len() - 1].chars();
-            while let Some(c) = chars.next() {
-                if c == '\\' {
+            let inner = &slice[1..slice.len() - 1];
+            let bytes = inner.as_bytes();
+            let mut string = String::with_capacity(inner.len());
+            let mut i = 0;
+            while i < bytes.len() {
+                let b = bytes[i];
+                if b == b'\\' {
                     let error = Error {
                         kind: ErrorKind::InvalidEscapeSequence,
                         span,
                     };
-                    // `unwrap()` here is safe because a string ending `\` such
-                    // as `"\"` would fail with `UnterminatedString`.
-                    let c = chars.next().unwrap();
+                    // Indexing `bytes[i + 1]` here is safe because a string
+                    // ending `\` such as `"\"` would fail with
+                    // `UnterminatedString`.
+                    let c = bytes[i + 1];
+                    i += 2;
                     match c {
-                        '\\' => string.push('\\'),
-                        '"' => string.push('"'),
+                        b'\\' => string.push('\\'),
+                        b'"' => string.push('"'),
                         // Bell
-                        'a' => string.push('\x07'),
+                        b'a' => string.push('\x07'),
                         // Backspace
-                        'b' => string.push('\x08'),
+                        b'b' => string.push('\x08'),
                         // Horizontal tab
-                        't' => string.push('\t'),
+                        b't' => string.push('\t'),
                         // Form feed
-                        'f' => string.push('\x0A'),
+                        b'f' => string.push('\x0A'),
                         // Vertical tab
-                        'v' => string.push('\x0B'),
+                        b'v' => string.push('\x0B'),
                         // Newline
-                        'n' => {
+                        b'n' => {
                             string.push('\n');
                         }
                         // Carriage return
-                        'r' => {
+                        b'r' => {
                             string.push('\r');
                         }
                         // Nul
-                        '0' => {
+                        b'0' => {
                             string.push('\0');
                         }
                         // Hexidecimal
-                        'x' => {
-                            let a = chars.next().ok_or(error)?;
-                            let b = chars.next().ok_or(error)?;
-                            let value =
-                                16 * match a {
-                                    '0'..='9' => a as u8 - b'0',
-                                    'a'..='f' => 10 + a as u8 - b'a',
-                                    'A'..='F' => 10 + a as u8 - b'A',
-                                    _ => return Err(error),
-                                } + match b {
-                                    '0'..='9' => b as u8 - b'0',
-                                    'a'..='f' => 10 + b as u8 - b'a',
-                                    'A'..='F' => 10 + b as u8 - b'A',
-                                    _ => return Err(error),
-                                };
+                        b'x' => {
+                            let a = *bytes.get(i).ok_or(error)?;
+                            let b = *bytes.get(i + 1).ok_or(error)?;
+                            i += 2;
+                            let value = 16 * match a {
+                                b'0'..=b'9' => a - b'0',
+                                b'a'..=b'f' => 10 + a - b'a',
+                                b'A'..=b'F' => 10 + a - b'A',
+                                _ => return Err(error),
+                            } + match b {
+                                b'0'..=b'9' => b - b'0',
+                                b'a'..=b'f' => 10 + b - b'a',
+                                b'A'..=b'F' => 10 + b - b'A',
+                                _ => return Err(error),
+                            };
                             if value > 127 {
                                 return Err(error);
                             }
                             string.push(value as char);
                         }
+                        // Unicode scalar value: `\u{XXXX}`, 1-6 hex digits.
+                        b'u' => {
+                            if bytes.get(i) != Some(&b'{') {
+                                return Err(error);
+                            }
+                            i += 1;
+                            let mut value: u32 = 0;
+                            let mut digits = 0;
+                            while let Some(&digit) = bytes.get(i) {
+                                let digit = match digit {
+                                    b'0'..=b'9' => digit - b'0',
+                                    b'a'..=b'f' => 10 + digit - b'a',
+                                    b'A'..=b'F' => 10 + digit - b'A',
+                                    _ => break,
+                                };
+                                if digits == 6 {
+                                    return Err(error);
+                                }
+                                value = value * 16 + digit as u32;
+                                digits += 1;
+                                i += 1;
+                            }
+                            if digits == 0 || bytes.get(i) != Some(&b'}') {
+                                return Err(error);
+                            }
+                            i += 1;
+                            string.push(char::from_u32(value).ok_or(error)?);
+                        }
                         _ => return Err(error),
                     }
+                } else if b < 0x80 {
+                    string.push(b as char);
+                    i += 1;
                 } else {
-                    string.push(c)
+                    // Non-ASCII byte: fall back to a single full-char decode.
+                    let c = inner[i..].chars().next().unwrap();
+                    string.push(c);
+                    i += c.len_utf8();
                 }
             }
-            Ok(Some(string))
+            Ok(Some((string, span)))
         } else {
             Ok(None)
         }
     }
 
-    fn parse_integer(&mut self) -> Result<Option<i64>> {
+    fn parse_integer(&mut self) -> Result<Option<(i64, Span)>> {
         let i = self.index;
         let minus = self.accept(TokenKind::Minus);
         if let Some(integer_span) = self.accept(TokenKind::Integer) {
@@ -198,14 +418,14 @@ impl<'source> Parser<'source> {
                     .checked_add(sign * (digit - b'0') as i64)
                     .ok_or(error)?;
             }
-            Ok(Some(value))
+            Ok(Some((value, span)))
         } else {
             self.index = i;
             Ok(None)
         }
     }
 
-    fn parse_float(&mut self) -> Result<Option<f64>> {
+    fn parse_float(&mut self) -> Result<Option<(f64, Span)>> {
         let i = self.index;
         let minus = self.accept(TokenKind::Minus);
         if let Some(float_span) = self.accept(TokenKind::Float) {
@@ -225,22 +445,25 @@ impl<'source> Parser<'source> {
                     kind: ErrorKind::InvalidFloat,
                     span,
                 })?;
-            Ok(Some(value))
+            Ok(Some((value, span)))
         } else {
             self.index = i;
             Ok(None)
         }
     }
 
-    fn parse_tuple_member(&mut self) -> Result<Option<(Option<String>, Expr)>> {
-        if let Some(identifier) = self.parse_identifier() {
+    fn parse_tuple_member(&mut self) -> Result<Option<(Option<String>, Spanned<Expr>)>> {
+        if let Some((identifier, identifier_span)) = self.parse_identifier() {
             if self.accept(TokenKind::Colon).is_some() {
-                let expr = self
-                    .parse_expr()?
-                    .unwrap_or_else(|| Expr::Identifier(identifier.clone()));
+                let expr = self.parse_expr()?.unwrap_or_else(|| {
+                    Spanned::new(Expr::Identifier(identifier.clone()), identifier_span)
+                });
                 Ok(Some((Some(identifier), expr)))
             } else {
-                Ok(Some((None, Expr::Identifier(identifier))))
+                Ok(Some((
+                    None,
+                    Spanned::new(Expr::Identifier(identifier), identifier_span),
+                )))
             }
         } else if let Some(expr) = self.parse_expr()? {
             Ok(Some((None, expr)))
@@ -249,45 +472,80 @@ impl<'source> Parser<'source> {
         }
     }
 
-    fn parse_tuple(&mut self) -> Result<Option<Expr>> {
+    fn parse_tuple(&mut self) -> Result<Option<Spanned<Expr>>> {
         let i = self.index;
         let tag = self.parse_tag();
         if let Some(left_parenthesis_span) = self.accept(TokenKind::LeftParenthesis) {
             let mut positional = vec![];
             let mut named = vec![];
-            if let Some((key, expr)) = self.parse_tuple_member()? {
-                if let Some(key) = key {
-                    named.push((key, expr));
-                } else {
-                    positional.push(expr);
+            let closers = [TokenKind::RightParenthesis];
+            let mut parsed_first = false;
+            match self.parse_tuple_member() {
+                Ok(Some((key, expr))) => {
+                    parsed_first = true;
+                    if let Some(key) = key {
+                        named.push((key, expr));
+                    } else {
+                        positional.push(expr);
+                    }
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    parsed_first = true;
+                    positional.push(self.recover_or_err(error, &closers)?);
                 }
+            }
+            if parsed_first {
                 while let Some(comma_span) = self.accept(TokenKind::Comma) {
-                    if let Some((key, expr)) = self.parse_tuple_member()? {
-                        if let Some(key) = key {
-                            named.push((key, expr));
-                        } else if named.len() > 0 {
-                            return Err(Error {
-                                kind: ErrorKind::TuplePositionalAfterNamed,
-                                span: comma_span,
-                            });
-                        } else {
-                            positional.push(expr);
+                    match self.parse_tuple_member() {
+                        Ok(Some((key, expr))) => {
+                            if let Some(key) = key {
+                                named.push((key, expr));
+                            } else if named.len() > 0 {
+                                if self.recovering {
+                                    self.errors.push(Error {
+                                        kind: ErrorKind::TuplePositionalAfterNamed,
+                                        span: comma_span,
+                                    });
+                                    positional.push(expr);
+                                } else {
+                                    return Err(Error {
+                                        kind: ErrorKind::TuplePositionalAfterNamed,
+                                        span: comma_span,
+                                    });
+                                }
+                            } else {
+                                positional.push(expr);
+                            }
                         }
+                        Ok(None) => {}
+                        Err(error) => positional.push(self.recover_or_err(error, &closers)?),
                     }
                 }
             }
-            self.accept(TokenKind::RightParenthesis).ok_or(Error {
+            let right_parenthesis_span = self.accept(TokenKind::RightParenthesis).ok_or(Error {
                 kind: ErrorKind::UnbalancedDelimiters,
                 span: left_parenthesis_span,
             })?;
+            let start = tag
+                .as_ref()
+                .map_or(left_parenthesis_span.start, |(_, span)| span.start);
+            let span = Span {
+                start,
+                end: right_parenthesis_span.end,
+            };
+            let tag = tag.map(|(name, _)| name);
             if positional.len() == 0 && named.len() == 0 {
-                Ok(Some(Expr::Nil))
+                Ok(Some(Spanned::new(Expr::Nil, span)))
             } else {
-                Ok(Some(Expr::Tuple(Tuple {
-                    tag,
-                    positional,
-                    named,
-                })))
+                Ok(Some(Spanned::new(
+                    Expr::Tuple(Tuple {
+                        tag,
+                        positional,
+                        named,
+                    }),
+                    span,
+                )))
             }
         } else {
             self.index = i;
@@ -295,35 +553,228 @@ impl<'source> Parser<'source> {
         }
     }
 
-    fn parse_vector(&mut self) -> Result<Option<Expr>> {
+    /// Parses the `(arg, ...)` call arguments trailing a lowercase
+    /// identifier, e.g. `len(xs)`. Capitalized `Tag(...)` heads never reach
+    /// here: those are tagged tuples, handled by `parse_tuple` before any
+    /// identifier is tried. Returns `None` without consuming anything if
+    /// `callee` isn't immediately followed by `(`, so a bare identifier
+    /// still parses as `Expr::Identifier`.
+    fn parse_call(&mut self, callee: String, callee_span: Span) -> Result<Option<Spanned<Expr>>> {
+        let Some(left_parenthesis_span) = self.accept(TokenKind::LeftParenthesis) else {
+            return Ok(None);
+        };
+        let mut args = Vec::new();
+        let closers = [TokenKind::RightParenthesis];
+        let mut parsed_first = false;
+        match self.parse_expr() {
+            Ok(Some(expr)) => {
+                parsed_first = true;
+                args.push(expr);
+            }
+            Ok(None) => {}
+            Err(error) => {
+                parsed_first = true;
+                args.push(self.recover_or_err(error, &closers)?);
+            }
+        }
+        if parsed_first {
+            while self.accept(TokenKind::Comma).is_some() {
+                match self.parse_expr() {
+                    Ok(Some(expr)) => args.push(expr),
+                    Ok(None) => {}
+                    Err(error) => args.push(self.recover_or_err(error, &closers)?),
+                }
+            }
+        }
+        let right_parenthesis_span = self.accept(TokenKind::RightParenthesis).ok_or(Error {
+            kind: ErrorKind::UnbalancedDelimiters,
+            span: left_parenthesis_span,
+        })?;
+        let span = Span {
+            start: callee_span.start,
+            end: right_parenthesis_span.end,
+        };
+        let callee = Box::new(Spanned::new(Expr::Identifier(callee), callee_span));
+        Ok(Some(Spanned::new(Expr::Call(callee, args), span)))
+    }
+
+    fn parse_vector(&mut self) -> Result<Option<Spanned<Expr>>> {
         if let Some(left_bracket_span) = self.accept(TokenKind::LeftBracket) {
             let mut exprs = Vec::new();
-            if let Some(expr) = self.parse_expr()? {
-                exprs.push(expr);
+            let closers = [TokenKind::RightBracket];
+            let mut parsed_first = false;
+            match self.parse_expr() {
+                Ok(Some(expr)) => {
+                    parsed_first = true;
+                    if self.accept(TokenKind::Semicolon).is_some() {
+                        let count = self.parse_expr()?.ok_or(Error {
+                            kind: ErrorKind::InvalidToken,
+                            span: self.token_span_at(self.index),
+                        })?;
+                        let right_bracket_span =
+                            self.accept(TokenKind::RightBracket).ok_or(Error {
+                                kind: ErrorKind::UnbalancedDelimiters,
+                                span: left_bracket_span,
+                            })?;
+                        let span = Span {
+                            start: left_bracket_span.start,
+                            end: right_bracket_span.end,
+                        };
+                        return Ok(Some(Spanned::new(
+                            Expr::VectorRepeat {
+                                element: Box::new(expr),
+                                count: Box::new(count),
+                            },
+                            span,
+                        )));
+                    }
+                    exprs.push(expr);
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    parsed_first = true;
+                    exprs.push(self.recover_or_err(error, &closers)?);
+                }
+            }
+            if parsed_first {
                 while self.accept(TokenKind::Comma).is_some() {
-                    if let Some(expr) = self.parse_expr()? {
-                        exprs.push(expr);
+                    match self.parse_expr() {
+                        Ok(Some(expr)) => exprs.push(expr),
+                        Ok(None) => {}
+                        Err(error) => exprs.push(self.recover_or_err(error, &closers)?),
                     }
                 }
             }
-            self.accept(TokenKind::RightBracket).ok_or(Error {
+            let right_bracket_span = self.accept(TokenKind::RightBracket).ok_or(Error {
                 kind: ErrorKind::UnbalancedDelimiters,
                 span: left_bracket_span,
             })?;
-            Ok(Some(Expr::Vector(exprs)))
+            let span = Span {
+                start: left_bracket_span.start,
+                end: right_bracket_span.end,
+            };
+            Ok(Some(Spanned::new(Expr::Vector(exprs), span)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parses a non-identifier map key: a tag, string, or integer atom.
+    /// Identifier keys are handled directly in `parse_map_entry`, since
+    /// only they support the bare `x` shorthand for `x: x`. A map's first
+    /// entry needs an explicit `key:` (see `peek_map_entry`), so a lone
+    /// `{x}` parses as a block, not a map.
+    fn parse_map_key(&mut self) -> Result<Option<Spanned<Expr>>> {
+        if let Some((tag, span)) = self.parse_tag() {
+            Ok(Some(Spanned::new(Expr::Tag(tag), span)))
+        } else if let Some((string, span)) = self.parse_string()? {
+            Ok(Some(Spanned::new(Expr::String(string), span)))
+        } else if let Some((integer, span)) = self.parse_integer()? {
+            Ok(Some(Spanned::new(Expr::Integer(integer), span)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Mirrors `parse_tuple_member`'s named-member shape: a `key: value`
+    /// pair, or a bare identifier key shorthand for `key: key`.
+    fn parse_map_entry(&mut self) -> Result<Option<(Spanned<Expr>, Spanned<Expr>)>> {
+        if let Some((identifier, identifier_span)) = self.parse_identifier() {
+            if let Some(colon_span) = self.accept(TokenKind::Colon) {
+                let value = self.parse_expr()?.ok_or(Error {
+                    kind: ErrorKind::TrailingColon,
+                    span: colon_span,
+                })?;
+                Ok(Some((
+                    Spanned::new(Expr::Identifier(identifier), identifier_span),
+                    value,
+                )))
+            } else {
+                Ok(Some((
+                    Spanned::new(Expr::Identifier(identifier.clone()), identifier_span),
+                    Spanned::new(Expr::Identifier(identifier), identifier_span),
+                )))
+            }
+        } else if let Some(key) = self.parse_map_key()? {
+            let span = self.token_span_at(self.index);
+            let colon_span = self.accept(TokenKind::Colon).ok_or(Error {
+                kind: ErrorKind::InvalidToken,
+                span,
+            })?;
+            let value = self.parse_expr()?.ok_or(Error {
+                kind: ErrorKind::TrailingColon,
+                span: colon_span,
+            })?;
+            Ok(Some((key, value)))
         } else {
             Ok(None)
         }
     }
 
-    fn parse_map(&mut self) -> Result<Option<Expr>> {
+    /// Looks ahead for a `key:` sequence without consuming it, so
+    /// `parse_atom` can tell a map literal apart from `parse_block`'s
+    /// empty-block and brace-delimited block syntax before committing.
+    fn peek_map_entry(&mut self) -> bool {
+        let i = self.index;
+        let has_key =
+            self.parse_identifier().is_some() || matches!(self.parse_map_key(), Ok(Some(_)));
+        let followed_by_colon =
+            matches!(self.tokens.get(self.index), Some(token) if token.kind == TokenKind::Colon);
+        self.index = i;
+        has_key && followed_by_colon
+    }
+
+    fn parse_map(&mut self) -> Result<Option<Spanned<Expr>>> {
         let i = self.index;
         if let Some(left_brace_span) = self.accept(TokenKind::LeftBrace) {
-            self.accept(TokenKind::RightBrace).ok_or(Error {
+            if !self.peek_map_entry() {
+                self.index = i;
+                return Ok(None);
+            }
+            let mut entries = Vec::new();
+            let closers = [TokenKind::RightBrace];
+            let mut parsed_first = false;
+            match self.parse_map_entry() {
+                Ok(Some(entry)) => {
+                    parsed_first = true;
+                    entries.push(entry);
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    if !self.recovering {
+                        return Err(error);
+                    }
+                    parsed_first = true;
+                    let span = error.span;
+                    let value = self.recover(error, &closers);
+                    entries.push((Spanned::new(Expr::Error, span), value));
+                }
+            }
+            if parsed_first {
+                while self.accept(TokenKind::Comma).is_some() {
+                    match self.parse_map_entry() {
+                        Ok(Some(entry)) => entries.push(entry),
+                        Ok(None) => {}
+                        Err(error) => {
+                            if !self.recovering {
+                                return Err(error);
+                            }
+                            let span = error.span;
+                            let value = self.recover(error, &closers);
+                            entries.push((Spanned::new(Expr::Error, span), value));
+                        }
+                    }
+                }
+            }
+            let right_brace_span = self.accept(TokenKind::RightBrace).ok_or(Error {
                 kind: ErrorKind::UnbalancedDelimiters,
                 span: left_brace_span,
             })?;
-            todo!()
+            let span = Span {
+                start: left_brace_span.start,
+                end: right_brace_span.end,
+            };
+            Ok(Some(Spanned::new(Expr::Map(entries), span)))
         } else {
             self.index = i;
             Ok(None)
@@ -331,8 +782,8 @@ impl<'source> Parser<'source> {
     }
 
     fn parse_import(&mut self) -> Result<Option<Import>> {
-        if let Some(identifier) = self.parse_identifier() {
-            if let Some(string) = self.parse_string()? {
+        if let Some((identifier, _)) = self.parse_identifier() {
+            if let Some((string, _)) = self.parse_string()? {
                 return Ok(Some(Import(identifier, string)));
             }
         }
@@ -364,9 +815,136 @@ impl<'source> Parser<'source> {
         }
     }
 
-    fn parse_pattern(&mut self) -> Result<Option<Pattern>> {
-        if let Some(identifier) = self.parse_identifier() {
-            Ok(Some(Pattern::Identifier(identifier)))
+    /// Mirrors `parse_tuple_member`'s shape, but for patterns: a
+    /// `key: pattern` pair, a bare `key:` as shorthand for `key: key`, or a
+    /// bare identifier (or `_`) as a positional member.
+    fn parse_pattern_tuple_member(&mut self) -> Result<Option<(Option<String>, Spanned<Pattern>)>> {
+        if let Some((identifier, identifier_span)) = self.parse_identifier() {
+            if self.accept(TokenKind::Colon).is_some() {
+                let fallback = if identifier == "_" {
+                    Pattern::Wildcard
+                } else {
+                    Pattern::Identifier(identifier.clone())
+                };
+                let pattern = self
+                    .parse_pattern()?
+                    .unwrap_or_else(|| Spanned::new(fallback, identifier_span));
+                Ok(Some((Some(identifier), pattern)))
+            } else {
+                let node = if identifier == "_" {
+                    Pattern::Wildcard
+                } else {
+                    Pattern::Identifier(identifier)
+                };
+                Ok(Some((None, Spanned::new(node, identifier_span))))
+            }
+        } else if let Some(pattern) = self.parse_pattern()? {
+            Ok(Some((None, pattern)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parses a tagged or plain tuple pattern, e.g. `(a, b)` or
+    /// `Point(x, y: y)`, mirroring `parse_tuple`'s shape without the
+    /// error-recovery machinery: patterns only ever appear in `let`, whose
+    /// errors `parse` recovers from as a whole, so a syntax error here
+    /// simply propagates as `Err`.
+    fn parse_pattern_tuple(&mut self) -> Result<Option<Spanned<Pattern>>> {
+        let i = self.index;
+        let tag = self.parse_tag();
+        if let Some(left_parenthesis_span) = self.accept(TokenKind::LeftParenthesis) {
+            let mut positional = vec![];
+            let mut named = vec![];
+            if let Some((key, pattern)) = self.parse_pattern_tuple_member()? {
+                if let Some(key) = key {
+                    named.push((key, pattern));
+                } else {
+                    positional.push(pattern);
+                }
+                while let Some(comma_span) = self.accept(TokenKind::Comma) {
+                    if let Some((key, pattern)) = self.parse_pattern_tuple_member()? {
+                        if let Some(key) = key {
+                            named.push((key, pattern));
+                        } else if named.len() > 0 {
+                            return Err(Error {
+                                kind: ErrorKind::TuplePositionalAfterNamed,
+                                span: comma_span,
+                            });
+                        } else {
+                            positional.push(pattern);
+                        }
+                    }
+                }
+            }
+            let right_parenthesis_span = self.accept(TokenKind::RightParenthesis).ok_or(Error {
+                kind: ErrorKind::UnbalancedDelimiters,
+                span: left_parenthesis_span,
+            })?;
+            let start = tag
+                .as_ref()
+                .map_or(left_parenthesis_span.start, |(_, span)| span.start);
+            let span = Span {
+                start,
+                end: right_parenthesis_span.end,
+            };
+            let tag = tag.map(|(name, _)| name);
+            Ok(Some(Spanned::new(
+                Pattern::Tuple(PatternTuple {
+                    tag,
+                    positional,
+                    named,
+                }),
+                span,
+            )))
+        } else {
+            self.index = i;
+            Ok(None)
+        }
+    }
+
+    /// Parses a vector pattern, e.g. `[head, rest]`. Unlike tuple patterns,
+    /// there's no tag to parse here, so a leading `Tag` is simply left
+    /// untouched and falls through to `parse_pattern`'s bare `Tag` arm
+    /// instead of being absorbed into a (nonexistent) tagged vector form.
+    fn parse_pattern_vector(&mut self) -> Result<Option<Spanned<Pattern>>> {
+        if let Some(left_bracket_span) = self.accept(TokenKind::LeftBracket) {
+            let mut patterns = Vec::new();
+            if let Some(pattern) = self.parse_pattern()? {
+                patterns.push(pattern);
+                while self.accept(TokenKind::Comma).is_some() {
+                    if let Some(pattern) = self.parse_pattern()? {
+                        patterns.push(pattern);
+                    }
+                }
+            }
+            let right_bracket_span = self.accept(TokenKind::RightBracket).ok_or(Error {
+                kind: ErrorKind::UnbalancedDelimiters,
+                span: left_bracket_span,
+            })?;
+            let span = Span {
+                start: left_bracket_span.start,
+                end: right_bracket_span.end,
+            };
+            Ok(Some(Spanned::new(Pattern::Vector(patterns), span)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn parse_pattern(&mut self) -> Result<Option<Spanned<Pattern>>> {
+        if let Some(pattern) = self.parse_pattern_tuple()? {
+            Ok(Some(pattern))
+        } else if let Some(pattern) = self.parse_pattern_vector()? {
+            Ok(Some(pattern))
+        } else if let Some((identifier, span)) = self.parse_identifier() {
+            if identifier == "_" {
+                Ok(Some(Spanned::new(Pattern::Wildcard, span)))
+            } else {
+                Ok(Some(Spanned::new(Pattern::Identifier(identifier), span)))
+            }
+        } else if let Some((tag, span)) = self.parse_tag() {
+            Ok(Some(Spanned::new(Pattern::Tag(tag), span)))
         } else {
             Ok(None)
         }
@@ -378,6 +956,15 @@ impl<'source> Parser<'source> {
                 kind: ErrorKind::InvalidToken,
                 span: let_span,
             })?;
+            let mut seen = HashMap::new();
+            for ident in bound_idents(&pattern.node) {
+                if seen.insert(ident, ()).is_some() {
+                    return Err(Error {
+                        kind: ErrorKind::DuplicateBinding,
+                        span: let_span,
+                    });
+                }
+            }
             self.accept(TokenKind::Equals).ok_or(Error {
                 kind: ErrorKind::InvalidToken,
                 span: let_span,
@@ -392,57 +979,149 @@ impl<'source> Parser<'source> {
         }
     }
 
-    fn parse_block(&mut self) -> Result<Option<Expr>> {
+    fn parse_block(&mut self) -> Result<Option<Spanned<Expr>>> {
         if let Some(left_brace_span) = self.accept(TokenKind::LeftBrace) {
-            let block = self.parse()?;
-            self.accept(TokenKind::RightBrace).ok_or(Error {
+            let block = self.parse(&[TokenKind::RightBrace])?;
+            let right_brace_span = self.accept(TokenKind::RightBrace).ok_or(Error {
                 kind: ErrorKind::UnbalancedDelimiters,
                 span: left_brace_span,
             })?;
-            Ok(Some(block))
+            let span = Span {
+                start: left_brace_span.start,
+                end: right_brace_span.end,
+            };
+            Ok(Some(Spanned::new(block, span)))
         } else {
             Ok(None)
         }
     }
 
-    fn parse_atom(&mut self) -> Result<Option<Expr>> {
-        if let Some(tuple) = self.parse_tuple()? {
+    /// Parses a boxed operator like `\+`: a backslash directly followed by
+    /// any binary operator token `accept_operator` knows, desugared to a two-argument
+    /// curried lambda (`fn(a, b) a + b`, i.e. `Function(a, Function(b,
+    /// Operator(...)))`) with fresh `a`/`b` parameters, so the operator
+    /// can be passed around as a first-class function.
+    fn parse_operator_section(&mut self) -> Result<Option<Spanned<Expr>>> {
+        let i = self.index;
+        if let Some(backslash_span) = self.accept(TokenKind::Backslash) {
+            if let Some((kind, operator_span, _, _)) = self.accept_operator() {
+                let span = Span {
+                    start: backslash_span.start,
+                    end: operator_span.end,
+                };
+                let a = Spanned::new(Pattern::Identifier("a".into()), span);
+                let b = Spanned::new(Pattern::Identifier("b".into()), span);
+                let body = Spanned::new(
+                    Expr::Operator(
+                        kind,
+                        vec![
+                            Spanned::new(Expr::Identifier("a".into()), span),
+                            Spanned::new(Expr::Identifier("b".into()), span),
+                        ],
+                    ),
+                    span,
+                );
+                let inner = Spanned::new(Expr::Function(b, Box::new(body)), span);
+                Ok(Some(Spanned::new(Expr::Function(a, Box::new(inner)), span)))
+            } else {
+                self.index = i;
+                Ok(None)
+            }
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn parse_atom(&mut self) -> Result<Option<Spanned<Expr>>> {
+        if let Some(section) = self.parse_operator_section()? {
+            Ok(Some(section))
+        } else if let Some(tuple) = self.parse_tuple()? {
             Ok(Some(tuple))
         } else if let Some(vector) = self.parse_vector()? {
             Ok(Some(vector))
+        } else if let Some(map) = self.parse_map()? {
+            Ok(Some(map))
         } else if let Some(block) = self.parse_block()? {
             Ok(Some(block))
-        } else if let Some(identifier) = self.parse_identifier() {
-            Ok(Some(Expr::Identifier(identifier)))
-        } else if let Some(tag) = self.parse_tag() {
-            Ok(Some(Expr::Tag(tag)))
-        } else if let Some(string) = self.parse_string()? {
-            Ok(Some(Expr::String(string)))
-        } else if let Some(integer) = self.parse_integer()? {
-            Ok(Some(Expr::Integer(integer)))
-        } else if let Some(float) = self.parse_float()? {
-            Ok(Some(Expr::Float(float)))
+        } else if let Some((identifier, span)) = self.parse_identifier() {
+            if let Some(call) = self.parse_call(identifier.clone(), span)? {
+                Ok(Some(call))
+            } else {
+                Ok(Some(Spanned::new(Expr::Identifier(identifier), span)))
+            }
+        } else if let Some((tag, span)) = self.parse_tag() {
+            Ok(Some(Spanned::new(Expr::Tag(tag), span)))
+        } else if let Some((string, span)) = self.parse_string()? {
+            Ok(Some(Spanned::new(Expr::String(string), span)))
+        } else if let Some((integer, span)) = self.parse_integer()? {
+            Ok(Some(Spanned::new(Expr::Integer(integer), span)))
+        } else if let Some((float, span)) = self.parse_float()? {
+            Ok(Some(Spanned::new(Expr::Float(float), span)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parses a prefix-position atom: `parse_atom` first, so a literal like
+    /// `-3` or `-3.0` keeps folding its sign straight into the literal via
+    /// `parse_integer`/`parse_float` as before; only once that's ruled out
+    /// does a bare `Minus` or `Bang` get treated as the general unary
+    /// `Negate`/`Not` operator, recursing at the unary tier (9, between
+    /// multiplicative and exponent) so `!a && b` still parses as
+    /// `(!a) && b` and `-a ^ b` as `-(a ^ b)`.
+    fn parse_prefix(&mut self) -> Result<Option<Spanned<Expr>>> {
+        if let Some(atom) = self.parse_atom()? {
+            return Ok(Some(atom));
+        }
+        if let Some(minus_span) = self.accept(TokenKind::Minus) {
+            let span = self.token_span_at(self.index);
+            let operand = self.parse_expr_precedence(9)?.ok_or(Error {
+                kind: ErrorKind::InvalidToken,
+                span,
+            })?;
+            let span = Span {
+                start: minus_span.start,
+                end: operand.span.end,
+            };
+            Ok(Some(Spanned::new(
+                Expr::Operator(OperatorKind::Negate, vec![operand]),
+                span,
+            )))
+        } else if let Some(bang_span) = self.accept(TokenKind::Bang) {
+            let span = self.token_span_at(self.index);
+            let operand = self.parse_expr_precedence(9)?.ok_or(Error {
+                kind: ErrorKind::InvalidToken,
+                span,
+            })?;
+            let span = Span {
+                start: bang_span.start,
+                end: operand.span.end,
+            };
+            Ok(Some(Spanned::new(
+                Expr::Operator(OperatorKind::Not, vec![operand]),
+                span,
+            )))
         } else {
             Ok(None)
         }
     }
 
-    fn parse_expr_precedence(&mut self, min_precedence: u8) -> Result<Option<Expr>> {
-        if let Some(mut left) = self.parse_atom()? {
+    fn parse_expr_precedence(&mut self, min_precedence: u8) -> Result<Option<Spanned<Expr>>> {
+        if let Some(mut left) = self.parse_prefix()? {
             loop {
-                if let Some((kind, left_precedence, right_precedence)) = self.accept_operator() {
+                if let Some((kind, _, left_precedence, right_precedence)) = self.accept_operator() {
                     if left_precedence < min_precedence {
                         self.index -= 1;
                         break;
                     }
                     if let Some(right) = self.parse_expr_precedence(right_precedence)? {
-                        left = Expr::Operator(kind, vec![left, right]);
-                    } else {
-                        let span = if self.index < self.tokens.len() {
-                            self.tokens[self.index].span
-                        } else {
-                            self.tokens[self.index - 1].span
+                        let span = Span {
+                            start: left.span.start,
+                            end: right.span.end,
                         };
+                        left = Spanned::new(Expr::Operator(kind, vec![left, right]), span);
+                    } else {
+                        let span = self.token_span_at(self.index);
                         return Err(Error {
                             kind: ErrorKind::InvalidToken,
                             span,
@@ -458,32 +1137,131 @@ impl<'source> Parser<'source> {
         }
     }
 
-    fn parse_expr(&mut self) -> Result<Option<Expr>> {
-        self.parse_expr_precedence(0)
+    /// Parses an operator expression, then checks for a trailing `..` or
+    /// `..=` turning it into a range: ranges sit below every operator
+    /// precedence tier, so `a..b+1` parses with `b+1` as the end rather
+    /// than needing parentheses.
+    fn parse_range(&mut self) -> Result<Option<Spanned<Expr>>> {
+        if let Some(start) = self.parse_expr_precedence(0)? {
+            if let Some(dot_dot_span) = self.accept(TokenKind::DotDot) {
+                let end = self.parse_expr_precedence(0)?;
+                let span = Span {
+                    start: start.span.start,
+                    end: end.as_ref().map_or(dot_dot_span.end, |end| end.span.end),
+                };
+                Ok(Some(Spanned::new(
+                    Expr::Range {
+                        start: Box::new(start),
+                        end: end.map(Box::new),
+                        inclusive: false,
+                    },
+                    span,
+                )))
+            } else if self.accept(TokenKind::DotDotEquals).is_some() {
+                let span = self.token_span_at(self.index);
+                let end = self.parse_expr_precedence(0)?.ok_or(Error {
+                    kind: ErrorKind::InvalidToken,
+                    span,
+                })?;
+                let span = Span {
+                    start: start.span.start,
+                    end: end.span.end,
+                };
+                Ok(Some(Spanned::new(
+                    Expr::Range {
+                        start: Box::new(start),
+                        end: Some(Box::new(end)),
+                        inclusive: true,
+                    },
+                    span,
+                )))
+            } else {
+                Ok(Some(start))
+            }
+        } else {
+            Ok(None)
+        }
     }
 
-    fn parse(&mut self) -> Result<Expr> {
-        let imports = if let Some(imports) = self.parse_imports()? {
-            self.accept(TokenKind::Comma);
-            imports
-        } else {
-            Vec::new()
+    fn parse_expr(&mut self) -> Result<Option<Spanned<Expr>>> {
+        self.parse_range()
+    }
+
+    /// Parses a block body: optional `import`s, optional comma-separated
+    /// `let`s, then comma-separated expressions. When `self.recovering`
+    /// is set, no section bails on its first error: each one recovers
+    /// independently, pushing diagnostics into `self.errors` and
+    /// resynchronizing at `closers` (the delimiter(s), if any, that end
+    /// this block) instead, so one typo doesn't hide every later error.
+    /// Otherwise behaves exactly as a plain `Result`-returning parse,
+    /// bailing at the first error.
+    fn parse(&mut self, closers: &[TokenKind]) -> Result<Expr> {
+        let imports = match self.parse_imports() {
+            Ok(Some(imports)) => {
+                self.accept(TokenKind::Comma);
+                imports
+            }
+            Ok(None) => Vec::new(),
+            Err(error) => {
+                if !self.recovering {
+                    return Err(error);
+                }
+                self.errors.push(error);
+                self.synchronize(closers);
+                Vec::new()
+            }
         };
         let mut lets = Vec::new();
-        if let Some(let_) = self.parse_let()? {
-            lets.push(let_);
+        let mut parsed_first_let = false;
+        match self.parse_let() {
+            Ok(Some(let_)) => {
+                parsed_first_let = true;
+                lets.push(let_);
+            }
+            Ok(None) => {}
+            Err(error) => {
+                if !self.recovering {
+                    return Err(error);
+                }
+                parsed_first_let = true;
+                self.errors.push(error);
+                self.synchronize(closers);
+            }
+        }
+        if parsed_first_let {
             while self.accept(TokenKind::Comma).is_some() {
-                if let Some(let_) = self.parse_let()? {
-                    lets.push(let_);
+                match self.parse_let() {
+                    Ok(Some(let_)) => lets.push(let_),
+                    Ok(None) => {}
+                    Err(error) => {
+                        if !self.recovering {
+                            return Err(error);
+                        }
+                        self.errors.push(error);
+                        self.synchronize(closers);
+                    }
                 }
             }
         }
         let mut exprs = Vec::new();
-        if let Some(expr) = self.parse_expr()? {
-            exprs.push(expr);
+        let mut parsed_first_expr = false;
+        match self.parse_expr() {
+            Ok(Some(expr)) => {
+                parsed_first_expr = true;
+                exprs.push(expr);
+            }
+            Ok(None) => {}
+            Err(error) => {
+                parsed_first_expr = true;
+                exprs.push(self.recover_or_err(error, closers)?);
+            }
+        }
+        if parsed_first_expr {
             while self.accept(TokenKind::Comma).is_some() {
-                if let Some(expr) = self.parse_expr()? {
-                    exprs.push(expr);
+                match self.parse_expr() {
+                    Ok(Some(expr)) => exprs.push(expr),
+                    Ok(None) => {}
+                    Err(error) => exprs.push(self.recover_or_err(error, closers)?),
                 }
             }
         }
@@ -495,13 +1273,29 @@ impl<'source> Parser<'source> {
     }
 }
 
-pub fn parse(source: &str) -> Result<Expr> {
-    Parser {
+/// Parses `source` in recovery mode: a syntax error inside a delimited
+/// construct (`parse_tuple`, `parse_vector`, `parse_map`, `parse_block`,
+/// comma-separated `let`s) is recorded and recovered from by
+/// synchronizing to the next comma, matching close-delimiter, or
+/// statement keyword, rather than aborting the whole parse. Returns a
+/// best-effort tree alongside every diagnostic collected along the way.
+pub fn parse(source: &str) -> (Expr, Vec<Error>) {
+    let tokens = match tokenize(source) {
+        Ok(tokens) => tokens,
+        Err(error) => return (Expr::Void, vec![error]),
+    };
+    let mut parser = Parser {
         source,
-        tokens: tokenize(source)?,
+        tokens,
         index: 0,
-    }
-    .parse()
+        recovering: true,
+        errors: Vec::new(),
+    };
+    let expr = match parser.parse(&[]) {
+        Ok(expr) => expr,
+        Err(_) => unreachable!("a recovering parser never propagates an Err"),
+    };
+    (expr, parser.errors)
 }
 
 pub fn parse_expr(source: &str) -> Result<Expr> {
@@ -509,8 +1303,11 @@ pub fn parse_expr(source: &str) -> Result<Expr> {
         source,
         tokens: tokenize(source)?,
         index: 0,
+        recovering: false,
+        errors: Vec::new(),
     }
     .parse_expr()?
+    .map(|spanned| spanned.node)
     .unwrap_or(Expr::Void);
     Ok(expr)
 }
@@ -519,6 +1316,27 @@ pub fn parse_expr(source: &str) -> Result<Expr> {
 mod tests {
     use super::*;
 
+    /// Wraps `node` with a throwaway span for building expected trees in
+    /// tests: `Spanned`'s `PartialEq` only compares `node`, so the span
+    /// value here is never inspected.
+    fn spanned<T>(node: T) -> Spanned<T> {
+        Spanned::new(
+            node,
+            Span {
+                start: Position {
+                    offset: 0,
+                    line: 1,
+                    column: 1,
+                },
+                end: Position {
+                    offset: 0,
+                    line: 1,
+                    column: 1,
+                },
+            },
+        )
+    }
+
     #[test]
     fn test_parse_identifier() {
         assert_eq!(parse_expr("x"), Ok(Expr::Identifier("x".into())));
@@ -550,6 +1368,26 @@ mod tests {
             parse_expr(r#""\xFF""#).unwrap_err().kind,
             ErrorKind::InvalidEscapeSequence
         );
+        assert_eq!(
+            parse_expr(r#""\u{48}\u{65}\u{6C}\u{6C}\u{6F}""#),
+            Ok(Expr::String("Hello".into()))
+        );
+        assert_eq!(
+            parse_expr(r#""\u{1F600}""#),
+            Ok(Expr::String("\u{1F600}".into()))
+        );
+        assert_eq!(
+            parse_expr(r#""\u{}""#).unwrap_err().kind,
+            ErrorKind::InvalidEscapeSequence
+        );
+        assert_eq!(
+            parse_expr(r#""\u{D800}""#).unwrap_err().kind,
+            ErrorKind::InvalidEscapeSequence
+        );
+        assert_eq!(
+            parse_expr(r#""\u{1000000}""#).unwrap_err().kind,
+            ErrorKind::InvalidEscapeSequence
+        );
     }
 
     #[test]
@@ -597,7 +1435,7 @@ mod tests {
             parse_expr("(1)"),
             Ok(Expr::Tuple(Tuple {
                 tag: None,
-                positional: vec![Expr::Integer(1)],
+                positional: vec![spanned(Expr::Integer(1))],
                 named: vec![],
             }))
         );
@@ -605,7 +1443,11 @@ mod tests {
             parse_expr("(1, 2, 3)"),
             Ok(Expr::Tuple(Tuple {
                 tag: None,
-                positional: vec![Expr::Integer(1), Expr::Integer(2), Expr::Integer(3)],
+                positional: vec![
+                    spanned(Expr::Integer(1)),
+                    spanned(Expr::Integer(2)),
+                    spanned(Expr::Integer(3))
+                ],
                 named: vec![],
             }))
         );
@@ -613,7 +1455,7 @@ mod tests {
             parse_expr("Point(1, 2)"),
             Ok(Expr::Tuple(Tuple {
                 tag: Some("Point".into()),
-                positional: vec![Expr::Integer(1), Expr::Integer(2)],
+                positional: vec![spanned(Expr::Integer(1)), spanned(Expr::Integer(2))],
                 named: vec![],
             }))
         );
@@ -623,8 +1465,8 @@ mod tests {
                 tag: None,
                 positional: vec![],
                 named: vec![
-                    ("x".into(), Expr::Integer(1)),
-                    ("y".into(), Expr::Integer(2)),
+                    ("x".into(), spanned(Expr::Integer(1))),
+                    ("y".into(), spanned(Expr::Integer(2))),
                 ]
             }))
         );
@@ -632,42 +1474,158 @@ mod tests {
             parse_expr(r#"Person("id", name: "Bob", age: 49)"#),
             Ok(Expr::Tuple(Tuple {
                 tag: Some("Person".into()),
-                positional: vec![Expr::String("id".into())],
+                positional: vec![spanned(Expr::String("id".into()))],
                 named: vec![
-                    ("name".into(), Expr::String("Bob".into())),
-                    ("age".into(), Expr::Integer(49))
+                    ("name".into(), spanned(Expr::String("Bob".into()))),
+                    ("age".into(), spanned(Expr::Integer(49)))
                 ],
             }))
         )
     }
 
+    #[test]
+    fn test_parse_call() {
+        assert_eq!(
+            parse_expr("len(xs)"),
+            Ok(Expr::Call(
+                Box::new(spanned(Expr::Identifier("len".into()))),
+                vec![spanned(Expr::Identifier("xs".into()))],
+            ))
+        );
+        assert_eq!(
+            parse_expr("concat([1, 2], [3, 4])"),
+            Ok(Expr::Call(
+                Box::new(spanned(Expr::Identifier("concat".into()))),
+                vec![
+                    spanned(Expr::Vector(vec![
+                        spanned(Expr::Integer(1)),
+                        spanned(Expr::Integer(2))
+                    ])),
+                    spanned(Expr::Vector(vec![
+                        spanned(Expr::Integer(3)),
+                        spanned(Expr::Integer(4))
+                    ])),
+                ],
+            ))
+        );
+        assert_eq!(
+            parse_expr("noargs()"),
+            Ok(Expr::Call(Box::new(spanned(Expr::Identifier("noargs".into()))), vec![]))
+        );
+        // A capitalized head before `(` is a tagged tuple, not a call.
+        assert_eq!(
+            parse_expr("Point(1, 2)"),
+            Ok(Expr::Tuple(Tuple {
+                tag: Some("Point".into()),
+                positional: vec![spanned(Expr::Integer(1)), spanned(Expr::Integer(2))],
+                named: vec![],
+            }))
+        );
+    }
+
     #[test]
     fn test_parse_vector() {
-        assert_eq!(parse_expr("[1]"), Ok(Expr::Vector(vec![Expr::Integer(1)])));
+        assert_eq!(
+            parse_expr("[1]"),
+            Ok(Expr::Vector(vec![spanned(Expr::Integer(1))]))
+        );
         assert_eq!(
             parse_expr("[1, 2, 3]"),
             Ok(Expr::Vector(vec![
-                Expr::Integer(1),
-                Expr::Integer(2),
-                Expr::Integer(3)
+                spanned(Expr::Integer(1)),
+                spanned(Expr::Integer(2)),
+                spanned(Expr::Integer(3))
             ]))
         )
     }
 
+    #[test]
+    fn test_parse_vector_repeat() {
+        assert_eq!(
+            parse_expr("[1; 3]"),
+            Ok(Expr::VectorRepeat {
+                element: Box::new(spanned(Expr::Integer(1))),
+                count: Box::new(spanned(Expr::Integer(3))),
+            })
+        );
+        assert_eq!(
+            parse_expr("[1; 0]"),
+            Ok(Expr::VectorRepeat {
+                element: Box::new(spanned(Expr::Integer(1))),
+                count: Box::new(spanned(Expr::Integer(0))),
+            })
+        );
+        // The count is an arbitrary sub-expression, not just a literal.
+        assert_eq!(
+            parse_expr("[0; width]"),
+            Ok(Expr::VectorRepeat {
+                element: Box::new(spanned(Expr::Integer(0))),
+                count: Box::new(spanned(Expr::Identifier("width".into()))),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_map() {
+        assert_eq!(parse_expr("{}"), Ok(Expr::Void));
+        assert_eq!(
+            parse_expr("{x: 1}"),
+            Ok(Expr::Map(vec![(
+                spanned(Expr::Identifier("x".into())),
+                spanned(Expr::Integer(1))
+            )]))
+        );
+        assert_eq!(
+            parse_expr("{x: 1, y: 2}"),
+            Ok(Expr::Map(vec![
+                (
+                    spanned(Expr::Identifier("x".into())),
+                    spanned(Expr::Integer(1))
+                ),
+                (
+                    spanned(Expr::Identifier("y".into())),
+                    spanned(Expr::Integer(2))
+                ),
+            ]))
+        );
+        assert_eq!(
+            parse_expr("{x: x}"),
+            Ok(Expr::Map(vec![(
+                spanned(Expr::Identifier("x".into())),
+                spanned(Expr::Identifier("x".into()))
+            )]))
+        );
+        assert_eq!(
+            parse_expr(r#"{"a": 1, Tag: 2, 3: 4}"#),
+            Ok(Expr::Map(vec![
+                (
+                    spanned(Expr::String("a".into())),
+                    spanned(Expr::Integer(1))
+                ),
+                (spanned(Expr::Tag("Tag".into())), spanned(Expr::Integer(2))),
+                (spanned(Expr::Integer(3)), spanned(Expr::Integer(4))),
+            ]))
+        );
+        assert_eq!(
+            parse_expr("{x:}").unwrap_err().kind,
+            ErrorKind::TrailingColon
+        );
+    }
+
     #[test]
     fn test_operators() {
         assert_eq!(
             parse_expr("0 + 0"),
             Ok(Expr::Operator(
                 OperatorKind::Add,
-                vec![Expr::Integer(0).into(), Expr::Integer(0).into()]
+                vec![spanned(Expr::Integer(0)), spanned(Expr::Integer(0))]
             ))
         );
         assert_eq!(
             parse_expr("0 * 0"),
             Ok(Expr::Operator(
                 OperatorKind::Multiply,
-                vec![Expr::Integer(0).into(), Expr::Integer(0).into()]
+                vec![spanned(Expr::Integer(0)), spanned(Expr::Integer(0))]
             ))
         );
         assert_eq!(
@@ -675,11 +1633,11 @@ mod tests {
             Ok(Expr::Operator(
                 OperatorKind::Add,
                 vec![
-                    Expr::Integer(0),
-                    Expr::Operator(
+                    spanned(Expr::Integer(0)),
+                    spanned(Expr::Operator(
                         OperatorKind::Multiply,
-                        vec![Expr::Integer(0), Expr::Integer(0)]
-                    )
+                        vec![spanned(Expr::Integer(0)), spanned(Expr::Integer(0))]
+                    ))
                 ],
             ))
         );
@@ -688,84 +1646,504 @@ mod tests {
             Ok(Expr::Operator(
                 OperatorKind::Subtract,
                 vec![
-                    Expr::Operator(
+                    spanned(Expr::Operator(
                         OperatorKind::Add,
                         vec![
-                            Expr::Operator(
+                            spanned(Expr::Operator(
                                 OperatorKind::Multiply,
-                                vec![Expr::Integer(0), Expr::Integer(0)],
-                            ),
-                            Expr::Operator(
+                                vec![spanned(Expr::Integer(0)), spanned(Expr::Integer(0))],
+                            )),
+                            spanned(Expr::Operator(
                                 OperatorKind::Divide,
-                                vec![Expr::Integer(0), Expr::Integer(0)],
-                            ),
+                                vec![spanned(Expr::Integer(0)), spanned(Expr::Integer(0))],
+                            )),
                         ]
-                    ),
-                    Expr::Integer(0)
+                    )),
+                    spanned(Expr::Integer(0))
                 ]
             )),
         )
     }
 
+    #[test]
+    fn test_modulo_operator() {
+        // Modulo sits at the same tier as multiply/divide, so this reads as
+        // `0 + ((0 % 0) * 0)`.
+        assert_eq!(
+            parse_expr("0 + 0 % 0 * 0"),
+            Ok(Expr::Operator(
+                OperatorKind::Add,
+                vec![
+                    spanned(Expr::Integer(0)),
+                    spanned(Expr::Operator(
+                        OperatorKind::Multiply,
+                        vec![
+                            spanned(Expr::Operator(
+                                OperatorKind::Modulo,
+                                vec![spanned(Expr::Integer(0)), spanned(Expr::Integer(0))]
+                            )),
+                            spanned(Expr::Integer(0))
+                        ]
+                    ))
+                ]
+            ))
+        );
+    }
+
+    #[test]
+    fn test_comparison_and_boolean_operators() {
+        assert_eq!(
+            parse_expr("0 == 0"),
+            Ok(Expr::Operator(
+                OperatorKind::Equal,
+                vec![spanned(Expr::Integer(0)), spanned(Expr::Integer(0))]
+            ))
+        );
+        assert_eq!(
+            parse_expr("0 != 0"),
+            Ok(Expr::Operator(
+                OperatorKind::NotEqual,
+                vec![spanned(Expr::Integer(0)), spanned(Expr::Integer(0))]
+            ))
+        );
+        assert_eq!(
+            parse_expr("0 <= 0 && 0 >= 0"),
+            Ok(Expr::Operator(
+                OperatorKind::And,
+                vec![
+                    spanned(Expr::Operator(
+                        OperatorKind::LessEqual,
+                        vec![spanned(Expr::Integer(0)), spanned(Expr::Integer(0))]
+                    )),
+                    spanned(Expr::Operator(
+                        OperatorKind::GreaterEqual,
+                        vec![spanned(Expr::Integer(0)), spanned(Expr::Integer(0))]
+                    ))
+                ]
+            ))
+        );
+        // Comparison binds tighter than boolean, and additive tighter than
+        // comparison, so this reads as `(0 < (0 + 0)) || (0 > (0 - 0))`.
+        assert_eq!(
+            parse_expr("0 < 0 + 0 || 0 > 0 - 0"),
+            Ok(Expr::Operator(
+                OperatorKind::Or,
+                vec![
+                    spanned(Expr::Operator(
+                        OperatorKind::Less,
+                        vec![
+                            spanned(Expr::Integer(0)),
+                            spanned(Expr::Operator(
+                                OperatorKind::Add,
+                                vec![spanned(Expr::Integer(0)), spanned(Expr::Integer(0))]
+                            ))
+                        ]
+                    )),
+                    spanned(Expr::Operator(
+                        OperatorKind::Greater,
+                        vec![
+                            spanned(Expr::Integer(0)),
+                            spanned(Expr::Operator(
+                                OperatorKind::Subtract,
+                                vec![spanned(Expr::Integer(0)), spanned(Expr::Integer(0))]
+                            ))
+                        ]
+                    ))
+                ]
+            ))
+        );
+    }
+
+    #[test]
+    fn test_exponent_is_right_associative() {
+        // `2 ^ 3 ^ 2` nests as `2 ^ (3 ^ 2)`, not `(2 ^ 3) ^ 2`.
+        assert_eq!(
+            parse_expr("2 ^ 3 ^ 2"),
+            Ok(Expr::Operator(
+                OperatorKind::Exponent,
+                vec![
+                    spanned(Expr::Integer(2)),
+                    spanned(Expr::Operator(
+                        OperatorKind::Exponent,
+                        vec![spanned(Expr::Integer(3)), spanned(Expr::Integer(2))]
+                    ))
+                ]
+            ))
+        );
+        // Exponent binds tighter than multiplicative.
+        assert_eq!(
+            parse_expr("2 * 3 ^ 2"),
+            Ok(Expr::Operator(
+                OperatorKind::Multiply,
+                vec![
+                    spanned(Expr::Integer(2)),
+                    spanned(Expr::Operator(
+                        OperatorKind::Exponent,
+                        vec![spanned(Expr::Integer(3)), spanned(Expr::Integer(2))]
+                    ))
+                ]
+            ))
+        );
+    }
+
+    #[test]
+    fn test_operator_sections() {
+        assert_eq!(
+            parse_expr(r"\+"),
+            Ok(Expr::Function(
+                spanned(Pattern::Identifier("a".into())),
+                spanned(Expr::Function(
+                    spanned(Pattern::Identifier("b".into())),
+                    spanned(Expr::Operator(
+                        OperatorKind::Add,
+                        vec![
+                            spanned(Expr::Identifier("a".into())),
+                            spanned(Expr::Identifier("b".into()))
+                        ]
+                    ))
+                    .into()
+                ))
+                .into()
+            ))
+        );
+        assert_eq!(
+            parse_expr(r"\-"),
+            Ok(Expr::Function(
+                spanned(Pattern::Identifier("a".into())),
+                spanned(Expr::Function(
+                    spanned(Pattern::Identifier("b".into())),
+                    spanned(Expr::Operator(
+                        OperatorKind::Subtract,
+                        vec![
+                            spanned(Expr::Identifier("a".into())),
+                            spanned(Expr::Identifier("b".into()))
+                        ]
+                    ))
+                    .into()
+                ))
+                .into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_unary_operators() {
+        assert_eq!(
+            parse_expr("-x"),
+            Ok(Expr::Operator(
+                OperatorKind::Negate,
+                vec![spanned(Expr::Identifier("x".into()))]
+            ))
+        );
+        assert_eq!(
+            parse_expr("!flag"),
+            Ok(Expr::Operator(
+                OperatorKind::Not,
+                vec![spanned(Expr::Identifier("flag".into()))]
+            ))
+        );
+        // `(1 + 2)` is a one-element tuple (see `test_parse_tuple`), so
+        // negating it wraps that tuple rather than the bare `Add` node.
+        assert_eq!(
+            parse_expr("-(1 + 2)"),
+            Ok(Expr::Operator(
+                OperatorKind::Negate,
+                vec![spanned(Expr::Tuple(Tuple {
+                    tag: None,
+                    positional: vec![spanned(Expr::Operator(
+                        OperatorKind::Add,
+                        vec![spanned(Expr::Integer(1)), spanned(Expr::Integer(2))]
+                    ))],
+                    named: vec![],
+                }))]
+            ))
+        );
+        // Literal integers and floats still fold their sign directly into
+        // the literal rather than going through `Negate`.
+        assert_eq!(parse_expr("-3"), Ok(Expr::Integer(-3)));
+        assert_eq!(parse_expr("-3.0"), Ok(Expr::Float(-3.0)));
+        // `!` binds tighter than `&&`, so this reads as `(!a) && b`.
+        assert_eq!(
+            parse_expr("!a && b"),
+            Ok(Expr::Operator(
+                OperatorKind::And,
+                vec![
+                    spanned(Expr::Operator(
+                        OperatorKind::Not,
+                        vec![spanned(Expr::Identifier("a".into()))]
+                    )),
+                    spanned(Expr::Identifier("b".into()))
+                ]
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(
+            parse_expr("0..3"),
+            Ok(Expr::Range {
+                start: Box::new(spanned(Expr::Integer(0))),
+                end: Some(Box::new(spanned(Expr::Integer(3)))),
+                inclusive: false,
+            })
+        );
+        assert_eq!(
+            parse_expr("0..=3"),
+            Ok(Expr::Range {
+                start: Box::new(spanned(Expr::Integer(0))),
+                end: Some(Box::new(spanned(Expr::Integer(3)))),
+                inclusive: true,
+            })
+        );
+        // Omitting the end produces an open-ended range.
+        assert_eq!(
+            parse_expr("0.."),
+            Ok(Expr::Range {
+                start: Box::new(spanned(Expr::Integer(0))),
+                end: None,
+                inclusive: false,
+            })
+        );
+        // Both endpoints are arbitrary sub-expressions, not just literals.
+        assert_eq!(
+            parse_expr("0..len"),
+            Ok(Expr::Range {
+                start: Box::new(spanned(Expr::Integer(0))),
+                end: Some(Box::new(spanned(Expr::Identifier("len".into())))),
+                inclusive: false,
+            })
+        );
+        assert_eq!(
+            parse_expr("a..a + 10"),
+            Ok(Expr::Range {
+                start: Box::new(spanned(Expr::Identifier("a".into()))),
+                end: Some(Box::new(spanned(Expr::Operator(
+                    OperatorKind::Add,
+                    vec![spanned(Expr::Identifier("a".into())), spanned(Expr::Integer(10))]
+                )))),
+                inclusive: false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_destructuring_patterns() {
+        assert_eq!(
+            parse_ok("let (a, b) = (1, 2), a"),
+            Expr::Block(
+                vec![],
+                vec![Let(
+                    spanned(Pattern::Tuple(PatternTuple {
+                        tag: None,
+                        positional: vec![
+                            spanned(Pattern::Identifier("a".into())),
+                            spanned(Pattern::Identifier("b".into())),
+                        ],
+                        named: vec![],
+                    })),
+                    spanned(Expr::Tuple(Tuple {
+                        tag: None,
+                        positional: vec![spanned(Expr::Integer(1)), spanned(Expr::Integer(2))],
+                        named: vec![],
+                    }))
+                    .into()
+                )],
+                vec![spanned(Expr::Identifier("a".into()))]
+            )
+        );
+        assert_eq!(
+            parse_ok("let Point(x, y) = p, x"),
+            Expr::Block(
+                vec![],
+                vec![Let(
+                    spanned(Pattern::Tuple(PatternTuple {
+                        tag: Some("Point".into()),
+                        positional: vec![
+                            spanned(Pattern::Identifier("x".into())),
+                            spanned(Pattern::Identifier("y".into())),
+                        ],
+                        named: vec![],
+                    })),
+                    spanned(Expr::Identifier("p".into())).into()
+                )],
+                vec![spanned(Expr::Identifier("x".into()))]
+            )
+        );
+        assert_eq!(
+            parse_ok("let [head, rest] = xs, head"),
+            Expr::Block(
+                vec![],
+                vec![Let(
+                    spanned(Pattern::Vector(vec![
+                        spanned(Pattern::Identifier("head".into())),
+                        spanned(Pattern::Identifier("rest".into())),
+                    ])),
+                    spanned(Expr::Identifier("xs".into())).into()
+                )],
+                vec![spanned(Expr::Identifier("head".into()))]
+            )
+        );
+        assert_eq!(
+            parse_ok("let (_, b) = (1, 2), b"),
+            Expr::Block(
+                vec![],
+                vec![Let(
+                    spanned(Pattern::Tuple(PatternTuple {
+                        tag: None,
+                        positional: vec![
+                            spanned(Pattern::Wildcard),
+                            spanned(Pattern::Identifier("b".into())),
+                        ],
+                        named: vec![],
+                    })),
+                    spanned(Expr::Tuple(Tuple {
+                        tag: None,
+                        positional: vec![spanned(Expr::Integer(1)), spanned(Expr::Integer(2))],
+                        named: vec![],
+                    }))
+                    .into()
+                )],
+                vec![spanned(Expr::Identifier("b".into()))]
+            )
+        );
+        assert_eq!(
+            parse_ok("let Person(name, age: age) = p, name"),
+            Expr::Block(
+                vec![],
+                vec![Let(
+                    spanned(Pattern::Tuple(PatternTuple {
+                        tag: Some("Person".into()),
+                        positional: vec![spanned(Pattern::Identifier("name".into()))],
+                        named: vec![("age".into(), spanned(Pattern::Identifier("age".into())))],
+                    })),
+                    spanned(Expr::Identifier("p".into())).into()
+                )],
+                vec![spanned(Expr::Identifier("name".into()))]
+            )
+        );
+    }
+
+    #[test]
+    fn test_bound_idents() {
+        assert_eq!(
+            bound_idents(&Pattern::Identifier("x".into())),
+            vec!["x".to_string()]
+        );
+        assert_eq!(bound_idents(&Pattern::Wildcard), Vec::<String>::new());
+        assert_eq!(bound_idents(&Pattern::Tag("None".into())), Vec::<String>::new());
+        assert_eq!(
+            bound_idents(&Pattern::Tuple(PatternTuple {
+                tag: Some("Point".into()),
+                positional: vec![spanned(Pattern::Identifier("x".into()))],
+                named: vec![("y".into(), spanned(Pattern::Identifier("y".into())))],
+            })),
+            vec!["x".to_string(), "y".to_string()]
+        );
+        assert_eq!(
+            bound_idents(&Pattern::Vector(vec![
+                spanned(Pattern::Identifier("head".into())),
+                spanned(Pattern::Tuple(PatternTuple {
+                    tag: None,
+                    positional: vec![
+                        spanned(Pattern::Identifier("a".into())),
+                        spanned(Pattern::Wildcard),
+                    ],
+                    named: vec![],
+                })),
+            ])),
+            vec!["head".to_string(), "a".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_binding_is_rejected() {
+        assert_eq!(
+            parse_expr("{let (x, x) = (1, 2), x}").unwrap_err().kind,
+            ErrorKind::DuplicateBinding
+        );
+    }
+
+    /// Parses `source` and asserts recovery mode collected no diagnostics,
+    /// so tests can keep comparing against a plain `Expr` tree.
+    fn parse_ok(source: &str) -> Expr {
+        let (expr, errors) = parse(source);
+        assert_eq!(errors, vec![]);
+        expr
+    }
+
     #[test]
     fn test_parse_block() {
         assert_eq!(parse_expr("{}"), Ok(Expr::Void));
-        assert_eq!(parse(""), Ok(Expr::Void));
+        assert_eq!(parse_ok(""), Expr::Void);
         assert_eq!(
-            parse("let x = 0, let y = 1, [x, y]"),
-            Ok(Expr::Block(
+            parse_ok("let x = 0, let y = 1, [x, y]"),
+            Expr::Block(
                 vec![],
                 vec![
-                    Let(Pattern::Identifier("x".into()), Expr::Integer(0).into()),
-                    Let(Pattern::Identifier("y".into()), Expr::Integer(1).into())
+                    Let(
+                        spanned(Pattern::Identifier("x".into())),
+                        spanned(Expr::Integer(0)).into()
+                    ),
+                    Let(
+                        spanned(Pattern::Identifier("y".into())),
+                        spanned(Expr::Integer(1)).into()
+                    )
                 ],
-                vec![Expr::Vector(vec![
-                    Expr::Identifier("x".into()),
-                    Expr::Identifier("y".into())
-                ])]
-            ))
+                vec![spanned(Expr::Vector(vec![
+                    spanned(Expr::Identifier("x".into())),
+                    spanned(Expr::Identifier("y".into()))
+                ]))]
+            )
         );
         assert_eq!(
-            parse("{{1}}"),
-            Ok(Expr::Block(
+            parse_ok("{{1}}"),
+            Expr::Block(
                 vec![],
                 vec![],
-                vec![Expr::Block(
+                vec![spanned(Expr::Block(
                     vec![],
                     vec![],
-                    vec![Expr::Block(vec![], vec![], vec![Expr::Integer(1)])]
-                )]
-            ))
+                    vec![spanned(Expr::Block(
+                        vec![],
+                        vec![],
+                        vec![spanned(Expr::Integer(1))]
+                    ))]
+                ))]
+            )
         );
         assert_eq!(
-            parse(
+            parse_ok(
                 r#"
                 let a = "test"
                 let b = "test"
                 (a, b)
                 "#
             ),
-            Ok(Expr::Block(
+            Expr::Block(
                 vec![],
                 vec![
                     Let(
-                        Pattern::Identifier("a".into()),
-                        Expr::String("test".into()).into()
+                        spanned(Pattern::Identifier("a".into())),
+                        spanned(Expr::String("test".into())).into()
                     ),
                     Let(
-                        Pattern::Identifier("b".into()),
-                        Expr::String("test".into()).into(),
+                        spanned(Pattern::Identifier("b".into())),
+                        spanned(Expr::String("test".into())).into(),
                     )
                 ],
-                vec![Expr::Tuple(Tuple {
+                vec![spanned(Expr::Tuple(Tuple {
                     tag: None,
-                    positional: vec![Expr::Identifier("a".into()), Expr::Identifier("b".into())],
+                    positional: vec![
+                        spanned(Expr::Identifier("a".into())),
+                        spanned(Expr::Identifier("b".into()))
+                    ],
                     named: vec![],
-                })]
-            ))
+                }))]
+            )
         );
         assert_eq!(
-            parse(
+            parse_ok(
                 r#"
                 import (
                     x "x"
@@ -775,25 +2153,48 @@ mod tests {
                 point
                 "#
             ),
-            Ok(Expr::Block(
+            Expr::Block(
                 vec![
                     Import("x".into(), "x".into()),
                     Import("y".into(), "y".into())
                 ],
                 vec![Let(
-                    Pattern::Identifier("point".into()),
-                    Expr::Tuple(Tuple {
+                    spanned(Pattern::Identifier("point".into())),
+                    spanned(Expr::Tuple(Tuple {
                         tag: Some("Point".into()),
                         positional: vec![
-                            Expr::Identifier("x".into()),
-                            Expr::Identifier("y".into())
+                            spanned(Expr::Identifier("x".into())),
+                            spanned(Expr::Identifier("y".into()))
                         ],
                         named: vec![],
-                    })
+                    }))
                     .into()
                 )],
-                vec![Expr::Identifier("point".into())]
-            ))
+                vec![spanned(Expr::Identifier("point".into()))]
+            )
         );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_recovery() {
+        let (expr, errors) = parse(r#"(1, "\z", 3)"#);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, ErrorKind::InvalidEscapeSequence);
+        assert_eq!(
+            expr,
+            Expr::Block(
+                vec![],
+                vec![],
+                vec![spanned(Expr::Tuple(Tuple {
+                    tag: None,
+                    positional: vec![
+                        spanned(Expr::Integer(1)),
+                        spanned(Expr::Error),
+                        spanned(Expr::Integer(3))
+                    ],
+                    named: vec![],
+                }))]
+            )
+        );
+    }
+}
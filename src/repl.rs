@@ -1,12 +1,127 @@
 use crate::source::SourceEvent;
-use rustyline::{error::ReadlineError, Editor};
-use std::sync::mpsc::Sender;
+use crate::symbol::Interner;
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::{ValidationContext, ValidationResult, Validator};
+use rustyline::{Context, Editor, Helper};
+use std::path::PathBuf;
+use std::sync::{mpsc::Sender, Arc, Mutex};
+
+/// Surface names of the `BuiltIn` arithmetic/conversion primitives, offered
+/// alongside interned identifiers during completion.
+const BUILT_IN_NAMES: &[&str] = &["add", "sub", "mul", "div", "mod", "exp", "index", "convert"];
+
+/// Backs tab-completion, bracket-aware multi-line continuation, and history
+/// for the REPL's `Editor`. `known_exports` is the integration point for
+/// whatever evaluates a `Module`'s `export` expression to register the
+/// names it binds; it starts empty until that's wired up.
+struct VampHelper {
+    interner: Arc<Mutex<Interner>>,
+    known_exports: Arc<Mutex<Vec<String>>>,
+}
+
+impl VampHelper {
+    fn candidates(&self, prefix: &str) -> Vec<Pair> {
+        let interner = self.interner.lock().unwrap();
+        let known_exports = self.known_exports.lock().unwrap();
+        BUILT_IN_NAMES
+            .iter()
+            .map(|name| name.to_string())
+            .chain(interner.names().map(str::to_owned))
+            .chain(known_exports.iter().cloned())
+            .filter(|name| name.starts_with(prefix))
+            .map(|name| Pair {
+                display: name.clone(),
+                replacement: name,
+            })
+            .collect()
+    }
+}
+
+impl Completer for VampHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let start = line[..pos]
+            .rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .map_or(0, |i| i + 1);
+        Ok((start, self.candidates(&line[start..pos])))
+    }
+}
+
+impl Hinter for VampHelper {
+    type Hint = String;
+}
+
+impl Highlighter for VampHelper {}
+
+impl Validator for VampHelper {
+    /// Keeps the prompt open across newlines until every `(`/`[`/`{` and
+    /// string literal opened so far has been closed, so a multi-line
+    /// `Block` or `Function` is sent as a single `SourceEvent::Repl`.
+    fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
+        Ok(if is_balanced(ctx.input()) {
+            ValidationResult::Valid(None)
+        } else {
+            ValidationResult::Incomplete
+        })
+    }
+}
+
+impl Helper for VampHelper {}
+
+fn is_balanced(input: &str) -> bool {
+    let mut depth = 0i32;
+    let mut string_delimiter: Option<char> = None;
+    let mut escaped = false;
+    for c in input.chars() {
+        if let Some(delimiter) = string_delimiter {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == delimiter {
+                string_delimiter = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => string_delimiter = Some(c),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    depth <= 0 && string_delimiter.is_none()
+}
+
+pub fn repl(
+    events: Sender<SourceEvent>,
+    root: PathBuf,
+    interner: Arc<Mutex<Interner>>,
+    known_exports: Arc<Mutex<Vec<String>>>,
+) {
+    let mut editor = Editor::<VampHelper, DefaultHistory>::new().unwrap();
+    editor.set_helper(Some(VampHelper {
+        interner,
+        known_exports,
+    }));
+
+    let history_path = root.join(".vamp_history");
+    let _ = editor.load_history(&history_path);
 
-pub fn repl(events: Sender<SourceEvent>) {
-    let mut editor = Editor::<()>::new().unwrap();
     loop {
         match editor.readline("> ") {
             Ok(line) => {
+                let _ = editor.add_history_entry(line.as_str());
                 events.send(SourceEvent::Repl(line)).unwrap();
             }
             Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => {
@@ -20,4 +135,6 @@ pub fn repl(events: Sender<SourceEvent>) {
             }
         }
     }
-}
\ No newline at end of file
+
+    let _ = editor.save_history(&history_path);
+}
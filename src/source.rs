@@ -1,4 +1,7 @@
-use std::{ops::Index, path::PathBuf};
+use std::{
+    ops::Index,
+    path::{Path, PathBuf},
+};
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct Position {
@@ -25,6 +28,7 @@ impl Index<Span> for str {
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum ErrorKind {
     UnterminatedString,
+    UnterminatedComment,
     InvalidEscapeSequence,
     InvalidInteger,
     InvalidFloat,
@@ -32,6 +36,8 @@ pub enum ErrorKind {
     InvalidCharacter,
     InvalidToken,
     TuplePositionalAfterNamed,
+    TrailingColon,
+    DuplicateBinding,
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -47,4 +53,219 @@ pub enum SourceEvent {
     File(PathBuf),
     Repl(String),
     Exit,
-}
\ No newline at end of file
+}
+
+/// Identifies one source registered with a [`SourceMap`]. Opaque outside
+/// this module; use [`SourceMap::resolve`] to get back a path and position.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FileId(usize);
+
+struct SourceEntry {
+    path: PathBuf,
+    content: String,
+    lo: usize,
+    hi: usize,
+}
+
+/// A registry of every source file (or REPL input) the compiler has lexed,
+/// each assigned a contiguous global offset range `[lo, hi)` with a
+/// one-byte gap between entries so adjacent ranges never touch. `Position`
+/// and `Span` only carry offsets, so a `Tokens` lexing one file's text in
+/// isolation can have its offsets shifted by that file's `lo` to land in
+/// this shared global space, and any `Span` can then be traced back to the
+/// file it came from via [`SourceMap::resolve`].
+#[derive(Default)]
+pub struct SourceMap {
+    entries: Vec<SourceEntry>,
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `content` under `path`, returning the `FileId` assigned to
+    /// it. The caller offsets every `Position` it lexes from `content` by
+    /// `self.offset(file)` before storing it in a `Span`, so that span's
+    /// offsets land in this map's shared global space.
+    pub fn register(&mut self, path: PathBuf, content: String) -> FileId {
+        let lo = self.entries.last().map_or(0, |entry| entry.hi + 1);
+        let hi = lo + content.len();
+        self.entries.push(SourceEntry {
+            path,
+            content,
+            lo,
+            hi,
+        });
+        FileId(self.entries.len() - 1)
+    }
+
+    /// The global offset at which `file`'s content begins.
+    pub fn offset(&self, file: FileId) -> usize {
+        self.entries[file.0].lo
+    }
+
+    /// Finds the source whose `[lo, hi)` range contains `global_offset` via
+    /// binary search over the (by construction, sorted) `lo` values, then
+    /// resolves it to a path and a `Position` local to that file.
+    pub fn resolve(&self, global_offset: usize) -> Option<(FileId, &Path, Position)> {
+        let index = match self
+            .entries
+            .binary_search_by(|entry| entry.lo.cmp(&global_offset))
+        {
+            Ok(index) => index,
+            Err(0) => return None,
+            Err(index) => index - 1,
+        };
+        let entry = &self.entries[index];
+        if global_offset >= entry.hi {
+            return None;
+        }
+        let position = Self::locate(&entry.content, global_offset - entry.lo);
+        Some((FileId(index), &entry.path, position))
+    }
+
+    /// Scans `content` up to `local_offset`, counting newlines to recover
+    /// the line and column a bare offset corresponds to.
+    fn locate(content: &str, local_offset: usize) -> Position {
+        let mut line = 1;
+        let mut column = 1;
+        for c in content[..local_offset].chars() {
+            if c == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+        Position {
+            offset: local_offset,
+            line,
+            column,
+        }
+    }
+
+    /// Renders the start of `span` as a `path:line:col` location string.
+    pub fn describe(&self, span: Span) -> String {
+        match self.resolve(span.start.offset) {
+            Some((_, path, position)) => {
+                format!("{}:{}:{}", path.display(), position.line, position.column)
+            }
+            None => "<unknown>".to_string(),
+        }
+    }
+
+    /// Slices the original source bytes `span` covers, for printing a
+    /// snippet alongside an error. Returns `None` if `span` doesn't fall
+    /// within a single registered source.
+    pub fn snippet(&self, span: Span) -> Option<&str> {
+        let (file, _, _) = self.resolve(span.start.offset)?;
+        let entry = &self.entries[file.0];
+        if span.end.offset > entry.hi {
+            return None;
+        }
+        let content: &str = &entry.content;
+        let local = Span {
+            start: Position {
+                offset: span.start.offset - entry.lo,
+                ..span.start
+            },
+            end: Position {
+                offset: span.end.offset - entry.lo,
+                ..span.end
+            },
+        };
+        Some(&content[local])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_assigns_non_overlapping_ranges() {
+        let mut map = SourceMap::new();
+        let a = map.register(PathBuf::from("a.vamp"), "abc".to_string());
+        let b = map.register(PathBuf::from("b.vamp"), "xy".to_string());
+        assert_eq!(map.offset(a), 0);
+        assert_eq!(map.offset(b), 4); // "abc" is 3 bytes, plus a one-byte gap.
+    }
+
+    #[test]
+    fn test_resolve_finds_the_owning_file_and_position() {
+        let mut map = SourceMap::new();
+        map.register(PathBuf::from("a.vamp"), "ab\ncd".to_string());
+        let b = map.register(PathBuf::from("b.vamp"), "xyz".to_string());
+
+        let (file, path, position) = map.resolve(map.offset(b) + 1).unwrap();
+        assert_eq!(file, b);
+        assert_eq!(path, Path::new("b.vamp"));
+        assert_eq!(position.line, 1);
+        assert_eq!(position.column, 2);
+    }
+
+    #[test]
+    fn test_resolve_tracks_lines_within_a_file() {
+        let mut map = SourceMap::new();
+        let a = map.register(PathBuf::from("a.vamp"), "ab\ncd".to_string());
+
+        let (file, _, position) = map.resolve(4).unwrap();
+        assert_eq!(file, a);
+        assert_eq!(position.line, 2);
+        assert_eq!(position.column, 2);
+    }
+
+    #[test]
+    fn test_resolve_rejects_offsets_in_the_gap_between_files() {
+        let mut map = SourceMap::new();
+        map.register(PathBuf::from("a.vamp"), "abc".to_string());
+        map.register(PathBuf::from("b.vamp"), "xy".to_string());
+
+        assert_eq!(map.resolve(3), None);
+    }
+
+    #[test]
+    fn test_describe_renders_path_line_and_column() {
+        let mut map = SourceMap::new();
+        map.register(PathBuf::from("a.vamp"), "let x".to_string());
+        let b = map.register(PathBuf::from("sub/b.vamp"), "1 + 2".to_string());
+
+        assert_eq!(
+            map.describe(Span {
+                start: Position {
+                    offset: map.offset(b) + 2,
+                    line: 1,
+                    column: 3
+                },
+                end: Position {
+                    offset: map.offset(b) + 2,
+                    line: 1,
+                    column: 3
+                }
+            }),
+            "sub/b.vamp:1:3"
+        );
+    }
+
+    #[test]
+    fn test_snippet_slices_the_original_bytes() {
+        let mut map = SourceMap::new();
+        let a = map.register(PathBuf::from("a.vamp"), "let x = 1".to_string());
+        let lo = map.offset(a);
+
+        let span = Span {
+            start: Position {
+                offset: lo,
+                line: 1,
+                column: 1,
+            },
+            end: Position {
+                offset: lo + 3,
+                line: 1,
+                column: 4,
+            },
+        };
+        assert_eq!(map.snippet(span), Some("let"));
+    }
+}
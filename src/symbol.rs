@@ -0,0 +1,58 @@
+//! Interns identifiers into small `Copy` handles so the AST and evaluator
+//! can pass them around and compare them cheaply instead of cloning
+//! `String`s everywhere.
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u32);
+
+#[derive(Debug, Default)]
+pub struct Interner {
+    map: HashMap<String, Symbol>,
+    vector: Vec<String>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Interner::default()
+    }
+
+    /// Interns `string` and returns a `Symbol`, reusing the existing one if
+    /// `string` has already been interned.
+    pub fn intern(&mut self, string: &str) -> Symbol {
+        if let Some(&symbol) = self.map.get(string) {
+            return symbol;
+        }
+        let symbol = Symbol(self.vector.len() as u32);
+        self.map.insert(string.into(), symbol);
+        self.vector.push(string.into());
+        symbol
+    }
+
+    /// Looks up the string value of `symbol`.
+    pub fn lookup(&self, symbol: Symbol) -> &str {
+        &self.vector[symbol.0 as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interning_the_same_string_twice_returns_the_same_symbol() {
+        let mut interner = Interner::new();
+        assert_eq!(interner.intern("x"), interner.intern("x"));
+    }
+
+    #[test]
+    fn lookup_returns_the_interned_string() {
+        let mut interner = Interner::new();
+        let strings = ["", "x0", "@self"];
+        let symbols: Vec<_> = strings.iter().map(|&s| interner.intern(s)).collect();
+        for (symbol, string) in symbols.iter().zip(strings) {
+            assert_eq!(interner.lookup(*symbol), string);
+        }
+    }
+}
@@ -1,27 +1,56 @@
 use crate::source::{Error, ErrorKind, Position, Result, Span};
 
-fn is_whitespace(c: u8) -> bool {
-    matches!(c, b' ' | b'\t' | b'\n' | b'\r')
+fn is_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\n' | '\r')
 }
 
-fn is_identifier_first(c: u8) -> bool {
-    matches!(c, b'a'..=b'z' | b'_')
+/// `XID_Start` (approximated via `char::is_alphabetic`, which Rust derives
+/// from the Unicode `Alphabetic` property) plus `_`, per UAX #31.
+fn is_identifier_start(c: char) -> bool {
+    c == '_' || c.is_alphabetic()
 }
 
-fn is_identifier_rest(c: u8) -> bool {
-    matches!(c, b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'_')
+/// `XID_Continue` (approximated via `char::is_alphanumeric`) plus `_`.
+fn is_identifier_continue(c: char) -> bool {
+    c == '_' || c.is_alphanumeric()
 }
 
-fn is_symbol_first(c: u8) -> bool {
-    matches!(c, b'A'..=b'Z')
+/// Routes general category `Lu`/`Lt` to `Tag` and leaves `Ll`/`Lo` (and
+/// everything else) to `Identifier`, preserving Vamp's existing
+/// lowercase-vs-uppercase distinction for multibyte letters.
+fn is_tag_start(c: char) -> bool {
+    // `char::is_uppercase` covers `Lu` but not `Lt`, whose letters are
+    // listed here in full.
+    c.is_uppercase()
+        || matches!(
+            c,
+            '\u{01C5}'
+                | '\u{01C8}'
+                | '\u{01CB}'
+                | '\u{01F2}'
+                | '\u{1F88}'..='\u{1F8F}'
+                | '\u{1F98}'..='\u{1F9F}'
+                | '\u{1FA8}'..='\u{1FAF}'
+                | '\u{1FBC}'
+                | '\u{1FCC}'
+                | '\u{1FFC}'
+        )
+}
+
+fn is_digit(c: char) -> bool {
+    c.is_ascii_digit()
+}
+
+fn is_hex_digit(c: char) -> bool {
+    c.is_ascii_hexdigit()
 }
 
-fn is_symbol_rest(c: u8) -> bool {
-    matches!(c, b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'_')
+fn is_octal_digit(c: char) -> bool {
+    matches!(c, '0'..='7')
 }
 
-fn is_digit(c: u8) -> bool {
-    matches!(c, b'0'..=b'9')
+fn is_binary_digit(c: char) -> bool {
+    matches!(c, '0' | '1')
 }
 
 #[allow(dead_code)]
@@ -36,12 +65,28 @@ pub enum TokenKind {
     RightBrace,
     Comma,
     Colon,
+    Semicolon,
     Equals,
+    EqualsEquals,
+    Bang,
+    BangEquals,
+    Less,
+    LessEquals,
+    Greater,
+    GreaterEquals,
+    AmpersandAmpersand,
+    PipePipe,
+    Caret,
     Plus,
     Minus,
     Times,
     Divide,
+    Percent,
+    Backslash,
     Arrow,
+    Dot,
+    DotDot,
+    DotDotEquals,
     Identifier,
     Tag,
     Integer,
@@ -58,8 +103,9 @@ pub struct Token {
 }
 
 pub struct Tokens<'source> {
-    source: &'source [u8],
-    byte: u8,
+    source: &'source str,
+    ch: char,
+    ch_len: usize,
     start: Position,
     end: Position,
     last_token_kind: Option<TokenKind>,
@@ -68,10 +114,11 @@ pub struct Tokens<'source> {
 
 impl<'source> Tokens<'source> {
     pub fn new(source: &str) -> Tokens {
-        let bytes = source.as_bytes();
+        let (ch, ch_len) = Self::decode(source, 0);
         Tokens {
-            source: bytes,
-            byte: *bytes.first().unwrap_or(&b'\0'),
+            source,
+            ch,
+            ch_len,
             start: Position {
                 offset: 0,
                 line: 1,
@@ -87,6 +134,15 @@ impl<'source> Tokens<'source> {
         }
     }
 
+    /// Decodes the UTF-8 scalar starting at `offset`, returning `('\0', 0)`
+    /// past the end of `source`.
+    fn decode(source: &str, offset: usize) -> (char, usize) {
+        match source.get(offset..).and_then(|rest| rest.chars().next()) {
+            Some(c) => (c, c.len_utf8()),
+            None => ('\0', 0),
+        }
+    }
+
     fn span(&self) -> Span {
         Span {
             start: self.start,
@@ -99,8 +155,14 @@ impl<'source> Tokens<'source> {
         }
     }
 
+    /// The char one past `self.ch`, without consuming anything. Used to
+    /// tell a float's fractional `.` apart from the first `.` of `..`/`..=`.
+    fn peek(&self) -> char {
+        Self::decode(self.source, self.end.offset + self.ch_len).0
+    }
+
     fn advance(&mut self) {
-        if self.byte == b'\n' {
+        if self.ch == '\n' {
             self.end.line += 1;
             self.end.column = 1;
             self.auto_insert_comma = matches!(
@@ -117,15 +179,17 @@ impl<'source> Tokens<'source> {
         } else {
             self.end.column += 1;
         }
-        self.end.offset += 1;
-        self.byte = *self.source.get(self.end.offset).unwrap_or(&b'\0');
+        self.end.offset += self.ch_len;
+        let (ch, ch_len) = Self::decode(self.source, self.end.offset);
+        self.ch = ch;
+        self.ch_len = ch_len;
     }
 
     fn accept_if<P>(&mut self, p: P) -> bool
     where
-        P: FnOnce(u8) -> bool,
+        P: FnOnce(char) -> bool,
     {
-        if p(self.byte) {
+        if p(self.ch) {
             self.advance();
             true
         } else {
@@ -135,13 +199,37 @@ impl<'source> Tokens<'source> {
 
     fn accept_while<P>(&mut self, p: P)
     where
-        P: Fn(u8) -> bool,
+        P: Fn(char) -> bool,
     {
-        while p(self.byte) {
+        while p(self.ch) {
             self.advance();
         }
     }
 
+    /// Consumes a run of digits classified by `is_digit`, allowing `_`
+    /// separators between digits but never leading, trailing, or doubled
+    /// (a separator is only consumed when a further digit follows it).
+    /// Returns the count of digits consumed, not counting separators; a
+    /// dangling separator left unconsumed shows up as `self.ch == '_'`
+    /// immediately after the call, for the caller to reject.
+    fn accept_digits<P>(&mut self, is_digit: P) -> usize
+    where
+        P: Fn(char) -> bool,
+    {
+        let mut digits = 0;
+        loop {
+            if is_digit(self.ch) {
+                self.advance();
+                digits += 1;
+            } else if self.ch == '_' && digits > 0 && is_digit(self.peek()) {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+        digits
+    }
+
     fn ok(&mut self, kind: TokenKind) -> Option<Result<Token>> {
         self.last_token_kind = Some(kind);
         Some(Ok(Token {
@@ -157,59 +245,158 @@ impl<'source> Tokens<'source> {
         }))
     }
 
-    fn skip_whitespace(&mut self) {
+    /// Skips runs of whitespace interleaved with `#` line comments and
+    /// nested `#{ ... }#` block comments in one pass.
+    fn skip_whitespace(&mut self) -> Option<Result<Token>> {
         loop {
             self.accept_while(is_whitespace);
-            if self.accept_if(|c| c == b'#') {
-                self.accept_while(|c| c != b'\n');
+            let opener = self.end;
+            if self.accept_if(|c| c == '#') {
+                if self.accept_if(|c| c == '{') {
+                    if let Err(error) = self.skip_block_comment(opener) {
+                        return Some(Err(error));
+                    }
+                } else {
+                    self.accept_while(|c| c != '\n');
+                }
             } else {
                 break;
             }
         }
+        None
+    }
+
+    /// Skips a `#{ ... }#` block comment whose outermost `#{` has already
+    /// been consumed, tracking nesting depth so an inner `#{ ... }#` doesn't
+    /// prematurely close the outer one. `opener` is the position of the
+    /// outermost `#`, used as the span if EOF is reached before depth
+    /// returns to zero.
+    fn skip_block_comment(&mut self, opener: Position) -> Result<()> {
+        let mut depth = 1;
+        while depth > 0 {
+            if self.ch == '\0' {
+                self.start = opener;
+                return Err(Error {
+                    kind: ErrorKind::UnterminatedComment,
+                    span: self.span(),
+                });
+            } else if self.ch == '#' && self.peek() == '{' {
+                self.advance();
+                self.advance();
+                depth += 1;
+            } else if self.ch == '}' && self.peek() == '#' {
+                self.advance();
+                self.advance();
+                depth -= 1;
+            } else {
+                self.advance();
+            }
+        }
+        Ok(())
     }
 
     fn next_punctuation(&mut self) -> Option<Result<Token>> {
-        if self.accept_if(|c| c == b'(') {
+        if self.accept_if(|c| c == '(') {
             self.ok(TokenKind::LeftParenthesis)
-        } else if self.accept_if(|c| c == b')') {
+        } else if self.accept_if(|c| c == ')') {
             self.ok(TokenKind::RightParenthesis)
-        } else if self.accept_if(|c| c == b'[') {
+        } else if self.accept_if(|c| c == '[') {
             self.ok(TokenKind::LeftBracket)
-        } else if self.accept_if(|c| c == b']') {
+        } else if self.accept_if(|c| c == ']') {
             self.ok(TokenKind::RightBracket)
-        } else if self.accept_if(|c| c == b'{') {
+        } else if self.accept_if(|c| c == '{') {
             self.ok(TokenKind::LeftBrace)
-        } else if self.accept_if(|c| c == b'}') {
+        } else if self.accept_if(|c| c == '}') {
             self.ok(TokenKind::RightBrace)
-        } else if self.accept_if(|c| c == b',') {
+        } else if self.accept_if(|c| c == ',') {
             self.ok(TokenKind::Comma)
-        } else if self.accept_if(|c| c == b':') {
+        } else if self.accept_if(|c| c == ':') {
             self.ok(TokenKind::Colon)
-        } else if self.accept_if(|c| c == b'=') {
-            self.ok(TokenKind::Equals)
-        } else if self.accept_if(|c| c == b'+') {
+        } else if self.accept_if(|c| c == ';') {
+            self.ok(TokenKind::Semicolon)
+        } else if self.accept_if(|c| c == '=') {
+            if self.accept_if(|c| c == '=') {
+                self.ok(TokenKind::EqualsEquals)
+            } else {
+                self.ok(TokenKind::Equals)
+            }
+        } else if self.accept_if(|c| c == '!') {
+            if self.accept_if(|c| c == '=') {
+                self.ok(TokenKind::BangEquals)
+            } else {
+                self.ok(TokenKind::Bang)
+            }
+        } else if self.accept_if(|c| c == '<') {
+            if self.accept_if(|c| c == '=') {
+                self.ok(TokenKind::LessEquals)
+            } else {
+                self.ok(TokenKind::Less)
+            }
+        } else if self.accept_if(|c| c == '>') {
+            if self.accept_if(|c| c == '=') {
+                self.ok(TokenKind::GreaterEquals)
+            } else {
+                self.ok(TokenKind::Greater)
+            }
+        } else if self.accept_if(|c| c == '&') {
+            if self.accept_if(|c| c == '&') {
+                self.ok(TokenKind::AmpersandAmpersand)
+            } else {
+                self.err(ErrorKind::InvalidCharacter)
+            }
+        } else if self.accept_if(|c| c == '|') {
+            if self.accept_if(|c| c == '|') {
+                self.ok(TokenKind::PipePipe)
+            } else {
+                self.err(ErrorKind::InvalidCharacter)
+            }
+        } else if self.accept_if(|c| c == '^') {
+            self.ok(TokenKind::Caret)
+        } else if self.accept_if(|c| c == '+') {
             self.ok(TokenKind::Plus)
-        } else if self.accept_if(|c| c == b'-') {
-            if self.accept_if(|c| c == b'>') {
+        } else if self.accept_if(|c| c == '-') {
+            if self.accept_if(|c| c == '>') {
                 self.ok(TokenKind::Arrow)
             } else {
                 self.ok(TokenKind::Minus)
             }
-        } else if self.accept_if(|c| c == b'*') {
+        } else if self.accept_if(|c| c == '*') {
             self.ok(TokenKind::Times)
-        } else if self.accept_if(|c| c == b'/') {
+        } else if self.accept_if(|c| c == '/') {
             self.ok(TokenKind::Divide)
+        } else if self.accept_if(|c| c == '%') {
+            self.ok(TokenKind::Percent)
+        } else if self.accept_if(|c| c == '\\') {
+            self.ok(TokenKind::Backslash)
+        } else if self.accept_if(|c| c == '.') {
+            if self.accept_if(|c| c == '.') {
+                if self.accept_if(|c| c == '=') {
+                    self.ok(TokenKind::DotDotEquals)
+                } else {
+                    self.ok(TokenKind::DotDot)
+                }
+            } else {
+                self.ok(TokenKind::Dot)
+            }
         } else {
             None
         }
     }
 
-    fn next_identifier(&mut self) -> Option<Result<Token>> {
-        if self.accept_if(is_identifier_first) {
-            self.accept_while(is_identifier_rest);
+    /// Lexes an identifier or a tag in one pass: the first character's case
+    /// decides which (`Lu`/`Lt` start a `Tag`, `Ll`/`Lo`/`_` start an
+    /// `Identifier`), and both accept the same `XID_Continue` run after it.
+    fn next_word(&mut self) -> Option<Result<Token>> {
+        if is_tag_start(self.ch) {
+            self.advance();
+            self.accept_while(is_identifier_continue);
+            self.ok(TokenKind::Tag)
+        } else if is_identifier_start(self.ch) {
+            self.advance();
+            self.accept_while(is_identifier_continue);
             self.ok(match &self.source[self.start.offset..self.end.offset] {
-                b"let" => TokenKind::Let,
-                b"import" => TokenKind::Import,
+                "let" => TokenKind::Let,
+                "import" => TokenKind::Import,
                 _ => TokenKind::Identifier,
             })
         } else {
@@ -217,39 +404,85 @@ impl<'source> Tokens<'source> {
         }
     }
 
-    fn next_tag(&mut self) -> Option<Result<Token>> {
-        if self.accept_if(is_symbol_first) {
-            self.accept_while(is_symbol_rest);
-            self.ok(TokenKind::Tag)
-        } else {
-            None
+    /// Lexes a `0x`/`0o`/`0b`-prefixed integer, consuming digits of the
+    /// matching radix with `_` separators and rejecting an empty or
+    /// dangling-separator digit run with `InvalidInteger`.
+    fn next_radix_integer(&mut self) -> Option<Result<Token>> {
+        let is_radix_digit: fn(char) -> bool = match self.peek() {
+            'x' | 'X' => is_hex_digit,
+            'o' | 'O' => is_octal_digit,
+            'b' | 'B' => is_binary_digit,
+            _ => unreachable!("only called after checking the radix prefix"),
+        };
+        self.advance(); // '0'
+        self.advance(); // radix letter
+        let digits = self.accept_digits(is_radix_digit);
+        if digits == 0 || self.ch == '_' {
+            self.accept_while(|c| c == '_');
+            return self.err(ErrorKind::InvalidInteger);
         }
+        self.ok(TokenKind::Integer)
     }
 
     fn next_number(&mut self) -> Option<Result<Token>> {
-        if self.accept_if(is_digit) {
-            self.accept_while(is_digit);
-            if self.accept_if(|c| c == b'.') {
-                self.accept_while(is_digit);
-                self.ok(TokenKind::Float)
-            } else {
-                self.ok(TokenKind::Integer)
+        if !is_digit(self.ch) {
+            return None;
+        }
+        if self.ch == '0' && matches!(self.peek(), 'x' | 'X' | 'o' | 'O' | 'b' | 'B') {
+            return self.next_radix_integer();
+        }
+
+        self.accept_digits(is_digit);
+        if self.ch == '_' {
+            self.accept_while(|c| c == '_');
+            return self.err(ErrorKind::InvalidInteger);
+        }
+
+        let mut kind = TokenKind::Integer;
+
+        // A `.` is only this literal's fractional separator when a digit
+        // follows it directly: `0..3` stays `Integer` then `DotDot` (the
+        // second `.` rules it out), and `1.foo` stays `Integer` then `Dot`
+        // so method-like access on an integer still tokenizes sensibly.
+        if self.ch == '.' && is_digit(self.peek()) {
+            self.advance();
+            self.accept_digits(is_digit);
+            if self.ch == '_' {
+                self.accept_while(|c| c == '_');
+                return self.err(ErrorKind::InvalidFloat);
             }
-        } else {
-            None
+            kind = TokenKind::Float;
         }
+
+        // Once an `e`/`E` directly follows the literal we're committed to
+        // an exponent, so a dangling one (`1e`) is `InvalidFloat` rather
+        // than being left for the next token to choke on.
+        if matches!(self.ch, 'e' | 'E') {
+            self.advance();
+            self.accept_if(|c| c == '+' || c == '-');
+            if self.accept_digits(is_digit) == 0 {
+                return self.err(ErrorKind::InvalidFloat);
+            }
+            if self.ch == '_' {
+                self.accept_while(|c| c == '_');
+                return self.err(ErrorKind::InvalidFloat);
+            }
+            kind = TokenKind::Float;
+        }
+
+        self.ok(kind)
     }
 
     fn next_string(&mut self) -> Option<Result<Token>> {
-        if self.accept_if(|c| c == b'"') {
+        if self.accept_if(|c| c == '"') {
             loop {
-                if self.byte == b'\0' {
+                if self.ch == '\0' {
                     return self.err(ErrorKind::UnterminatedString);
-                } else if self.accept_if(|c| c == b'\\') {
-                    if !self.accept_if(|c| c != b'\0') {
+                } else if self.accept_if(|c| c == '\\') {
+                    if !self.accept_if(|c| c != '\0') {
                         return self.err(ErrorKind::UnterminatedString);
                     }
-                } else if self.accept_if(|c| c == b'"') {
+                } else if self.accept_if(|c| c == '"') {
                     return self.ok(TokenKind::String);
                 } else {
                     self.advance();
@@ -260,8 +493,10 @@ impl<'source> Tokens<'source> {
         }
     }
 
+    /// Consumes one invalid/stray codepoint, producing `InvalidCharacter`
+    /// with a span covering the whole codepoint rather than a single byte.
     fn next_error(&mut self) -> Option<Result<Token>> {
-        if self.byte != b'\0' {
+        if self.ch != '\0' {
             self.advance();
             self.err(ErrorKind::InvalidCharacter)
         } else {
@@ -274,7 +509,9 @@ impl<'source> Iterator for Tokens<'source> {
     type Item = Result<Token>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.skip_whitespace();
+        if let Some(error) = self.skip_whitespace() {
+            return Some(error);
+        }
         self.start = self.end;
 
         if self.auto_insert_comma {
@@ -284,8 +521,7 @@ impl<'source> Iterator for Tokens<'source> {
         }
 
         self.next_punctuation()
-            .or_else(|| self.next_identifier())
-            .or_else(|| self.next_tag())
+            .or_else(|| self.next_word())
             .or_else(|| self.next_number())
             .or_else(|| self.next_string())
             .or_else(|| self.next_error())
@@ -321,7 +557,7 @@ mod tests {
     #[test]
     fn test_punctuation() {
         assert_eq!(
-            token_slices("( ) [ ] { } , : = + - * / ->"),
+            token_slices("( ) [ ] { } , : ; = + - * / % \\ ->"),
             Ok(vec![
                 (TokenKind::LeftParenthesis, "("),
                 (TokenKind::RightParenthesis, ")"),
@@ -331,16 +567,56 @@ mod tests {
                 (TokenKind::RightBrace, "}"),
                 (TokenKind::Comma, ","),
                 (TokenKind::Colon, ":"),
+                (TokenKind::Semicolon, ";"),
                 (TokenKind::Equals, "="),
                 (TokenKind::Plus, "+"),
                 (TokenKind::Minus, "-"),
                 (TokenKind::Times, "*"),
                 (TokenKind::Divide, "/"),
+                (TokenKind::Percent, "%"),
+                (TokenKind::Backslash, "\\"),
                 (TokenKind::Arrow, "->"),
             ])
         );
     }
 
+    #[test]
+    fn test_comparison_and_boolean_punctuation() {
+        assert_eq!(
+            token_slices("== ! != < <= > >= && || ^"),
+            Ok(vec![
+                (TokenKind::EqualsEquals, "=="),
+                (TokenKind::Bang, "!"),
+                (TokenKind::BangEquals, "!="),
+                (TokenKind::Less, "<"),
+                (TokenKind::LessEquals, "<="),
+                (TokenKind::Greater, ">"),
+                (TokenKind::GreaterEquals, ">="),
+                (TokenKind::AmpersandAmpersand, "&&"),
+                (TokenKind::PipePipe, "||"),
+                (TokenKind::Caret, "^"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_range_punctuation() {
+        assert_eq!(
+            token_slices("0..3 0..=3 0.. 0.5"),
+            Ok(vec![
+                (TokenKind::Integer, "0"),
+                (TokenKind::DotDot, ".."),
+                (TokenKind::Integer, "3"),
+                (TokenKind::Integer, "0"),
+                (TokenKind::DotDotEquals, "..="),
+                (TokenKind::Integer, "3"),
+                (TokenKind::Integer, "0"),
+                (TokenKind::DotDot, ".."),
+                (TokenKind::Float, "0.5"),
+            ])
+        );
+    }
+
     #[test]
     fn test_auto_insert_comma() {
         assert_eq!(
@@ -413,15 +689,106 @@ mod tests {
     #[test]
     fn test_floats() {
         assert_eq!(
-            token_slices("0. 0.5 3.14"),
+            token_slices("0.5 3.14"),
+            Ok(vec![(TokenKind::Float, "0.5"), (TokenKind::Float, "3.14"),])
+        );
+    }
+
+    #[test]
+    fn test_dot_not_followed_by_digit_is_not_a_float() {
+        // A `.` with no digit after it is field/method access, not a
+        // fractional separator, so `1.foo` stays `Integer`, `Dot`,
+        // `Identifier` rather than erroring on a bogus `1.` float.
+        assert_eq!(
+            token_slices("1.foo 0."),
             Ok(vec![
-                (TokenKind::Float, "0."),
-                (TokenKind::Float, "0.5"),
-                (TokenKind::Float, "3.14"),
+                (TokenKind::Integer, "1"),
+                (TokenKind::Dot, "."),
+                (TokenKind::Identifier, "foo"),
+                (TokenKind::Integer, "0"),
+                (TokenKind::Dot, "."),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_radix_integers() {
+        assert_eq!(
+            token_slices("0x1F 0o17 0b1010 0XAB 0B11 0O7"),
+            Ok(vec![
+                (TokenKind::Integer, "0x1F"),
+                (TokenKind::Integer, "0o17"),
+                (TokenKind::Integer, "0b1010"),
+                (TokenKind::Integer, "0XAB"),
+                (TokenKind::Integer, "0B11"),
+                (TokenKind::Integer, "0O7"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_radix_integer_with_no_digits_is_invalid() {
+        assert_eq!(
+            token_slices("0x").unwrap_err().kind,
+            ErrorKind::InvalidInteger
+        );
+        assert_eq!(
+            token_slices("0b").unwrap_err().kind,
+            ErrorKind::InvalidInteger
+        );
+    }
+
+    #[test]
+    fn test_digit_separators() {
+        assert_eq!(
+            token_slices("1_000_000 0xFF_FF 3.14_15 1_0e1_0"),
+            Ok(vec![
+                (TokenKind::Integer, "1_000_000"),
+                (TokenKind::Integer, "0xFF_FF"),
+                (TokenKind::Float, "3.14_15"),
+                (TokenKind::Float, "1_0e1_0"),
             ])
         );
     }
 
+    #[test]
+    fn test_dangling_separator_is_invalid() {
+        assert_eq!(
+            token_slices("1_").unwrap_err().kind,
+            ErrorKind::InvalidInteger
+        );
+        assert_eq!(
+            token_slices("1__2").unwrap_err().kind,
+            ErrorKind::InvalidInteger
+        );
+        assert_eq!(
+            token_slices("1.5_").unwrap_err().kind,
+            ErrorKind::InvalidFloat
+        );
+    }
+
+    #[test]
+    fn test_exponent_floats() {
+        assert_eq!(
+            token_slices("1e10 3.14e-2 2.5E+3 1E5"),
+            Ok(vec![
+                (TokenKind::Float, "1e10"),
+                (TokenKind::Float, "3.14e-2"),
+                (TokenKind::Float, "2.5E+3"),
+                (TokenKind::Float, "1E5"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_dangling_exponent_is_invalid() {
+        assert_eq!(token_slices("1e").unwrap_err().kind, ErrorKind::InvalidFloat);
+        assert_eq!(
+            token_slices("1e-").unwrap_err().kind,
+            ErrorKind::InvalidFloat
+        );
+    }
+
     #[test]
     fn test_strings() {
         assert_eq!(
@@ -460,4 +827,54 @@ mod tests {
             })
         );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_unicode_identifiers_and_tags() {
+        assert_eq!(
+            token_slices("café λ"),
+            Ok(vec![
+                (TokenKind::Identifier, "café"),
+                (TokenKind::Identifier, "λ"),
+            ])
+        );
+        assert_eq!(
+            token_slices("Ångström Δt"),
+            Ok(vec![
+                (TokenKind::Tag, "Ångström"),
+                (TokenKind::Tag, "Δt"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_nested_block_comments() {
+        assert_eq!(
+            token_slices("#{ outer #{ inner }# still outer }# x"),
+            Ok(vec![(TokenKind::Identifier, "x")])
+        );
+        assert_eq!(token_slices("#{ a # not a line comment }# y").map(|t| t.len()), Ok(1));
+    }
+
+    #[test]
+    fn test_unterminated_block_comment() {
+        let error = token_slices("#{ outer #{ inner }# still open").unwrap_err();
+        assert_eq!(error.kind, ErrorKind::UnterminatedComment);
+        assert_eq!(error.span.start.offset, 0);
+    }
+
+    #[test]
+    fn test_invalid_codepoint_spans_whole_char() {
+        assert_eq!(
+            token_slices("日"),
+            Ok(vec![(TokenKind::Identifier, "日")])
+        );
+        // `§` is a multibyte character that isn't a recognized identifier,
+        // punctuation, or digit start, so it should still produce a single
+        // `InvalidCharacter` error spanning the whole codepoint, not a
+        // single byte of it.
+        let error = token_slices("§").unwrap_err();
+        assert_eq!(error.kind, ErrorKind::InvalidCharacter);
+        assert_eq!(error.span.start.offset, 0);
+        assert_eq!(error.span.end.offset, "§".len());
+    }
+}
@@ -0,0 +1,36 @@
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::Path, path::PathBuf};
+use vamp_sym::Interner;
+use vamp_syntax::ast::Mod;
+
+/// A point-in-time capture of interned symbols and parsed modules, saved as
+/// a single `.vamp-cache` file in the package root. It records no file
+/// modification times, so it does not tell which files changed since the
+/// snapshot, and the `vamp` driver neither saves nor loads it. It serves as
+/// a debugging dump: the interner plus every module's AST can be replayed
+/// later to reproduce a failing program offline.
+#[derive(Serialize, Deserialize)]
+pub struct Cache {
+    pub interner: Interner,
+    pub modules: FxHashMap<String, Mod>,
+}
+
+/// Path to the cache file for a package rooted at `root`.
+fn cache_path(root: &Path) -> PathBuf {
+    root.join(".vamp-cache")
+}
+
+/// Writes `cache` to the package's cache file, overwriting any existing one.
+pub fn save(root: &Path, cache: &Cache) -> io::Result<()> {
+    let bytes =
+        bincode::serialize(cache).map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+    fs::write(cache_path(root), bytes)
+}
+
+/// Loads a previously saved cache for the package rooted at `root`, if one
+/// exists and can still be decoded.
+pub fn load(root: &Path) -> Option<Cache> {
+    let bytes = fs::read(cache_path(root)).ok()?;
+    bincode::deserialize(&bytes).ok()
+}
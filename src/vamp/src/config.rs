@@ -1,4 +1,9 @@
 use serde::{self, Deserialize, Serialize};
+use std::{
+    collections::{HashSet, VecDeque},
+    fs, io,
+    path::{Path, PathBuf},
+};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Config {
@@ -11,7 +16,7 @@ pub struct Package {
     pub name: Option<String>,
     pub version: Option<String>,
     #[serde(default)]
-    pub dependencies: Vec<String>,
+    pub dependencies: Vec<Dependency>,
     #[serde(default = "default_root")]
     pub root: String,
     #[serde(default = "default_entry")]
@@ -25,3 +30,125 @@ fn default_root() -> String {
 fn default_entry() -> String {
     "main.vamp".into()
 }
+
+/// A single dependency declaration, accepted either as a bare name
+/// (`"pkg"`) or a detailed table (`{ name = "pkg", path = "../pkg" }`).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Dependency {
+    Name(String),
+    Detailed(DetailedDependency),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DetailedDependency {
+    pub name: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub git: Option<String>,
+    #[serde(default)]
+    pub rev: Option<String>,
+    #[serde(default)]
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub tag: Option<String>,
+}
+
+impl Dependency {
+    pub fn name(&self) -> &str {
+        match self {
+            Dependency::Name(name) => name,
+            Dependency::Detailed(detailed) => &detailed.name,
+        }
+    }
+
+    fn version(&self) -> Option<&str> {
+        match self {
+            Dependency::Name(_) => None,
+            Dependency::Detailed(detailed) => detailed.version.as_deref(),
+        }
+    }
+
+    fn path(&self) -> Option<&str> {
+        match self {
+            Dependency::Name(_) => None,
+            Dependency::Detailed(detailed) => detailed.path.as_deref(),
+        }
+    }
+}
+
+/// A dependency resolved to the package root its own `vamp.toml` lives in.
+#[derive(Debug)]
+pub struct ResolvedDependency {
+    pub name: String,
+    pub root: PathBuf,
+}
+
+#[derive(Debug)]
+pub enum ResolveError {
+    ReadConfig(PathBuf, io::Error),
+    ParseConfig(PathBuf, toml::de::Error),
+    VersionConflict {
+        name: String,
+        first: String,
+        second: String,
+    },
+}
+
+/// Walks `package`'s dependencies (and theirs, recursively), producing a
+/// flattened, de-duplicated load order. Dependencies declared with a `path`
+/// are resolved relative to the package that declares them; anything else
+/// falls back to a shared vendor directory under `root`. Two dependencies
+/// on the same name with different pinned `version`s are reported as a
+/// conflict rather than silently picking one.
+pub fn resolve(root: &Path, package: &Package) -> Result<Vec<ResolvedDependency>, ResolveError> {
+    let mut resolved = Vec::new();
+    let mut seen = HashSet::new();
+    let mut versions: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+    let mut queue: VecDeque<(PathBuf, Dependency)> = package
+        .dependencies
+        .iter()
+        .cloned()
+        .map(|dependency| (root.to_owned(), dependency))
+        .collect();
+
+    while let Some((base, dependency)) = queue.pop_front() {
+        let name = dependency.name().to_owned();
+        if let Some(version) = dependency.version() {
+            if let Some(existing) = versions.insert(name.clone(), version.to_owned()) {
+                if existing != version {
+                    return Err(ResolveError::VersionConflict {
+                        name,
+                        first: existing,
+                        second: version.to_owned(),
+                    });
+                }
+            }
+        }
+        if !seen.insert(name.clone()) {
+            continue;
+        }
+
+        let dependency_root = match dependency.path() {
+            Some(path) => base.join(path),
+            None => root.join(".vamp").join("deps").join(&name),
+        };
+        let config_path = dependency_root.join("vamp.toml");
+        let text = fs::read_to_string(&config_path)
+            .map_err(|error| ResolveError::ReadConfig(config_path.clone(), error))?;
+        let config: Config = toml::from_str(&text)
+            .map_err(|error| ResolveError::ParseConfig(config_path.clone(), error))?;
+        for child in config.package.dependencies {
+            queue.push_back((dependency_root.clone(), child));
+        }
+        resolved.push(ResolvedDependency {
+            name,
+            root: dependency_root,
+        });
+    }
+
+    Ok(resolved)
+}
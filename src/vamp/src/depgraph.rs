@@ -0,0 +1,155 @@
+//! A directed graph of which modules import which, built from each parsed
+//! module's `deps` list. [`Session`](crate::Session) uses it to turn a
+//! changed module into exactly the set of modules that need to be
+//! re-evaluated: the module itself plus every module that transitively
+//! depends on it, then [`topo_sort`](DependencyGraph::topo_sort) puts that
+//! set in the order imports need to run before their importers.
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+#[derive(Default)]
+pub struct DependencyGraph {
+    // dependencies[module] = modules it imports.
+    dependencies: FxHashMap<String, FxHashSet<String>>,
+    // dependents[dependency] = modules that import `dependency`.
+    dependents: FxHashMap<String, FxHashSet<String>>,
+}
+
+impl DependencyGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the modules `module_path` depends on, replacing any edges
+    /// previously recorded for it.
+    pub fn record(&mut self, module_path: &str, deps: impl IntoIterator<Item = String>) {
+        self.forget(module_path);
+        let deps: FxHashSet<String> = deps.into_iter().collect();
+        for dep in &deps {
+            self.dependents
+                .entry(dep.clone())
+                .or_default()
+                .insert(module_path.to_string());
+        }
+        self.dependencies.insert(module_path.to_string(), deps);
+    }
+
+    fn forget(&mut self, module_path: &str) {
+        if let Some(deps) = self.dependencies.remove(module_path) {
+            for dep in deps {
+                if let Some(dependents) = self.dependents.get_mut(&dep) {
+                    dependents.remove(module_path);
+                }
+            }
+        }
+    }
+
+    /// Returns `changed` plus every module that transitively depends on it,
+    /// so a single save invalidates its whole wave of dependents at once.
+    pub fn invalidate(&self, changed: &str) -> FxHashSet<String> {
+        let mut wave = FxHashSet::default();
+        wave.insert(changed.to_string());
+        let mut frontier = vec![changed.to_string()];
+        while let Some(path) = frontier.pop() {
+            let Some(dependents) = self.dependents.get(&path) else {
+                continue;
+            };
+            for dependent in dependents {
+                if wave.insert(dependent.clone()) {
+                    frontier.push(dependent.clone());
+                }
+            }
+        }
+        wave
+    }
+
+    /// Topologically sorts `subset` so every module comes after its
+    /// dependencies (edges to modules outside `subset` are ignored, since
+    /// those aren't being re-evaluated). Returns the first module found to
+    /// be part of an import cycle as `Err`, rather than recursing forever.
+    pub fn topo_sort(&self, subset: &FxHashSet<String>) -> Result<Vec<String>, String> {
+        let mut order = vec![];
+        let mut state: FxHashMap<String, bool> = FxHashMap::default();
+        for module_path in subset {
+            self.visit(module_path, subset, &mut state, &mut order)?;
+        }
+        Ok(order)
+    }
+
+    /// Depth-first visit with `state` tracking, per module, whether it's
+    /// `false` (on the current path, not yet finished) or `true` (finished
+    /// and already pushed to `order`); seeing `false` again means a cycle.
+    fn visit(
+        &self,
+        module_path: &str,
+        subset: &FxHashSet<String>,
+        state: &mut FxHashMap<String, bool>,
+        order: &mut Vec<String>,
+    ) -> Result<(), String> {
+        match state.get(module_path) {
+            Some(true) => return Ok(()),
+            Some(false) => return Err(module_path.to_string()),
+            None => {}
+        }
+        state.insert(module_path.to_string(), false);
+        if let Some(deps) = self.dependencies.get(module_path) {
+            for dep in deps {
+                if subset.contains(dep) {
+                    self.visit(dep, subset, state, order)?;
+                }
+            }
+        }
+        state.insert(module_path.to_string(), true);
+        order.push(module_path.to_string());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalidate_includes_transitive_dependents() {
+        let mut graph = DependencyGraph::new();
+        graph.record("b", ["a".to_string()]);
+        graph.record("c", ["b".to_string()]);
+        graph.record("a", []);
+        let wave = graph.invalidate("a");
+        assert_eq!(
+            wave,
+            ["a", "b", "c"].map(String::from).into_iter().collect()
+        );
+    }
+
+    #[test]
+    fn record_replaces_previously_recorded_edges() {
+        let mut graph = DependencyGraph::new();
+        graph.record("b", ["a".to_string()]);
+        graph.record("b", []);
+        assert_eq!(
+            graph.invalidate("a"),
+            ["a"].map(String::from).into_iter().collect()
+        );
+    }
+
+    #[test]
+    fn topo_sort_orders_dependencies_before_dependents() {
+        let mut graph = DependencyGraph::new();
+        graph.record("a", []);
+        graph.record("b", ["a".to_string()]);
+        graph.record("c", ["a".to_string(), "b".to_string()]);
+        let subset = ["a", "b", "c"].map(String::from).into_iter().collect();
+        let order = graph.topo_sort(&subset).unwrap();
+        assert_eq!(order, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn topo_sort_detects_a_cycle() {
+        let mut graph = DependencyGraph::new();
+        graph.record("a", ["b".to_string()]);
+        graph.record("b", ["a".to_string()]);
+        let subset = ["a", "b"].map(String::from).into_iter().collect();
+        assert!(graph.topo_sort(&subset).is_err());
+    }
+}
@@ -0,0 +1,33 @@
+use std::path::Path;
+use vamp_syntax::SourceMap;
+
+/// Renders a syntax error as an editor-style diagnostic: a human-readable
+/// message, the offending location, and the source line underlined with
+/// `^^^` beneath the span. `path` is included in the location when the
+/// source came from a file rather than a REPL line.
+pub fn render_syntax_error(path: Option<&Path>, source: &str, error: &vamp_syntax::Error) -> String {
+    let map = SourceMap::new(source);
+    let start = map.locate(error.span.start);
+    let location = match path {
+        Some(path) => format!("{}:{}:{}", path.display(), start.line, start.column),
+        None => format!("{}:{}", start.line, start.column),
+    };
+    format!(
+        "error: {error}\n  --> {location}\n{}",
+        map.render_span(error.span)
+    )
+}
+
+/// Renders a runtime error as a short human-readable message. Unlike
+/// syntax errors, `vamp_eval::Error` carries no span, so there's no source
+/// line to underline.
+pub fn render_runtime_error(error: &vamp_eval::Error) -> String {
+    let message = match error {
+        vamp_eval::Error::Void => "expected a value, but got void",
+        vamp_eval::Error::Types => "value has the wrong type for this operation",
+        vamp_eval::Error::KeyNotFound => "tuple has no entry with that name",
+        vamp_eval::Error::Unbound => "unbound identifier",
+        vamp_eval::Error::Mismatch => "value did not match the pattern",
+    };
+    format!("error: {message}")
+}
@@ -1,6 +1,6 @@
 use notify::RecursiveMode;
 use notify_debouncer_mini::{new_debouncer, DebounceEventResult};
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use rustyline::error::ReadlineError;
 use rustyline::DefaultEditor;
 use std::{
@@ -15,14 +15,43 @@ use toml;
 use vamp_eval::{eval_module, eval_stmt, Mod, Scope, Value};
 use vamp_sym::Interner;
 use vamp_syntax::{parse_module, parse_stmt};
+mod cache;
 mod config;
+mod depgraph;
+mod diagnostics;
+mod manifest;
+mod module_graph;
 use config::Config;
+use depgraph::DependencyGraph;
 
 #[derive(Debug)]
 enum Error {
     IoError(io::Error),
-    SyntaxError(vamp_syntax::Error),
+    /// `source` is the text that failed to parse, kept alongside the error
+    /// so it can be rendered against a real source line later; `path` is
+    /// set for a file load and left `None` for a REPL line.
+    SyntaxError {
+        source: String,
+        error: vamp_syntax::Error,
+        path: Option<PathBuf>,
+    },
     RuntimeError(vamp_eval::Error),
+    /// The dependency graph found a module that (directly or indirectly)
+    /// imports itself, named by its dotted module path.
+    CyclicImport(String),
+}
+
+fn render_error(error: &Error) -> String {
+    match error {
+        Error::IoError(io_error) => format!("error: {io_error}"),
+        Error::SyntaxError { source, error, path } => {
+            diagnostics::render_syntax_error(path.as_deref(), source, error)
+        }
+        Error::RuntimeError(error) => diagnostics::render_runtime_error(error),
+        Error::CyclicImport(module_path) => {
+            format!("error: cyclic import involving module `{module_path}`")
+        }
+    }
 }
 
 struct Session {
@@ -31,6 +60,12 @@ struct Session {
     scope: Rc<RefCell<Scope>>,
     ctx: Rc<RefCell<Scope>>,
     modules: FxHashMap<String, Mod>,
+    /// Every module's parsed AST, kept around so a dependent can be
+    /// re-evaluated after a reload without re-reading or re-parsing it.
+    asts: FxHashMap<String, vamp_syntax::ast::Mod>,
+    /// Which modules import which, so a changed file can be turned into
+    /// exactly the set of modules that need re-evaluating.
+    graph: DependencyGraph,
 }
 
 impl Session {
@@ -41,36 +76,96 @@ impl Session {
             scope: Rc::new(RefCell::new(Scope::new(None))),
             ctx: Rc::new(RefCell::new(Scope::new(None))),
             modules: FxHashMap::default(),
+            asts: FxHashMap::default(),
+            graph: DependencyGraph::new(),
         }
     }
 
-    fn load(&mut self, path: &Path, reload: bool) -> Result<(), Error> {
-        let module_path = path
-            .with_extension("")
+    fn module_path(path: &Path) -> String {
+        path.with_extension("")
             .components()
             .map(|c| c.as_os_str().to_str().unwrap())
             .collect::<Vec<_>>()
-            .join(".");
-        if !reload && self.modules.contains_key(&module_path) {
-            return Ok(());
-        }
+            .join(".")
+    }
+
+    /// Parses `path` and caches its AST under its module path, recording
+    /// its deps in the dependency graph and recursing into any dependency
+    /// that hasn't been parsed yet (an already-cached one is assumed
+    /// unchanged). Every module path touched this way is added to `dirty`,
+    /// the set of modules that need (re-)evaluating.
+    fn load_ast(&mut self, path: &Path, dirty: &mut FxHashSet<String>) -> Result<String, Error> {
+        let module_path = Self::module_path(path);
         let source = fs::read_to_string(self.root.join(path)).map_err(Error::IoError)?;
-        let module = parse_module(&source, &mut self.interner).map_err(Error::SyntaxError)?;
+        let module = match parse_module(&source, &mut self.interner) {
+            Ok(module) => module,
+            Err(error) => {
+                return Err(Error::SyntaxError {
+                    source,
+                    error,
+                    path: Some(path.to_path_buf()),
+                })
+            }
+        };
+        let mut dep_module_paths = vec![];
         for dep in module.deps.iter() {
             let mut dep_path = PathBuf::new();
             for segment in dep.path.segments.iter() {
                 dep_path.push(self.interner.lookup(*segment));
             }
-            self.load(&dep_path, false)?;
+            let dep_module_path = Self::module_path(&dep_path);
+            if !self.asts.contains_key(&dep_module_path) {
+                self.load_ast(&dep_path, dirty)?;
+            }
+            dep_module_paths.push(dep_module_path);
         }
-        let module = eval_module(&module, self.scope.clone(), self.ctx.clone())
+        self.graph.record(&module_path, dep_module_paths);
+        self.asts.insert(module_path.clone(), module);
+        dirty.insert(module_path.clone());
+        Ok(module_path)
+    }
+
+    /// Re-evaluates the cached AST for `module_path` against the session's
+    /// shared scope, overwriting whatever bindings it previously defined.
+    fn eval_cached(&mut self, module_path: &str) -> Result<(), Error> {
+        let module_ast = &self.asts[module_path];
+        let module = eval_module(module_ast, self.scope.clone(), self.ctx.clone())
             .map_err(Error::RuntimeError)?;
-        self.modules.insert(module_path.into(), module);
+        self.modules.insert(module_path.to_string(), module);
+        Ok(())
+    }
+
+    fn load(&mut self, path: &Path, reload: bool) -> Result<(), Error> {
+        let module_path = Self::module_path(path);
+        if !reload && self.modules.contains_key(&module_path) {
+            return Ok(());
+        }
+        let mut dirty = FxHashSet::default();
+        self.load_ast(path, &mut dirty)?;
+        if reload {
+            dirty.extend(self.graph.invalidate(&module_path));
+        }
+        let order = self
+            .graph
+            .topo_sort(&dirty)
+            .map_err(Error::CyclicImport)?;
+        for module_path in order {
+            self.eval_cached(&module_path)?;
+        }
         Ok(())
     }
 
     fn eval_stmt(&mut self, stmt_source: &str) -> Result<Option<Value>, Error> {
-        let stmt = parse_stmt(stmt_source, &mut self.interner).map_err(Error::SyntaxError)?;
+        let stmt = match parse_stmt(stmt_source, &mut self.interner) {
+            Ok(stmt) => stmt,
+            Err(error) => {
+                return Err(Error::SyntaxError {
+                    source: stmt_source.to_string(),
+                    error,
+                    path: None,
+                })
+            }
+        };
         Ok(eval_stmt(&stmt, self.scope.clone(), self.ctx.clone()).map_err(Error::RuntimeError)?)
     }
 }
@@ -152,16 +247,23 @@ fn main() {
     });
 
     let mut session = Session::new(root.clone());
-    session.load(Path::new(&package.entry), false).unwrap();
+    if let Err(error) = session.load(Path::new(&package.entry), false) {
+        eprintln!("{}", render_error(&error));
+        return;
+    }
     for event in rx {
         match event {
-            SourceEvent::File(path) => session.load(&path, true).unwrap(),
+            SourceEvent::File(path) => {
+                if let Err(error) = session.load(&path, true) {
+                    eprintln!("{}", render_error(&error));
+                }
+            }
             SourceEvent::Repl(line) => match session.eval_stmt(&line) {
                 Ok(value) => {
                     println!("{:?}", value);
                 }
                 Err(error) => {
-                    eprintln!("{:?}", error);
+                    eprintln!("{}", render_error(&error));
                 }
             },
             SourceEvent::Exit => break,
@@ -0,0 +1,306 @@
+//! Parses a `vamp.mod` manifest: the `name`, `version`, and `dependencies`
+//! (dependency name -> path or version) of a single package. This is
+//! deliberately separate from [`ast::Dep`](vamp_syntax::ast::Dep): a
+//! module's `deps` say what it imports, while the manifest says where
+//! those imports actually live on disk, which [`module_graph`](crate::module_graph)
+//! needs in order to turn a dependency name into a file.
+//!
+//! The grammar is a simple keyed-record format:
+//!
+//! ```text
+//! name = my-package
+//! version = 0.1.0
+//!
+//! dependencies {
+//!     stdlib = ../vamp-stdlib
+//!     widgets = 2.3.1
+//! }
+//! ```
+//!
+//! Its tokenizer is its own, separate from [`vamp_syntax::lexer`], since
+//! the manifest format has nothing to do with expression syntax; each
+//! token it produces carries the line it started on, so a malformed
+//! manifest can be reported against the offending line.
+
+use vamp_sym::{Interner, Sym};
+
+/// A parsed `vamp.mod`. Dependency names are interned to [`Sym`] so they
+/// compare directly against a [`ModPath`](vamp_syntax::ast::ModPath)
+/// segment instead of going through a string lookup on every resolution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Manifest {
+    pub name: String,
+    pub version: String,
+    pub dependencies: Vec<(Sym, String)>,
+}
+
+/// An error parsing a manifest, with the 1-based line it occurred on.
+#[derive(Debug, PartialEq)]
+pub struct Error {
+    pub line: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.line, self.message)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Ident(String),
+    Equals,
+    LBrace,
+    RBrace,
+    Eof,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    line: usize,
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '/')
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\n' => {
+                line += 1;
+                i += 1;
+            }
+            c if c.is_whitespace() => i += 1,
+            '#' => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '=' => {
+                tokens.push(Token { kind: TokenKind::Equals, line });
+                i += 1;
+            }
+            '{' => {
+                tokens.push(Token { kind: TokenKind::LBrace, line });
+                i += 1;
+            }
+            '}' => {
+                tokens.push(Token { kind: TokenKind::RBrace, line });
+                i += 1;
+            }
+            c if is_ident_char(c) => {
+                let start = i;
+                while i < chars.len() && is_ident_char(chars[i]) {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                tokens.push(Token { kind: TokenKind::Ident(text), line });
+            }
+            c => {
+                return Err(Error {
+                    line,
+                    message: format!("unexpected character `{c}`"),
+                })
+            }
+        }
+    }
+    tokens.push(Token { kind: TokenKind::Eof, line });
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.index]
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.index].clone();
+        if self.index + 1 < self.tokens.len() {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn expect_ident(&mut self) -> Result<String> {
+        let line = self.peek().line;
+        match self.advance().kind {
+            TokenKind::Ident(name) => Ok(name),
+            other => Err(unexpected(line, "a name", &other)),
+        }
+    }
+
+    fn expect_equals(&mut self) -> Result<()> {
+        let line = self.peek().line;
+        match self.advance().kind {
+            TokenKind::Equals => Ok(()),
+            other => Err(unexpected(line, "`=`", &other)),
+        }
+    }
+
+    fn expect_lbrace(&mut self) -> Result<()> {
+        let line = self.peek().line;
+        match self.advance().kind {
+            TokenKind::LBrace => Ok(()),
+            other => Err(unexpected(line, "`{`", &other)),
+        }
+    }
+
+    fn expect_rbrace(&mut self) -> Result<()> {
+        let line = self.peek().line;
+        match self.advance().kind {
+            TokenKind::RBrace => Ok(()),
+            other => Err(unexpected(line, "`}`", &other)),
+        }
+    }
+
+    fn at_eof(&self) -> bool {
+        matches!(self.peek().kind, TokenKind::Eof)
+    }
+
+    fn at_rbrace(&self) -> bool {
+        matches!(self.peek().kind, TokenKind::RBrace)
+    }
+}
+
+fn describe(kind: &TokenKind) -> String {
+    match kind {
+        TokenKind::Ident(name) => format!("`{name}`"),
+        TokenKind::Equals => "`=`".to_string(),
+        TokenKind::LBrace => "`{`".to_string(),
+        TokenKind::RBrace => "`}`".to_string(),
+        TokenKind::Eof => "end of input".to_string(),
+    }
+}
+
+fn unexpected(line: usize, expected: &str, found: &TokenKind) -> Error {
+    Error {
+        line,
+        message: format!("expected {expected}, found {}", describe(found)),
+    }
+}
+
+/// Parses a `vamp.mod` manifest. `name = ...` and `version = ...` may
+/// appear in any order, each at most once; `dependencies { ... }` is a
+/// brace-delimited block of `name = path-or-version` pairs, also optional
+/// and at most once.
+pub fn parse_manifest(source: &str, interner: &mut Interner) -> Result<Manifest> {
+    let mut parser = Parser { tokens: tokenize(source)?, index: 0 };
+    let mut name = None;
+    let mut version = None;
+    let mut dependencies = Vec::new();
+    while !parser.at_eof() {
+        let line = parser.peek().line;
+        let key = parser.expect_ident()?;
+        match key.as_str() {
+            "name" => {
+                parser.expect_equals()?;
+                name = Some(parser.expect_ident()?);
+            }
+            "version" => {
+                parser.expect_equals()?;
+                version = Some(parser.expect_ident()?);
+            }
+            "dependencies" => {
+                parser.expect_lbrace()?;
+                while !parser.at_rbrace() {
+                    let dep_name = parser.expect_ident()?;
+                    parser.expect_equals()?;
+                    let dep_target = parser.expect_ident()?;
+                    dependencies.push((interner.intern(&dep_name), dep_target));
+                }
+                parser.expect_rbrace()?;
+            }
+            other => {
+                return Err(Error {
+                    line,
+                    message: format!("unknown manifest key `{other}`"),
+                })
+            }
+        }
+    }
+    Ok(Manifest {
+        name: name.ok_or_else(|| Error {
+            line: parser.peek().line,
+            message: "manifest is missing a `name`".to_string(),
+        })?,
+        version: version.ok_or_else(|| Error {
+            line: parser.peek().line,
+            message: "manifest is missing a `version`".to_string(),
+        })?,
+        dependencies,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_name_version_and_dependencies() {
+        let mut interner = Interner::new();
+        let manifest = parse_manifest(
+            "name = my-package\nversion = 0.1.0\ndependencies {\n    stdlib = ../vamp-stdlib\n}",
+            &mut interner,
+        )
+        .unwrap();
+        assert_eq!(manifest.name, "my-package");
+        assert_eq!(manifest.version, "0.1.0");
+        assert_eq!(manifest.dependencies.len(), 1);
+        assert_eq!(interner.lookup(manifest.dependencies[0].0), "stdlib");
+        assert_eq!(manifest.dependencies[0].1, "../vamp-stdlib");
+    }
+
+    #[test]
+    fn dependencies_are_optional() {
+        let mut interner = Interner::new();
+        let manifest = parse_manifest("name = my-package\nversion = 0.1.0", &mut interner).unwrap();
+        assert_eq!(manifest.dependencies.len(), 0);
+    }
+
+    #[test]
+    fn reports_a_missing_name() {
+        let mut interner = Interner::new();
+        let error = parse_manifest("version = 0.1.0", &mut interner).unwrap_err();
+        assert!(error.message.contains("`name`"));
+    }
+
+    #[test]
+    fn reports_an_unknown_key() {
+        let mut interner = Interner::new();
+        let error = parse_manifest("name = my-package\nversion = 0.1.0\nauthor = me", &mut interner)
+            .unwrap_err();
+        assert!(error.message.contains("author"));
+    }
+
+    #[test]
+    fn reports_the_line_of_the_token_found_in_place_of_a_value() {
+        let mut interner = Interner::new();
+        let error = parse_manifest("name = my-package\nversion = \n", &mut interner).unwrap_err();
+        assert_eq!(error.line, 3);
+    }
+
+    #[test]
+    fn reports_a_dependency_missing_its_target() {
+        let mut interner = Interner::new();
+        let error = parse_manifest(
+            "name = my-package\nversion = 0.1.0\ndependencies {\n    stdlib =\n}",
+            &mut interner,
+        )
+        .unwrap_err();
+        assert_eq!(error.line, 5);
+    }
+}
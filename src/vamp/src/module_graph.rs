@@ -0,0 +1,284 @@
+//! Resolves a root module and everything it transitively depends on into a
+//! whole-program [`ModuleGraph`], by mapping each [`Dep`]'s module path to a
+//! `.vamp` source file on disk, parsing it, and recursing into its own
+//! `deps`. Where [`Session`](crate::Session) re-parses and re-evaluates
+//! incrementally as files change, this is the one-shot version: load
+//! everything once, up front.
+//!
+//! The first segment of a non-local dep is looked up in the root's
+//! `vamp.mod` manifest (see [`manifest`](crate::manifest)), if one exists,
+//! to find the directory it actually lives in; a dep with no match there
+//! falls back to resolving relative to the root, same as before manifests
+//! existed.
+
+use crate::manifest::{self, Manifest};
+use rustc_hash::FxHashMap;
+use std::{collections::HashSet, fs, io, path::Path, path::PathBuf};
+use vamp_sym::Interner;
+use vamp_syntax::ast::{Dep, Mod, ModPath};
+
+/// A module's dotted path, e.g. `"x.y.z"`, canonical in that it's derived
+/// from the module's location on disk rather than how any one importer
+/// spelled it, so a diamond dependency resolves to the same key everywhere.
+pub type ModulePath = String;
+
+/// Every module reachable from a root module, keyed by [`ModulePath`].
+#[derive(Debug, Default)]
+pub struct ModuleGraph {
+    pub modules: FxHashMap<ModulePath, Mod>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// A module's source file couldn't be read.
+    Io { path: PathBuf, error: io::Error },
+    /// A module's source file couldn't be parsed.
+    Parse {
+        path: PathBuf,
+        error: vamp_syntax::Error,
+    },
+    /// A `dep` named a module whose file doesn't exist on disk.
+    DepNotFound { path: PathBuf },
+    /// A module was reached again while it was still being resolved, i.e.
+    /// it (transitively) imports itself.
+    Cycle { module_path: ModulePath },
+    /// The root's `vamp.mod` exists but couldn't be parsed.
+    Manifest { path: PathBuf, error: manifest::Error },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Parses the module at `root` and every module it transitively depends
+/// on, assembling a [`ModuleGraph`] keyed by canonical module path.
+///
+/// A dependency already present in the graph (a diamond import) is reused
+/// rather than re-parsed. A dependency still being resolved when it's
+/// reached again (an import cycle) is reported as [`Error::Cycle`] instead
+/// of recursing forever.
+pub fn resolve_module_tree(root: &Path, interner: &mut Interner) -> Result<ModuleGraph> {
+    let root_dir = root.parent().unwrap_or_else(|| Path::new(""));
+    let manifest = load_manifest(root_dir, interner)?;
+    let mut graph = ModuleGraph::default();
+    let mut in_progress = HashSet::new();
+    resolve(root, root_dir, manifest.as_ref(), &mut graph, &mut in_progress, interner)?;
+    Ok(graph)
+}
+
+/// Loads and parses `root_dir`'s `vamp.mod`, if it has one. A project with
+/// no manifest resolves every non-local dep relative to the root, as
+/// before manifests existed.
+fn load_manifest(root_dir: &Path, interner: &mut Interner) -> Result<Option<Manifest>> {
+    let path = root_dir.join("vamp.mod");
+    if !path.exists() {
+        return Ok(None);
+    }
+    let source = fs::read_to_string(&path).map_err(|error| Error::Io { path: path.clone(), error })?;
+    let manifest = manifest::parse_manifest(&source, interner)
+        .map_err(|error| Error::Manifest { path, error })?;
+    Ok(Some(manifest))
+}
+
+/// Computes `path`'s canonical module path relative to `root_dir`, e.g.
+/// `root_dir = "/tmp/pkg"`, `path = "/tmp/pkg/sub/main.vamp"` gives
+/// `"sub.main"`. Falls back to the whole (extension-stripped) path if
+/// `path` isn't actually under `root_dir`.
+fn module_path_of(path: &Path, root_dir: &Path) -> ModulePath {
+    let relative = path.strip_prefix(root_dir).unwrap_or(path);
+    relative
+        .with_extension("")
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Where a dep's module path points on disk: relative to the importing
+/// module's own directory when the dep is local (`.sibling`); otherwise,
+/// if `manifest` maps the dep's first segment to a dependency, relative to
+/// that dependency's directory with the first segment consumed; otherwise
+/// relative to the package root, same as a project with no manifest.
+fn dep_file_path(
+    dep_path: &ModPath,
+    importer_dir: &Path,
+    root_dir: &Path,
+    manifest: Option<&Manifest>,
+    interner: &Interner,
+) -> PathBuf {
+    if dep_path.local {
+        let mut file = importer_dir.to_path_buf();
+        for segment in dep_path.segments.iter() {
+            file.push(interner.lookup(*segment));
+        }
+        file.set_extension("vamp");
+        return file;
+    }
+    let (mut file, rest) = dep_path
+        .segments
+        .split_first()
+        .and_then(|(first, rest)| {
+            let (_, target) = manifest?.dependencies.iter().find(|(name, _)| name == first)?;
+            Some((root_dir.join(target), rest))
+        })
+        .unwrap_or_else(|| (root_dir.to_path_buf(), dep_path.segments.as_ref()));
+    for segment in rest {
+        file.push(interner.lookup(*segment));
+    }
+    file.set_extension("vamp");
+    file
+}
+
+fn dep_mod_path(dep: &Dep) -> &ModPath {
+    match dep {
+        Dep::Named { path, .. } => path,
+        Dep::Glob(path) => path,
+    }
+}
+
+fn resolve(
+    path: &Path,
+    root_dir: &Path,
+    manifest: Option<&Manifest>,
+    graph: &mut ModuleGraph,
+    in_progress: &mut HashSet<ModulePath>,
+    interner: &mut Interner,
+) -> Result<()> {
+    let module_path = module_path_of(path, root_dir);
+    if graph.modules.contains_key(&module_path) {
+        return Ok(());
+    }
+    if !in_progress.insert(module_path.clone()) {
+        return Err(Error::Cycle { module_path });
+    }
+    let source = fs::read_to_string(path).map_err(|error| Error::Io {
+        path: path.to_path_buf(),
+        error,
+    })?;
+    let module = vamp_syntax::parse_module(&source, interner).map_err(|error| Error::Parse {
+        path: path.to_path_buf(),
+        error,
+    })?;
+    let importer_dir = path.parent().unwrap_or_else(|| Path::new(""));
+    for dep in module.deps.iter() {
+        let dep_path = dep_mod_path(dep);
+        let dep_file = dep_file_path(dep_path, importer_dir, root_dir, manifest, interner);
+        if !dep_file.exists() {
+            return Err(Error::DepNotFound { path: dep_file });
+        }
+        resolve(&dep_file, root_dir, manifest, graph, in_progress, interner)?;
+    }
+    in_progress.remove(&module_path);
+    graph.modules.insert(module_path, module);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write(dir: &Path, relative: &str, source: &str) {
+        let path = dir.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(path, source).unwrap();
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("vamp-module-graph-test-{name}"));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn resolves_a_single_module_with_no_deps() {
+        let dir = temp_dir("no-deps");
+        write(&dir, "main.vamp", "let x = 1");
+        let mut interner = Interner::new();
+        let graph = resolve_module_tree(&dir.join("main.vamp"), &mut interner).unwrap();
+        assert_eq!(graph.modules.len(), 1);
+        assert!(graph.modules.contains_key("main"));
+    }
+
+    #[test]
+    fn resolves_a_transitive_dependency() {
+        let dir = temp_dir("transitive");
+        write(&dir, "main.vamp", "use { a (x) }\nlet y = x");
+        write(&dir, "a.vamp", "let x = 1");
+        let mut interner = Interner::new();
+        let graph = resolve_module_tree(&dir.join("main.vamp"), &mut interner).unwrap();
+        assert_eq!(graph.modules.len(), 2);
+        assert!(graph.modules.contains_key("main"));
+        assert!(graph.modules.contains_key("a"));
+    }
+
+    #[test]
+    fn resolves_a_diamond_dependency_once() {
+        let dir = temp_dir("diamond");
+        write(&dir, "main.vamp", "use { a (x), b (y) }\nlet z = x");
+        write(&dir, "a.vamp", "use { c (x) }\nlet x = x");
+        write(&dir, "b.vamp", "use { c (x as y) }");
+        write(&dir, "c.vamp", "let x = 1");
+        let mut interner = Interner::new();
+        let graph = resolve_module_tree(&dir.join("main.vamp"), &mut interner).unwrap();
+        assert_eq!(graph.modules.len(), 4);
+    }
+
+    #[test]
+    fn reports_a_cyclic_import() {
+        let dir = temp_dir("cycle");
+        write(&dir, "main.vamp", "use { a (x) }\nlet y = x");
+        write(&dir, "a.vamp", "use { main (y) }\nlet x = y");
+        let mut interner = Interner::new();
+        let error = resolve_module_tree(&dir.join("main.vamp"), &mut interner).unwrap_err();
+        assert!(matches!(error, Error::Cycle { .. }));
+    }
+
+    #[test]
+    fn reports_a_missing_dependency_file() {
+        let dir = temp_dir("missing");
+        write(&dir, "main.vamp", "use { nope (x) }\nlet y = x");
+        let mut interner = Interner::new();
+        let error = resolve_module_tree(&dir.join("main.vamp"), &mut interner).unwrap_err();
+        assert!(matches!(error, Error::DepNotFound { .. }));
+    }
+
+    #[test]
+    fn resolves_a_local_sibling_import_relative_to_the_importer() {
+        let dir = temp_dir("local");
+        write(&dir, "main.vamp", "use { sub.main (x) }\nlet y = x");
+        write(&dir, "sub/main.vamp", "use { .helper (x) }\nlet y = x");
+        write(&dir, "sub/helper.vamp", "let x = 1");
+        let mut interner = Interner::new();
+        let graph = resolve_module_tree(&dir.join("main.vamp"), &mut interner).unwrap();
+        assert_eq!(graph.modules.len(), 3);
+        assert!(graph.modules.contains_key("sub.helper"));
+    }
+
+    #[test]
+    fn resolves_a_non_local_dep_through_the_manifest() {
+        let dir = temp_dir("manifest");
+        write(
+            &dir,
+            "vamp.mod",
+            "name = example\nversion = 0.1.0\ndependencies {\n    stdlib = vendor/vamp-stdlib\n}",
+        );
+        write(&dir, "main.vamp", "use { stdlib.io (read) }\nlet x = read");
+        write(&dir, "vendor/vamp-stdlib/io.vamp", "let read = 1");
+        let mut interner = Interner::new();
+        let graph = resolve_module_tree(&dir.join("main.vamp"), &mut interner).unwrap();
+        assert_eq!(graph.modules.len(), 2);
+        assert!(graph.modules.contains_key("vendor.vamp-stdlib.io"));
+    }
+
+    #[test]
+    fn reports_an_unparseable_manifest() {
+        let dir = temp_dir("bad-manifest");
+        write(&dir, "vamp.mod", "name = example\n");
+        write(&dir, "main.vamp", "let x = 1");
+        let mut interner = Interner::new();
+        let error = resolve_module_tree(&dir.join("main.vamp"), &mut interner).unwrap_err();
+        assert!(matches!(error, Error::Manifest { .. }));
+    }
+}
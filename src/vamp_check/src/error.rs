@@ -1,8 +1,16 @@
+use vamp_syntax::Span;
 use vamp_ty::Ty;
 
 #[derive(Debug, PartialEq)]
 pub enum Error {
     TypeError { expected: Ty, found: Ty },
+    /// Two types that were required to be equal didn't unify.
+    Mismatch { expected: Ty, found: Ty, span: Span },
+    /// A type variable unified with a type that contains itself, e.g. from
+    /// `|x| x(x)`.
+    InfiniteType { span: Span },
+    /// An identifier with no binding in scope.
+    Unbound { span: Span },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
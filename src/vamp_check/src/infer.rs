@@ -0,0 +1,626 @@
+//! Hindley-Milner type inference over the parsed `ast::Expr` tree, filling
+//! in the `Ty::Unknown` left by the parser with concrete types.
+//!
+//! This is Algorithm W: constraints are generated bottom-up while walking
+//! the tree, unknowns are represented as fresh `Ty::Var` variables, and
+//! `unify` resolves two types against a substitution built up as we go
+//! (binding a variable to whichever concrete type it's unified with, with
+//! an occurs-check to reject infinite types). `infer_expr` itself never
+//! writes into `expr.ty` — it just returns the inferred type — since a
+//! type found early in the walk may still contain unresolved variables
+//! that only get pinned down by a later constraint. Once every definition
+//! in a module has been walked, [`infer_module`] makes a second pass with
+//! [`Infer::resolve_expr`] that writes the final, fully-resolved type into
+//! every expression in the tree.
+//!
+//! A few expression kinds (`List`, `For`, `Index`, `Bytes`) don't have a
+//! `Ty` to infer to, since `vamp_ty::Ty` doesn't yet model a list,
+//! iterable, or byte-string type; those are still walked for their side
+//! effects (so nested expressions get real types), but their own type is
+//! left as `Ty::Unknown`.
+
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+use vamp_sym::Sym;
+use vamp_syntax::ast::{BinOp, Expr, ExprKind, Mod, Pat, Stmt, UnOp};
+use vamp_syntax::Span;
+use vamp_tuple::{Tuple, TupleEntry};
+use vamp_ty::Ty;
+
+/// A lexical environment mapping bound names to their (possibly still
+/// unresolved) type. Context identifiers (`@foo`) share the same table as
+/// ordinary identifiers; unlike `vamp_eval::Scope`, inference has no need
+/// to tell the two apart at lookup time.
+struct Env<'a> {
+    parent: Option<&'a Env<'a>>,
+    bindings: Vec<(Sym, Ty)>,
+}
+
+impl<'a> Env<'a> {
+    fn root() -> Self {
+        Env {
+            parent: None,
+            bindings: Vec::new(),
+        }
+    }
+
+    fn child(&'a self) -> Env<'a> {
+        Env {
+            parent: Some(self),
+            bindings: Vec::new(),
+        }
+    }
+
+    fn bind(&mut self, name: Sym, ty: Ty) {
+        self.bindings.push((name, ty));
+    }
+
+    fn lookup(&self, name: Sym) -> Option<Ty> {
+        self.bindings
+            .iter()
+            .rev()
+            .find(|(sym, _)| *sym == name)
+            .map(|(_, ty)| ty.clone())
+            .or_else(|| self.parent.and_then(|parent| parent.lookup(name)))
+    }
+}
+
+/// Unwraps a tuple entry to its value, discarding the key if it was named.
+fn entry_value<T>(entry: TupleEntry<T>) -> T {
+    match entry {
+        TupleEntry::Pos(value) => value,
+        TupleEntry::Named(_, value) => value,
+    }
+}
+
+/// Inference state: the substitution built up by `unify`, plus a counter
+/// for minting fresh type variables.
+pub struct Infer {
+    subst: HashMap<u32, Ty>,
+    next_var: u32,
+}
+
+impl Infer {
+    pub fn new() -> Self {
+        Infer {
+            subst: HashMap::new(),
+            next_var: 0,
+        }
+    }
+
+    fn fresh(&mut self) -> Ty {
+        let var = self.next_var;
+        self.next_var += 1;
+        Ty::Var(var)
+    }
+
+    /// Follows `ty` through the substitution to the representative type of
+    /// its equivalence class: a concrete type, or an unbound variable.
+    fn prune(&self, ty: &Ty) -> Ty {
+        match ty {
+            Ty::Var(var) => match self.subst.get(var) {
+                Some(bound) => self.prune(bound),
+                None => ty.clone(),
+            },
+            _ => ty.clone(),
+        }
+    }
+
+    /// Whether type variable `var` occurs free in `ty`, after pruning.
+    /// Used to reject infinite types like `Var(0) = Tuple(Var(0), ...)`.
+    fn occurs(&self, var: u32, ty: &Ty) -> bool {
+        match self.prune(ty) {
+            Ty::Var(other) => other == var,
+            Ty::Tuple(tuple) => tuple.iter().any(|entry| match entry {
+                TupleEntry::Pos(ty) => self.occurs(var, ty),
+                TupleEntry::Named(_, ty) => self.occurs(var, ty),
+            }),
+            Ty::Any(tys) => tys.iter().any(|ty| self.occurs(var, ty)),
+            Ty::Fn(params, ret) => {
+                params.iter().any(|entry| match entry {
+                    TupleEntry::Pos(ty) => self.occurs(var, ty),
+                    TupleEntry::Named(_, ty) => self.occurs(var, ty),
+                }) || self.occurs(var, &ret)
+            }
+            _ => false,
+        }
+    }
+
+    /// Unifies `a` and `b`, extending the substitution if one (or both) is
+    /// an unbound variable, recursing structurally into tuples and
+    /// function types, and erroring on mismatched constructors.
+    pub fn unify(&mut self, a: &Ty, b: &Ty, span: Span) -> Result<Ty> {
+        let a = self.prune(a);
+        let b = self.prune(b);
+        match (&a, &b) {
+            (Ty::Var(x), Ty::Var(y)) if x == y => Ok(a),
+            (Ty::Var(x), _) => {
+                if self.occurs(*x, &b) {
+                    return Err(Error::InfiniteType { span });
+                }
+                self.subst.insert(*x, b.clone());
+                Ok(b)
+            }
+            (_, Ty::Var(y)) => {
+                if self.occurs(*y, &a) {
+                    return Err(Error::InfiniteType { span });
+                }
+                self.subst.insert(*y, a.clone());
+                Ok(a)
+            }
+            (Ty::Tuple(ta), Ty::Tuple(tb)) => {
+                if ta.len() != tb.len() {
+                    return Err(Error::Mismatch {
+                        expected: a.clone(),
+                        found: b.clone(),
+                        span,
+                    });
+                }
+                let mut entries = vec![];
+                for (ea, eb) in ta.iter().zip(tb.iter()) {
+                    entries.push(self.unify_entry(ea, eb, span)?);
+                }
+                Ok(Ty::Tuple(Tuple::from_iter(entries)))
+            }
+            (Ty::Fn(pa, ra), Ty::Fn(pb, rb)) => {
+                let params = self.unify(
+                    &Ty::Tuple((**pa).clone()),
+                    &Ty::Tuple((**pb).clone()),
+                    span,
+                )?;
+                let Ty::Tuple(params) = params else {
+                    unreachable!("unifying two Ty::Tuple always yields a Ty::Tuple");
+                };
+                let ret = self.unify(ra, rb, span)?;
+                Ok(Ty::Fn(Box::new(params), Box::new(ret)))
+            }
+            _ if a == b => Ok(a),
+            _ => Err(Error::Mismatch {
+                expected: a,
+                found: b,
+                span,
+            }),
+        }
+    }
+
+    fn unify_entry(
+        &mut self,
+        a: TupleEntry<&Ty>,
+        b: TupleEntry<&Ty>,
+        span: Span,
+    ) -> Result<TupleEntry<Ty>> {
+        match (a, b) {
+            (TupleEntry::Pos(a), TupleEntry::Pos(b)) => Ok(TupleEntry::Pos(self.unify(a, b, span)?)),
+            (TupleEntry::Named(ka, a), TupleEntry::Named(kb, b)) if ka == kb => {
+                Ok(TupleEntry::Named(ka, self.unify(a, b, span)?))
+            }
+            (a, b) => Err(Error::Mismatch {
+                expected: entry_value(a).clone(),
+                found: entry_value(b).clone(),
+                span,
+            }),
+        }
+    }
+
+    /// Unifies `ty` with whichever of `Int`/`Float` it's already concrete
+    /// as, or defaults it to `Int` if it's still an unbound variable.
+    /// Vamp has no numeric type class to express "any number", so an
+    /// ambiguous numeric operand defaults the same way an unsuffixed
+    /// integer literal does.
+    fn unify_numeric(&mut self, ty: &Ty, span: Span) -> Result<Ty> {
+        match self.prune(ty) {
+            Ty::Int => Ok(Ty::Int),
+            Ty::Float => Ok(Ty::Float),
+            Ty::Var(_) => self.unify(ty, &Ty::Int, span),
+            other => Err(Error::Mismatch {
+                expected: Ty::Int,
+                found: other,
+                span,
+            }),
+        }
+    }
+
+    /// Binds the names in `pat` to fresh type variables in `env` and
+    /// returns the type the pattern as a whole requires. Literal patterns
+    /// (`Pat::Int`, ...) require the matched value's type without binding
+    /// anything; `Pat::List` can't be typed since `Ty` has no list
+    /// constructor, so its elements are bound as fresh variables without a
+    /// constraint tying them to one another.
+    fn pat_ty(&mut self, pat: &Pat, env: &mut Env) -> Ty {
+        match pat {
+            Pat::Ident(sym) | Pat::CtxIdent(sym) => {
+                let ty = self.fresh();
+                env.bind(*sym, ty.clone());
+                ty
+            }
+            Pat::Wild => self.fresh(),
+            Pat::Sym(_) => Ty::Sym,
+            Pat::Str(_) => Ty::Str,
+            Pat::Int(_) => Ty::Int,
+            Pat::Float(_) => Ty::Float,
+            Pat::Bool(_) => Ty::Bool,
+            Pat::Tuple(tuple) => Ty::Tuple(Tuple::from_iter(tuple.iter().map(|entry| match entry {
+                TupleEntry::Pos(pat) => TupleEntry::Pos(self.pat_ty(pat, env)),
+                TupleEntry::Named(key, pat) => TupleEntry::Named(*key, self.pat_ty(pat, env)),
+            }))),
+            Pat::List(items) => {
+                for item in items.iter() {
+                    self.pat_ty(item, env);
+                }
+                self.fresh()
+            }
+        }
+    }
+
+    fn infer_tuple(&mut self, tuple: &Tuple<Expr>, env: &Env) -> Result<Tuple<Ty>> {
+        let mut entries = vec![];
+        for entry in tuple.iter() {
+            entries.push(match entry {
+                TupleEntry::Pos(expr) => TupleEntry::Pos(self.infer_expr(expr, env)?),
+                TupleEntry::Named(key, expr) => TupleEntry::Named(key, self.infer_expr(expr, env)?),
+            });
+        }
+        Ok(Tuple::from_iter(entries))
+    }
+
+    /// Infers the type of `expr` without writing it back; see the module
+    /// doc comment for why the write-back is a separate pass.
+    fn infer_expr(&mut self, expr: &Expr, env: &Env) -> Result<Ty> {
+        match &expr.kind {
+            ExprKind::Void => Ok(Ty::Void),
+            ExprKind::Bool(_) => Ok(Ty::Bool),
+            ExprKind::Sym(_) => Ok(Ty::Sym),
+            ExprKind::Str(_) => Ok(Ty::Str),
+            ExprKind::Int(_) => Ok(Ty::Int),
+            ExprKind::Float(_) => Ok(Ty::Float),
+            ExprKind::Bytes(_) => Ok(Ty::Unknown),
+            ExprKind::Ident(sym) | ExprKind::CtxIdent(sym) => {
+                env.lookup(*sym).ok_or(Error::Unbound { span: expr.span })
+            }
+            ExprKind::Block(stmts) => {
+                let mut block_env = env.child();
+                let mut ty = Ty::Void;
+                for stmt in stmts.iter() {
+                    ty = self.infer_stmt(stmt, &mut block_env)?;
+                }
+                Ok(ty)
+            }
+            ExprKind::Tuple(tuple) => Ok(Ty::Tuple(self.infer_tuple(tuple, env)?)),
+            ExprKind::List(items) => {
+                let mut element_ty: Option<Ty> = None;
+                for item in items.iter() {
+                    let item_ty = self.infer_expr(item, env)?;
+                    element_ty = Some(match element_ty {
+                        Some(previous) => self.unify(&previous, &item_ty, item.span)?,
+                        None => item_ty,
+                    });
+                }
+                Ok(Ty::Unknown)
+            }
+            ExprKind::Call(callee, args) => {
+                let callee_ty = self.infer_expr(callee, env)?;
+                let args_ty = self.infer_tuple(args, env)?;
+                let ret = self.fresh();
+                self.unify(
+                    &callee_ty,
+                    &Ty::Fn(Box::new(args_ty), Box::new(ret.clone())),
+                    expr.span,
+                )?;
+                Ok(self.prune(&ret))
+            }
+            ExprKind::Field(target, name) => {
+                let target_ty = self.infer_expr(target, env)?;
+                match self.prune(&target_ty) {
+                    Ty::Tuple(tuple) => Ok(tuple.get(*name).cloned().unwrap_or(Ty::Unknown)),
+                    _ => Ok(Ty::Unknown),
+                }
+            }
+            ExprKind::Index(target, index) => {
+                self.infer_expr(target, env)?;
+                self.infer_expr(index, env)?;
+                Ok(Ty::Unknown)
+            }
+            ExprKind::Fn(params, body) => {
+                let mut fn_env = env.child();
+                let mut param_tys = vec![];
+                for entry in params.iter() {
+                    param_tys.push(match entry {
+                        TupleEntry::Pos(pat) => TupleEntry::Pos(self.pat_ty(pat, &mut fn_env)),
+                        TupleEntry::Named(key, pat) => {
+                            TupleEntry::Named(key, self.pat_ty(pat, &mut fn_env))
+                        }
+                    });
+                }
+                let body_ty = self.infer_expr(body, &fn_env)?;
+                Ok(Ty::Fn(Box::new(Tuple::from_iter(param_tys)), Box::new(body_ty)))
+            }
+            ExprKind::UnOp(op, operand) => {
+                let operand_ty = self.infer_expr(operand, env)?;
+                match op {
+                    UnOp::Neg => self.unify_numeric(&operand_ty, expr.span),
+                    UnOp::Not => self.unify(&operand_ty, &Ty::Bool, expr.span),
+                    UnOp::BitNot => self.unify(&operand_ty, &Ty::Int, expr.span),
+                }
+            }
+            ExprKind::BinOp(op, left, right) => {
+                let left_ty = self.infer_expr(left, env)?;
+                let right_ty = self.infer_expr(right, env)?;
+                match op {
+                    BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div | BinOp::Mod | BinOp::Exp => {
+                        let left_ty = self.unify_numeric(&left_ty, left.span)?;
+                        let right_ty = self.unify_numeric(&right_ty, right.span)?;
+                        self.unify(&left_ty, &right_ty, expr.span)
+                    }
+                    BinOp::Eq | BinOp::NotEq | BinOp::Lt | BinOp::LtEq | BinOp::Gt | BinOp::GtEq => {
+                        self.unify(&left_ty, &right_ty, expr.span)?;
+                        Ok(Ty::Bool)
+                    }
+                    BinOp::And | BinOp::Or => {
+                        self.unify(&left_ty, &Ty::Bool, left.span)?;
+                        self.unify(&right_ty, &Ty::Bool, right.span)?;
+                        Ok(Ty::Bool)
+                    }
+                    BinOp::BitAnd | BinOp::BitOr | BinOp::Xor | BinOp::ShiftL | BinOp::ShiftR => {
+                        self.unify(&left_ty, &Ty::Int, left.span)?;
+                        self.unify(&right_ty, &Ty::Int, right.span)?;
+                        Ok(Ty::Int)
+                    }
+                }
+            }
+            ExprKind::IfElse(cond, then, or_else) => {
+                let cond_ty = self.infer_expr(cond, env)?;
+                self.unify(&cond_ty, &Ty::Bool, cond.span)?;
+                let then_ty = self.infer_expr(then, env)?;
+                let or_else_ty = self.infer_expr(or_else, env)?;
+                self.unify(&then_ty, &or_else_ty, expr.span)
+            }
+            ExprKind::For {
+                pat,
+                iter,
+                guard,
+                body,
+                else_body,
+            } => {
+                self.infer_expr(iter, env)?;
+                let mut loop_env = env.child();
+                self.pat_ty(pat, &mut loop_env);
+                if let Some(guard) = guard {
+                    let guard_ty = self.infer_expr(guard, &loop_env)?;
+                    self.unify(&guard_ty, &Ty::Bool, guard.span)?;
+                }
+                self.infer_expr(body, &loop_env)?;
+                if let Some(else_body) = else_body {
+                    self.infer_expr(else_body, env)?;
+                }
+                Ok(Ty::Unknown)
+            }
+            ExprKind::Match(scrutinee, arms) => {
+                let scrutinee_ty = self.infer_expr(scrutinee, env)?;
+                let mut result_ty = None;
+                for (pat, guard, body) in arms.iter() {
+                    let mut arm_env = env.child();
+                    let pat_ty = self.pat_ty(pat, &mut arm_env);
+                    self.unify(&scrutinee_ty, &pat_ty, body.span)?;
+                    if let Some(guard) = guard {
+                        let guard_ty = self.infer_expr(guard, &arm_env)?;
+                        self.unify(&guard_ty, &Ty::Bool, guard.span)?;
+                    }
+                    let body_ty = self.infer_expr(body, &arm_env)?;
+                    result_ty = Some(match result_ty {
+                        Some(previous) => self.unify(&previous, &body_ty, body.span)?,
+                        None => body_ty,
+                    });
+                }
+                Ok(result_ty.unwrap_or(Ty::Unknown))
+            }
+        }
+    }
+
+    fn infer_stmt(&mut self, stmt: &Stmt, env: &mut Env) -> Result<Ty> {
+        match stmt {
+            Stmt::Let(pat, expr) => {
+                let expr_ty = self.infer_expr(expr, env)?;
+                let pat_ty = self.pat_ty(pat, env);
+                self.unify(&expr_ty, &pat_ty, expr.span)?;
+                Ok(Ty::Void)
+            }
+            Stmt::Expr(expr) => self.infer_expr(expr, env),
+        }
+    }
+
+    /// Replaces every `Ty::Var` in `ty` with its final resolution.
+    fn resolve(&self, ty: &Ty) -> Ty {
+        match self.prune(ty) {
+            Ty::Tuple(tuple) => Ty::Tuple(Tuple::from_iter(tuple.iter().map(|entry| match entry {
+                TupleEntry::Pos(ty) => TupleEntry::Pos(self.resolve(ty)),
+                TupleEntry::Named(key, ty) => TupleEntry::Named(key, self.resolve(ty)),
+            }))),
+            Ty::Any(tys) => Ty::Any(tys.iter().map(|ty| self.resolve(ty)).collect()),
+            Ty::Fn(params, ret) => Ty::Fn(
+                Box::new(Tuple::from_iter(params.iter().map(|entry| match entry {
+                    TupleEntry::Pos(ty) => TupleEntry::Pos(self.resolve(ty)),
+                    TupleEntry::Named(key, ty) => TupleEntry::Named(key, self.resolve(ty)),
+                }))),
+                Box::new(self.resolve(&ret)),
+            ),
+            resolved => resolved,
+        }
+    }
+
+    /// Writes `self.resolve(&expr.ty)` back into `expr` and every nested
+    /// expression, recursing through statements and match/for/if bodies.
+    fn resolve_expr(&self, expr: &mut Expr) {
+        expr.ty = self.resolve(&expr.ty);
+        match &mut expr.kind {
+            ExprKind::Void
+            | ExprKind::Ident(_)
+            | ExprKind::CtxIdent(_)
+            | ExprKind::Sym(_)
+            | ExprKind::Str(_)
+            | ExprKind::Bytes(_)
+            | ExprKind::Int(_)
+            | ExprKind::Float(_)
+            | ExprKind::Bool(_) => {}
+            ExprKind::Block(stmts) => stmts.iter_mut().for_each(|stmt| self.resolve_stmt(stmt)),
+            ExprKind::Tuple(tuple) => self.resolve_tuple(tuple),
+            ExprKind::List(items) => items.iter_mut().for_each(|item| self.resolve_expr(item)),
+            ExprKind::Call(callee, args) => {
+                self.resolve_expr(callee);
+                self.resolve_tuple(args);
+            }
+            ExprKind::Field(target, _) => self.resolve_expr(target),
+            ExprKind::Index(target, index) => {
+                self.resolve_expr(target);
+                self.resolve_expr(index);
+            }
+            ExprKind::Fn(_, body) => self.resolve_expr(body),
+            ExprKind::UnOp(_, operand) => self.resolve_expr(operand),
+            ExprKind::BinOp(_, left, right) => {
+                self.resolve_expr(left);
+                self.resolve_expr(right);
+            }
+            ExprKind::IfElse(cond, then, or_else) => {
+                self.resolve_expr(cond);
+                self.resolve_expr(then);
+                self.resolve_expr(or_else);
+            }
+            ExprKind::For {
+                iter,
+                guard,
+                body,
+                else_body,
+                ..
+            } => {
+                self.resolve_expr(iter);
+                if let Some(guard) = guard {
+                    self.resolve_expr(guard);
+                }
+                self.resolve_expr(body);
+                if let Some(else_body) = else_body {
+                    self.resolve_expr(else_body);
+                }
+            }
+            ExprKind::Match(scrutinee, arms) => {
+                self.resolve_expr(scrutinee);
+                for (_, guard, body) in arms.iter_mut() {
+                    if let Some(guard) = guard {
+                        self.resolve_expr(guard);
+                    }
+                    self.resolve_expr(body);
+                }
+            }
+        }
+    }
+
+    fn resolve_tuple(&self, tuple: &mut Tuple<Expr>) {
+        for i in 0..tuple.len() {
+            self.resolve_expr(&mut tuple[i]);
+        }
+    }
+
+    fn resolve_stmt(&self, stmt: &mut Stmt) {
+        match stmt {
+            Stmt::Let(_, expr) | Stmt::Expr(expr) => self.resolve_expr(expr),
+        }
+    }
+}
+
+impl Default for Infer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Runs Algorithm W over every definition in `module`, writing the
+/// resolved `Ty` back into each `Expr`. Definitions are inferred in order
+/// so that later ones see earlier bindings; a definition that fails to
+/// type-check still leaves its own (and later definitions') types
+/// resolved as best as possible, and its error joins the returned list.
+pub fn infer_module(module: &mut Mod) -> std::result::Result<(), Vec<Error>> {
+    let mut infer = Infer::new();
+    let mut env = Env::root();
+    let mut errors = vec![];
+    for stmt in module.defs.iter() {
+        if let Err(error) = infer.infer_stmt(stmt, &mut env) {
+            errors.push(error);
+        }
+    }
+    for stmt in module.defs.iter_mut() {
+        infer.resolve_stmt(stmt);
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vamp_sym::Interner;
+    use vamp_syntax::parser::parse_expr;
+
+    fn infer(source: &str) -> Result<Ty> {
+        let mut interner = Interner::new();
+        let expr = parse_expr(source, &mut interner).unwrap();
+        let mut infer = Infer::new();
+        let env = Env::root();
+        let ty = infer.infer_expr(&expr, &env)?;
+        Ok(infer.resolve(&ty))
+    }
+
+    #[test]
+    fn infers_literals() {
+        assert_eq!(infer("1"), Ok(Ty::Int));
+        assert_eq!(infer("1.5"), Ok(Ty::Float));
+        assert_eq!(infer("true"), Ok(Ty::Bool));
+        assert_eq!(infer("\"s\""), Ok(Ty::Str));
+    }
+
+    #[test]
+    fn infers_arithmetic() {
+        assert_eq!(infer("1 + 2"), Ok(Ty::Int));
+        assert_eq!(infer("1.0 + 2.0"), Ok(Ty::Float));
+    }
+
+    #[test]
+    fn rejects_mismatched_arithmetic_operands() {
+        assert!(matches!(infer("1 + 1.0"), Err(Error::Mismatch { .. })));
+    }
+
+    #[test]
+    fn infers_comparisons_as_bool() {
+        assert_eq!(infer("1 < 2"), Ok(Ty::Bool));
+    }
+
+    #[test]
+    fn infers_if_else_by_unifying_branches() {
+        assert_eq!(infer("if true { 1 } else { 2 }"), Ok(Ty::Int));
+    }
+
+    #[test]
+    fn rejects_if_else_with_a_non_bool_condition() {
+        assert!(matches!(
+            infer("if 1 { 1 } else { 2 }"),
+            Err(Error::Mismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn infers_identity_function_application_through_a_type_variable() {
+        assert_eq!(infer("(|x| x)(1)"), Ok(Ty::Int));
+    }
+
+    #[test]
+    fn rejects_an_infinite_type() {
+        let mut interner = Interner::new();
+        let expr = parse_expr("|x| x(x)", &mut interner).unwrap();
+        let mut infer_pass = Infer::new();
+        let env = Env::root();
+        assert!(matches!(
+            infer_pass.infer_expr(&expr, &env),
+            Err(Error::InfiniteType { .. })
+        ));
+    }
+}
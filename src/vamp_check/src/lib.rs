@@ -1,4 +1,6 @@
 pub mod error;
+pub mod infer;
+pub use infer::{infer_module, Infer};
 
 use error::{Error, Result};
 use vamp_syntax::ast::{BinOp, Expr, ExprKind, Mod, Stmt};
@@ -13,6 +13,7 @@ pub fn eval_expr(expr: &Expr, scope: Rc<RefCell<Scope>>, ctx: Rc<RefCell<Scope>>
         ExprKind::CtxIdent(sym) => ctx.borrow().lookup(*sym).map(|value| value.clone()),
         ExprKind::Sym(sym) => Ok(Value::Sym(*sym)),
         ExprKind::Str(str) => Ok(Value::Str(str.clone())),
+        ExprKind::Bytes(bytes) => Ok(Value::Bytes(bytes.clone())),
         ExprKind::Int(value) => Ok(Value::Int(*value)),
         ExprKind::Float(value) => Ok(Value::Float(*value)),
         ExprKind::Bool(value) => Ok(Value::Bool(*value)),
@@ -51,21 +52,28 @@ pub fn eval_expr(expr: &Expr, scope: Rc<RefCell<Scope>>, ctx: Rc<RefCell<Scope>>
             },
             _ => todo!(),
         },
+        ExprKind::Field(target, name) => match eval_expr(target, scope, ctx)? {
+            Value::Tuple(tuple) => tuple
+                .get(*name)
+                .map(|value| value.clone())
+                .ok_or(Error::KeyNotFound),
+            _ => Err(Error::Types),
+        },
+        ExprKind::Index(target, index) => match (
+            eval_expr(target, scope.clone(), ctx.clone())?,
+            eval_expr(index, scope, ctx)?,
+        ) {
+            (Value::Tuple(tuple), Value::Int(i)) => {
+                let i: usize = i.try_into().map_err(|_| Error::KeyNotFound)?;
+                tuple.get(i).map(|value| value.clone()).ok_or(Error::KeyNotFound)
+            }
+            (Value::List(list), Value::Int(i)) => {
+                let i: usize = i.try_into().map_err(|_| Error::KeyNotFound)?;
+                list.get(i).map(|value| value.clone()).ok_or(Error::KeyNotFound)
+            }
+            _ => Err(Error::Types),
+        },
         ExprKind::BinOp(binary_op, l, r) => match binary_op {
-            BinOp::Dot => match (eval_expr(l, scope, ctx)?, &r.kind) {
-                (Value::Tuple(tuple), ExprKind::Ident(key)) => tuple
-                    .get(*key)
-                    .map(|value| value.clone())
-                    .ok_or(Error::KeyNotFound),
-                (Value::Tuple(tuple), ExprKind::Int(i)) => {
-                    let i: usize = (*i).try_into().map_err(|_| Error::KeyNotFound)?;
-                    tuple
-                        .get(i)
-                        .map(|value| value.clone())
-                        .ok_or(Error::KeyNotFound)
-                }
-                _ => Err(Error::Types),
-            },
             BinOp::Add => match (
                 eval_expr(l, scope.clone(), ctx.clone())?,
                 eval_expr(r, scope, ctx)?,
@@ -120,6 +128,7 @@ pub fn eval_expr(expr: &Expr, scope: Rc<RefCell<Scope>>, ctx: Rc<RefCell<Scope>>
                 (Value::Bool(a), Value::Bool(b)) => Ok(Value::Bool(a == b)),
                 (Value::Sym(a), Value::Sym(b)) => Ok(Value::Bool(a == b)),
                 (Value::Str(a), Value::Str(b)) => Ok(Value::Bool(a == b)),
+                (Value::Bytes(a), Value::Bytes(b)) => Ok(Value::Bool(a == b)),
                 (Value::Int(a), Value::Int(b)) => Ok(Value::Bool(a == b)),
                 (Value::Float(a), Value::Float(b)) => Ok(Value::Bool(a == b)),
                 (Value::Tuple(a), Value::Tuple(b)) => Ok(Value::Bool(a == b)),
@@ -133,6 +142,7 @@ pub fn eval_expr(expr: &Expr, scope: Rc<RefCell<Scope>>, ctx: Rc<RefCell<Scope>>
                 (Value::Bool(a), Value::Bool(b)) => Ok(Value::Bool(a != b)),
                 (Value::Sym(a), Value::Sym(b)) => Ok(Value::Bool(a != b)),
                 (Value::Str(a), Value::Str(b)) => Ok(Value::Bool(a != b)),
+                (Value::Bytes(a), Value::Bytes(b)) => Ok(Value::Bool(a != b)),
                 (Value::Int(a), Value::Int(b)) => Ok(Value::Bool(a != b)),
                 (Value::Float(a), Value::Float(b)) => Ok(Value::Bool(a != b)),
                 (Value::Tuple(a), Value::Tuple(b)) => Ok(Value::Bool(a != b)),
@@ -209,6 +219,56 @@ pub fn eval_expr(expr: &Expr, scope: Rc<RefCell<Scope>>, ctx: Rc<RefCell<Scope>>
                 _ => Err(Error::Types),
             }
         }
+        ExprKind::For {
+            pat,
+            iter,
+            guard,
+            body,
+            else_body,
+        } => {
+            let items = match eval_expr(iter, scope.clone(), ctx.clone())? {
+                Value::List(items) => items,
+                _ => return Err(Error::Types),
+            };
+            let iter_was_empty = items.is_empty();
+            let mut results = Vec::new();
+            for item in items {
+                let mut loop_scope = Scope::new(Some(scope.clone()));
+                bind(&mut loop_scope, &mut ctx.borrow_mut(), pat, item)?;
+                let loop_scope = Rc::new(RefCell::new(loop_scope));
+                if let Some(guard) = guard {
+                    match eval_expr(guard, loop_scope.clone(), ctx.clone())? {
+                        Value::Bool(true) => {}
+                        Value::Bool(false) => continue,
+                        _ => return Err(Error::Types),
+                    }
+                }
+                results.push(eval_expr(body, loop_scope, ctx.clone())?);
+            }
+            match (iter_was_empty, else_body) {
+                (true, Some(else_body)) => eval_expr(else_body, scope, ctx),
+                _ => Ok(Value::List(results)),
+            }
+        }
+        ExprKind::Match(scrutinee, arms) => {
+            let value = eval_expr(scrutinee, scope.clone(), ctx.clone())?;
+            for (pat, guard, body) in arms.iter() {
+                let mut arm_scope = Scope::new(Some(scope.clone()));
+                if bind(&mut arm_scope, &mut ctx.borrow_mut(), pat, value.clone()).is_err() {
+                    continue;
+                }
+                let arm_scope = Rc::new(RefCell::new(arm_scope));
+                if let Some(guard) = guard {
+                    match eval_expr(guard, arm_scope.clone(), ctx.clone())? {
+                        Value::Bool(true) => {}
+                        Value::Bool(false) => continue,
+                        _ => return Err(Error::Types),
+                    }
+                }
+                return eval_expr(body, arm_scope, ctx);
+            }
+            Err(Error::Mismatch)
+        }
         ExprKind::Fn(params, body) => Ok(Value::Fn(Fn {
             params: params.clone(),
             body: body.clone(),
@@ -304,14 +364,16 @@ fn bind(scope: &mut Scope, ctx: &mut Scope, pat: &Pat, value: Value) -> Result<(
             Value::Tuple(value) => bind_tuple(scope, ctx, tuple, value),
             _ => Err(Error::Mismatch),
         },
-        /*
-        Pat::List(items) => {
-            for item in items.into_iter() {
-                self.bind(item, value);
+        Pat::List(items) => match value {
+            Value::List(values) if items.len() == values.len() => {
+                for (item, value) in items.iter().zip(values) {
+                    bind(scope, ctx, item, value)?;
+                }
+                Ok(())
             }
-        }*/
+            _ => Err(Error::Mismatch),
+        },
         Pat::Wild => Ok(()),
-        _ => todo!(),
     }
 }
 
@@ -374,6 +436,16 @@ mod tests {
         assert_eq!(eval_string("\"abc\""), Ok(Value::Str("abc".into())));
     }
 
+    #[test]
+    fn test_bytes() {
+        assert_eq!(eval_string(r#"b"abc""#), Ok(Value::Bytes(b"abc".to_vec())));
+        assert_eq!(
+            eval_string(r#"b64"SGVsbG8=""#),
+            Ok(Value::Bytes(b"Hello".to_vec()))
+        );
+        assert_eq!(eval_string(r#"b"abc" == b"abc""#), Ok(Value::Bool(true)));
+    }
+
     #[test]
     fn test_int() {
         assert_eq!(eval_string("123"), Ok(Value::Int(123)));
@@ -417,4 +489,64 @@ mod tests {
         assert_eq!(eval_string("2 * -1 + 10 / 2"), Ok(Value::Int(3)));
         assert_eq!(eval_string("0 * 'abc'"), Err(Error::Types));
     }
+
+    #[test]
+    fn test_field() {
+        assert_eq!(eval_string("(x: 1, y: 2).y"), Ok(Value::Int(2)));
+        assert_eq!(eval_string("(x: 1).z"), Err(Error::KeyNotFound));
+        assert_eq!(eval_string("1.x"), Err(Error::Types));
+    }
+
+    #[test]
+    fn test_index() {
+        assert_eq!(eval_string("(1, 2, 3)[1]"), Ok(Value::Int(2)));
+        assert_eq!(eval_string("[1, 2, 3][2]"), Ok(Value::Int(3)));
+        assert_eq!(eval_string("(1, 2)[5]"), Err(Error::KeyNotFound));
+        assert_eq!(eval_string("1[0]"), Err(Error::Types));
+    }
+
+    #[test]
+    fn test_for() {
+        assert_eq!(
+            eval_string("for x in [1, 2, 3] { x * 2 }"),
+            Ok(Value::List(vec![
+                Value::Int(2),
+                Value::Int(4),
+                Value::Int(6),
+            ]))
+        );
+        assert_eq!(
+            eval_string("for x in [1, 2, 3] if x > 1 { x }"),
+            Ok(Value::List(vec![Value::Int(2), Value::Int(3)]))
+        );
+        assert_eq!(
+            eval_string("for x in [] { x } else { 0 }"),
+            Ok(Value::Int(0))
+        );
+        assert_eq!(eval_string("for x in 1 { x }"), Err(Error::Types));
+    }
+
+    #[test]
+    fn test_match() {
+        assert_eq!(
+            eval_string("match (1, 2) { (a, b) if a > b => a, y => y }"),
+            Ok(Value::Tuple(Tuple::from_iter([
+                TupleEntry::Pos(Value::Int(1)),
+                TupleEntry::Pos(Value::Int(2)),
+            ])))
+        );
+        assert_eq!(
+            eval_string("match (2, 1) { (a, b) if a > b => a, y => y }"),
+            Ok(Value::Int(2))
+        );
+        assert_eq!(eval_string("match 1 { (a, b) => a }"), Err(Error::Mismatch));
+        assert_eq!(
+            eval_string("match [1, 2, 3] { [a, b] => a, [a, b, c] => c }"),
+            Ok(Value::Int(3))
+        );
+        assert_eq!(
+            eval_string("match [1, 2] { [a, b, c] => a }"),
+            Err(Error::Mismatch)
+        );
+    }
 }
@@ -9,6 +9,7 @@ pub enum Value {
     Bool(bool),
     Sym(Sym),
     Str(String),
+    Bytes(Vec<u8>),
     Int(i64),
     Float(f64),
     Tuple(Tuple<Value>),
@@ -7,11 +7,35 @@ use serde::{Deserialize, Serialize};
 pub struct Sym(pub u32);
 
 #[derive(Default)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Interner {
+    #[cfg_attr(feature = "serde", serde(skip))]
     map: FxHashMap<String, Sym>,
     vector: Vec<String>,
 }
 
+/// `map` is a skipped, derived index over `vector`, so it's left out of the
+/// serialized form and rebuilt here on load instead.
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Interner {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Data {
+            vector: Vec<String>,
+        }
+        let Data { vector } = Data::deserialize(deserializer)?;
+        let map = vector
+            .iter()
+            .enumerate()
+            .map(|(index, string)| (string.clone(), Sym(index as u32)))
+            .collect();
+        Ok(Interner { map, vector })
+    }
+}
+
 impl Interner {
     /// Constructs an empty `SymTable`.
     pub fn new() -> Self {
@@ -43,6 +67,15 @@ impl Interner {
     pub fn lookup(&self, symbol: Sym) -> &str {
         &self.vector[symbol.0 as usize]
     }
+
+    /// Iterates every interned name, skipping the `#`-prefixed ones
+    /// generated by `private()`.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.vector
+            .iter()
+            .map(String::as_str)
+            .filter(|name| !name.starts_with('#'))
+    }
 }
 
 #[cfg(test)]
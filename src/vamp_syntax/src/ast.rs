@@ -1,8 +1,13 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use vamp_sym::Sym;
 use vamp_tuple::Tuple;
 use vamp_ty::Ty;
 
+use crate::span::Span;
+
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Pat {
     Tuple(Tuple<Pat>),
     List(Box<[Pat]>),
@@ -18,6 +23,7 @@ pub enum Pat {
 
 /// A block statement.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Stmt {
     /// A let binding `let y = f(x)`.
     Let(Pat, Expr),
@@ -27,6 +33,7 @@ pub enum Stmt {
 
 // Unary operators.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum UnOp {
     /// Negation `(-)`
     Neg,
@@ -38,11 +45,8 @@ pub enum UnOp {
 
 /// Binary operators.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum BinOp {
-    // Property lookup
-    /// Dot `(.)`
-    Dot,
-
     // Mathematical
     /// Addition `(+)`
     Add,
@@ -91,6 +95,7 @@ pub enum BinOp {
 /// An expression. Except for a `Module`, which has no value, everything in Vamp
 /// builds and composes from `Expr`.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ExprKind {
     /// An empty sequence of statements `{}`.
     Void,
@@ -102,6 +107,10 @@ pub enum ExprKind {
     List(Box<[Expr]>),
     /// A function call/application.
     Call(Box<Expr>, Tuple<Expr>),
+    /// A named tuple entry looked up by field `expr.name`.
+    Field(Box<Expr>, Sym),
+    /// A list or tuple entry looked up by position `expr[idx]`.
+    Index(Box<Expr>, Box<Expr>),
     /// A function abstraction.
     Fn(Tuple<Pat>, Box<Expr>),
     /// An identifier.
@@ -112,6 +121,8 @@ pub enum ExprKind {
     Sym(Sym),
     /// A string literal `"abc"`.
     Str(String),
+    /// A byte-string (`b"abc"`) or base64 (`b64"YWJj"`) literal.
+    Bytes(Vec<u8>),
     /// An integer literal `1`.
     Int(i64),
     /// A floating point literal `1.2`.
@@ -124,25 +135,68 @@ pub enum ExprKind {
     BinOp(BinOp, Box<Expr>, Box<Expr>),
     /// An if-else expression.
     IfElse(Box<Expr>, Box<Expr>, Box<Expr>),
+    /// A for loop `for <pat> in <iter> <body>`, with an optional guard
+    /// (`for x in xs if <cond> <body>`) and an optional `else <body>` that
+    /// runs once if `iter` produces no elements.
+    For {
+        pat: Pat,
+        iter: Box<Expr>,
+        guard: Option<Box<Expr>>,
+        body: Box<Expr>,
+        else_body: Option<Box<Expr>>,
+    },
+    /// A `match` expression `match <scrutinee> { <pat> => <expr>, ... }`.
+    /// Each arm is `(pattern, optional guard, body)`; the guard comes from
+    /// an optional `if <cond>` between the pattern and `=>`.
+    Match(Box<Expr>, Box<[(Pat, Option<Expr>, Expr)]>),
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Expr {
     pub ty: Ty,
     pub kind: ExprKind,
+    /// The expression's location in the source, from its first token to
+    /// its last.
+    pub span: Span,
+}
+
+/// Ignores `span`: two expressions are equal if they have the same shape,
+/// regardless of where in the source they came from. This is what lets
+/// parser tests compare real output against `Expr::unknown`, which fills
+/// a sentinel span.
+impl PartialEq for Expr {
+    fn eq(&self, other: &Self) -> bool {
+        self.ty == other.ty && self.kind == other.kind
+    }
 }
 
 impl Expr {
+    /// Builds an expr with a real source span, covering its first token
+    /// through its last.
+    pub fn new(kind: ExprKind, span: Span) -> Self {
+        Self {
+            ty: Ty::Unknown,
+            kind,
+            span,
+        }
+    }
+
+    /// A test-only constructor that fills a sentinel span. Production
+    /// parser code should use `Expr::new` with the span of the tokens it
+    /// actually parsed.
     pub fn unknown(kind: ExprKind) -> Self {
         Self {
             ty: Ty::Unknown,
             kind,
+            span: Span::default(),
         }
     }
 }
 
 /// A module's location.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ModPath {
     /// Whether or not the module is local to the curent package.
     pub local: bool,
@@ -152,15 +206,24 @@ pub struct ModPath {
 
 /// Represents a dependency on a single module.
 #[derive(Debug, PartialEq, Clone)]
-pub struct Dep {
-    /// The location of the module being depended on.
-    pub path: ModPath,
-    /// A map of symbols to bind in the form `[(source, destination), ...]`.
-    pub bindings: Box<[(Sym, Sym)]>,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Dep {
+    /// Imports specific bindings from a module, each as `(source,
+    /// destination)`; `destination` differs from `source` when the
+    /// binding is renamed with `as`, e.g. `x.y.z (w as v)`.
+    Named {
+        /// The location of the module being depended on.
+        path: ModPath,
+        /// A map of symbols to bind in the form `[(source, destination), ...]`.
+        bindings: Box<[(Sym, Sym)]>,
+    },
+    /// Imports every binding from a module: `x.y.z (*)`.
+    Glob(ModPath),
 }
 
 /// The top-level type for Vamp files/modules.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Mod {
     /// A module's dependencies.
     pub deps: Box<[Dep]>,
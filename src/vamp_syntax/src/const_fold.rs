@@ -0,0 +1,422 @@
+//! A constant-folding pass over the parsed `ast::Expr` tree.
+//!
+//! `fold_constants` rewrites an expression bottom-up, collapsing literal
+//! arithmetic, comparison, and bitwise expressions (`1 + 2` becomes `3`,
+//! `1 < 2` becomes `true`) and a handful of algebraic identities
+//! (`x + 0`, `x * 1`, `x - x`, `x & x`, ...) that show up once inlining or
+//! macro expansion has produced redundant operands. It also collapses an
+//! `IfElse` whose condition is already a `Bool` literal down to whichever
+//! branch is taken, dropping the other one entirely. It never folds
+//! integer division/modulo by zero, integer arithmetic that would
+//! overflow, a shift by a negative or out-of-range amount, or float
+//! arithmetic that would produce a non-finite result: those nodes are left
+//! intact so the runtime preserves their usual error/IEEE 754 semantics.
+
+use crate::ast::{BinOp, Expr, ExprKind, Mod, Stmt, UnOp};
+use vamp_tuple::{Tuple, TupleEntry};
+
+/// Folds constant arithmetic and algebraic identities throughout `expr`.
+pub fn fold_constants(expr: &Expr) -> Expr {
+    let kind = match &expr.kind {
+        ExprKind::Void
+        | ExprKind::Ident(_)
+        | ExprKind::CtxIdent(_)
+        | ExprKind::Sym(_)
+        | ExprKind::Str(_)
+        | ExprKind::Bytes(_)
+        | ExprKind::Int(_)
+        | ExprKind::Float(_)
+        | ExprKind::Bool(_) => return expr.clone(),
+        ExprKind::Block(stmts) => ExprKind::Block(stmts.iter().map(fold_stmt).collect()),
+        ExprKind::Tuple(tuple) => ExprKind::Tuple(fold_tuple(tuple)),
+        ExprKind::List(items) => ExprKind::List(items.iter().map(fold_constants).collect()),
+        ExprKind::Call(callee, args) => {
+            ExprKind::Call(Box::new(fold_constants(callee)), fold_tuple(args))
+        }
+        ExprKind::Field(target, name) => {
+            ExprKind::Field(Box::new(fold_constants(target)), *name)
+        }
+        ExprKind::Index(target, index) => ExprKind::Index(
+            Box::new(fold_constants(target)),
+            Box::new(fold_constants(index)),
+        ),
+        ExprKind::Fn(params, body) => {
+            ExprKind::Fn(params.clone(), Box::new(fold_constants(body)))
+        }
+        ExprKind::UnOp(op, operand) => return fold_unop(op.clone(), fold_constants(operand)),
+        ExprKind::BinOp(op, l, r) => {
+            return fold_binop(op.clone(), fold_constants(l), fold_constants(r))
+        }
+        ExprKind::IfElse(cond, then, or_else) => {
+            let cond = fold_constants(cond);
+            let then = fold_constants(then);
+            let or_else = fold_constants(or_else);
+            return match cond.kind {
+                ExprKind::Bool(true) => then,
+                ExprKind::Bool(false) => or_else,
+                _ => Expr::unknown(ExprKind::IfElse(
+                    Box::new(cond),
+                    Box::new(then),
+                    Box::new(or_else),
+                )),
+            };
+        }
+        ExprKind::For {
+            pat,
+            iter,
+            guard,
+            body,
+            else_body,
+        } => ExprKind::For {
+            pat: pat.clone(),
+            iter: Box::new(fold_constants(iter)),
+            guard: guard.as_deref().map(fold_constants).map(Box::new),
+            body: Box::new(fold_constants(body)),
+            else_body: else_body.as_deref().map(fold_constants).map(Box::new),
+        },
+        ExprKind::Match(scrutinee, arms) => ExprKind::Match(
+            Box::new(fold_constants(scrutinee)),
+            arms.iter()
+                .map(|(pat, guard, body)| {
+                    (
+                        pat.clone(),
+                        guard.as_ref().map(fold_constants),
+                        fold_constants(body),
+                    )
+                })
+                .collect(),
+        ),
+    };
+    Expr {
+        ty: expr.ty.clone(),
+        kind,
+        span: expr.span,
+    }
+}
+
+/// Folds every definition (and dependency-free body) in a module.
+pub fn fold_mod(module: &Mod) -> Mod {
+    Mod {
+        deps: module.deps.clone(),
+        defs: module.defs.iter().map(fold_stmt).collect(),
+    }
+}
+
+fn fold_stmt(stmt: &Stmt) -> Stmt {
+    match stmt {
+        Stmt::Let(pat, expr) => Stmt::Let(pat.clone(), fold_constants(expr)),
+        Stmt::Expr(expr) => Stmt::Expr(fold_constants(expr)),
+    }
+}
+
+fn fold_tuple(tuple: &Tuple<Expr>) -> Tuple<Expr> {
+    tuple
+        .iter()
+        .map(|entry| match entry {
+            TupleEntry::Pos(expr) => TupleEntry::Pos(fold_constants(expr)),
+            TupleEntry::Named(key, expr) => TupleEntry::Named(key, fold_constants(expr)),
+        })
+        .collect()
+}
+
+fn fold_unop(op: UnOp, operand: Expr) -> Expr {
+    let folded = match (&op, &operand.kind) {
+        (UnOp::Neg, ExprKind::Int(value)) => value.checked_neg().map(ExprKind::Int),
+        (UnOp::Neg, ExprKind::Float(value)) => Some(ExprKind::Float(-value)),
+        (UnOp::Not, ExprKind::Bool(value)) => Some(ExprKind::Bool(!value)),
+        (UnOp::BitNot, ExprKind::Int(value)) => Some(ExprKind::Int(!value)),
+        _ => None,
+    };
+    match folded {
+        Some(kind) => Expr::unknown(kind),
+        None => Expr::unknown(ExprKind::UnOp(op, Box::new(operand))),
+    }
+}
+
+fn fold_binop(op: BinOp, l: Expr, r: Expr) -> Expr {
+    if let Some(folded) = fold_literal_binop(&op, &l, &r) {
+        return folded;
+    }
+    if let Some(folded) = fold_identity_binop(&op, &l, &r) {
+        return folded;
+    }
+    Expr::unknown(ExprKind::BinOp(op, Box::new(l), Box::new(r)))
+}
+
+/// Folds `op` when both sides are `Int`, both are `Float`, or both are
+/// `Bool` literals. Returns `None` (leaving the node intact) on integer
+/// overflow, integer division/modulo by zero, a shift by a negative or
+/// out-of-range amount, or a float result that isn't finite.
+fn fold_literal_binop(op: &BinOp, l: &Expr, r: &Expr) -> Option<Expr> {
+    match (&l.kind, &r.kind) {
+        (ExprKind::Int(a), ExprKind::Int(b)) => fold_int_binop(op, *a, *b),
+        (ExprKind::Float(a), ExprKind::Float(b)) => fold_float_binop(op, *a, *b),
+        (ExprKind::Bool(a), ExprKind::Bool(b)) => fold_bool_binop(op, *a, *b),
+        _ => None,
+    }
+}
+
+fn fold_int_binop(op: &BinOp, a: i64, b: i64) -> Option<Expr> {
+    let kind = match op {
+        BinOp::Add => ExprKind::Int(a.checked_add(b)?),
+        BinOp::Sub => ExprKind::Int(a.checked_sub(b)?),
+        BinOp::Mul => ExprKind::Int(a.checked_mul(b)?),
+        BinOp::Div => ExprKind::Int(a.checked_div(b)?),
+        BinOp::Mod => ExprKind::Int(a.checked_rem(b)?),
+        BinOp::Eq => ExprKind::Bool(a == b),
+        BinOp::NotEq => ExprKind::Bool(a != b),
+        BinOp::Lt => ExprKind::Bool(a < b),
+        BinOp::LtEq => ExprKind::Bool(a <= b),
+        BinOp::Gt => ExprKind::Bool(a > b),
+        BinOp::GtEq => ExprKind::Bool(a >= b),
+        BinOp::BitAnd => ExprKind::Int(a & b),
+        BinOp::BitOr => ExprKind::Int(a | b),
+        BinOp::Xor => ExprKind::Int(a ^ b),
+        BinOp::ShiftL => ExprKind::Int(a.checked_shl(b.try_into().ok()?)?),
+        BinOp::ShiftR => ExprKind::Int(a.checked_shr(b.try_into().ok()?)?),
+        _ => return None,
+    };
+    Some(Expr::unknown(kind))
+}
+
+fn fold_float_binop(op: &BinOp, a: f64, b: f64) -> Option<Expr> {
+    match op {
+        BinOp::Add => (a + b).is_finite().then(|| Expr::unknown(ExprKind::Float(a + b))),
+        BinOp::Sub => (a - b).is_finite().then(|| Expr::unknown(ExprKind::Float(a - b))),
+        BinOp::Mul => (a * b).is_finite().then(|| Expr::unknown(ExprKind::Float(a * b))),
+        BinOp::Div => (a / b).is_finite().then(|| Expr::unknown(ExprKind::Float(a / b))),
+        BinOp::Eq => Some(Expr::unknown(ExprKind::Bool(a == b))),
+        BinOp::NotEq => Some(Expr::unknown(ExprKind::Bool(a != b))),
+        BinOp::Lt => Some(Expr::unknown(ExprKind::Bool(a < b))),
+        BinOp::LtEq => Some(Expr::unknown(ExprKind::Bool(a <= b))),
+        BinOp::Gt => Some(Expr::unknown(ExprKind::Bool(a > b))),
+        BinOp::GtEq => Some(Expr::unknown(ExprKind::Bool(a >= b))),
+        _ => None,
+    }
+}
+
+fn fold_bool_binop(op: &BinOp, a: bool, b: bool) -> Option<Expr> {
+    let value = match op {
+        BinOp::And => a && b,
+        BinOp::Or => a || b,
+        BinOp::Eq => a == b,
+        BinOp::NotEq => a != b,
+        _ => return None,
+    };
+    Some(Expr::unknown(ExprKind::Bool(value)))
+}
+
+/// Whether swapping `op`'s operands leaves its result unchanged, so an
+/// identity written for one operand order (e.g. `x + 0`) also matches the
+/// other (`0 + x`).
+fn is_commutative(op: &BinOp) -> bool {
+    matches!(op, BinOp::Add | BinOp::Mul | BinOp::BitAnd)
+}
+
+/// Folds `x + 0`, `0 + x`, `x - 0`, `x * 1`, `1 * x`, `x * 0`, `x - x`, and
+/// `x & x`.
+fn fold_identity_binop(op: &BinOp, l: &Expr, r: &Expr) -> Option<Expr> {
+    if let Some(folded) = fold_identity_binop_ordered(op, l, r) {
+        return Some(folded);
+    }
+    if is_commutative(op) {
+        return fold_identity_binop_ordered(op, r, l);
+    }
+    None
+}
+
+/// Tries an identity assuming `l`/`r` are in the order they're written in
+/// this function's patterns; `fold_identity_binop` retries with the
+/// operands swapped for commutative operators.
+fn fold_identity_binop_ordered(op: &BinOp, l: &Expr, r: &Expr) -> Option<Expr> {
+    match op {
+        BinOp::Add => is_zero(r).then(|| l.clone()),
+        BinOp::Sub => {
+            if is_zero(r) {
+                Some(l.clone())
+            } else if is_pure(l) && l.kind == r.kind {
+                Some(Expr::unknown(ExprKind::Int(0)))
+            } else {
+                None
+            }
+        }
+        BinOp::Mul => {
+            if is_zero(r) {
+                Some(r.clone())
+            } else if is_one(r) {
+                Some(l.clone())
+            } else {
+                None
+            }
+        }
+        BinOp::BitAnd if is_pure(l) && l.kind == r.kind => Some(l.clone()),
+        _ => None,
+    }
+}
+
+fn is_zero(expr: &Expr) -> bool {
+    matches!(expr.kind, ExprKind::Int(0)) || matches!(expr.kind, ExprKind::Float(f) if f == 0.0)
+}
+
+fn is_one(expr: &Expr) -> bool {
+    matches!(expr.kind, ExprKind::Int(1)) || matches!(expr.kind, ExprKind::Float(f) if f == 1.0)
+}
+
+/// Whether folding two copies of `expr` away is safe, i.e. evaluating it
+/// can't perform a side effect. Calls are excluded since evaluating them
+/// twice (or not at all) could observably differ.
+fn is_pure(expr: &Expr) -> bool {
+    match &expr.kind {
+        ExprKind::Call(..) => false,
+        ExprKind::Field(target, _) => is_pure(target),
+        ExprKind::Index(target, index) => is_pure(target) && is_pure(index),
+        ExprKind::Void
+        | ExprKind::Ident(_)
+        | ExprKind::CtxIdent(_)
+        | ExprKind::Sym(_)
+        | ExprKind::Str(_)
+        | ExprKind::Bytes(_)
+        | ExprKind::Int(_)
+        | ExprKind::Float(_)
+        | ExprKind::Bool(_)
+        | ExprKind::Fn(..) => true,
+        ExprKind::Block(stmts) => stmts.iter().all(|stmt| match stmt {
+            Stmt::Let(_, expr) | Stmt::Expr(expr) => is_pure(expr),
+        }),
+        ExprKind::Tuple(tuple) => tuple.iter().all(|entry| match entry {
+            TupleEntry::Pos(expr) | TupleEntry::Named(_, expr) => is_pure(expr),
+        }),
+        ExprKind::List(items) => items.iter().all(is_pure),
+        ExprKind::UnOp(_, operand) => is_pure(operand),
+        ExprKind::BinOp(_, l, r) => is_pure(l) && is_pure(r),
+        ExprKind::IfElse(cond, then, or_else) => {
+            is_pure(cond) && is_pure(then) && is_pure(or_else)
+        }
+        ExprKind::For {
+            iter,
+            guard,
+            body,
+            else_body,
+            ..
+        } => {
+            is_pure(iter)
+                && guard.as_deref().map_or(true, is_pure)
+                && is_pure(body)
+                && else_body.as_deref().map_or(true, is_pure)
+        }
+        ExprKind::Match(scrutinee, arms) => {
+            is_pure(scrutinee)
+                && arms.iter().all(|(_, guard, body)| {
+                    guard.as_ref().map_or(true, is_pure) && is_pure(body)
+                })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_expr;
+    use vamp_sym::Interner;
+
+    fn fold(source: &str) -> Expr {
+        let mut interner = Interner::new();
+        fold_constants(&parse_expr(source, &mut interner).unwrap())
+    }
+
+    #[test]
+    fn folds_literal_arithmetic() {
+        assert_eq!(fold("1 + 2"), Expr::unknown(ExprKind::Int(3)));
+        assert_eq!(fold("3.5 - 1.5"), Expr::unknown(ExprKind::Float(2.0)));
+        assert_eq!(fold("2 * 3"), Expr::unknown(ExprKind::Int(6)));
+        assert_eq!(fold("7 / 2"), Expr::unknown(ExprKind::Int(3)));
+    }
+
+    #[test]
+    fn does_not_fold_integer_division_by_zero() {
+        let mut interner = Interner::new();
+        let expected = parse_expr("1 / 0", &mut interner).unwrap();
+        assert_eq!(fold("1 / 0"), expected);
+    }
+
+    #[test]
+    fn does_not_fold_integer_overflow() {
+        let mut interner = Interner::new();
+        let source = format!("{} + 1", i64::MAX);
+        let expected = parse_expr(&source, &mut interner).unwrap();
+        assert_eq!(fold(&source), expected);
+    }
+
+    #[test]
+    fn does_not_fold_float_division_to_infinity() {
+        let mut interner = Interner::new();
+        let expected = parse_expr("1.0 / 0.0", &mut interner).unwrap();
+        assert_eq!(fold("1.0 / 0.0"), expected);
+    }
+
+    #[test]
+    fn folds_literal_comparisons() {
+        assert_eq!(fold("1 < 2"), Expr::unknown(ExprKind::Bool(true)));
+        assert_eq!(fold("1 == 1"), Expr::unknown(ExprKind::Bool(true)));
+        assert_eq!(fold("1.5 >= 2.0"), Expr::unknown(ExprKind::Bool(false)));
+        assert_eq!(fold("true && false"), Expr::unknown(ExprKind::Bool(false)));
+    }
+
+    #[test]
+    fn folds_literal_bitwise_ops() {
+        assert_eq!(fold("6 & 3"), Expr::unknown(ExprKind::Int(2)));
+        assert_eq!(fold("6 | 1"), Expr::unknown(ExprKind::Int(7)));
+        assert_eq!(fold("6 ^ 3"), Expr::unknown(ExprKind::Int(5)));
+        assert_eq!(fold("1 << 4"), Expr::unknown(ExprKind::Int(16)));
+        assert_eq!(fold("16 >> 4"), Expr::unknown(ExprKind::Int(1)));
+    }
+
+    #[test]
+    fn does_not_fold_a_shift_by_an_out_of_range_amount() {
+        let mut interner = Interner::new();
+        let expected = parse_expr("1 << 64", &mut interner).unwrap();
+        assert_eq!(fold("1 << 64"), expected);
+        // The negation in the operand is folded; the shift itself is kept.
+        let expected = Expr::unknown(ExprKind::BinOp(
+            BinOp::ShiftL,
+            Box::new(Expr::unknown(ExprKind::Int(1))),
+            Box::new(Expr::unknown(ExprKind::Int(-1))),
+        ));
+        assert_eq!(fold("1 << -1"), expected);
+    }
+
+    #[test]
+    fn folds_additive_and_multiplicative_identities() {
+        assert_eq!(fold("x + 0"), fold("x"));
+        assert_eq!(fold("0 + x"), fold("x"));
+        assert_eq!(fold("x - 0"), fold("x"));
+        assert_eq!(fold("x * 1"), fold("x"));
+        assert_eq!(fold("1 * x"), fold("x"));
+        assert_eq!(fold("x * 0"), Expr::unknown(ExprKind::Int(0)));
+        assert_eq!(fold("x - x"), Expr::unknown(ExprKind::Int(0)));
+    }
+
+    #[test]
+    fn folds_the_identities_inside_a_chained_expression() {
+        // `arg + 0 - arg * 1` collapses to `0` and `0 + arg` to `arg`; the
+        // rest is not reassociated, so it stays as written.
+        assert_eq!(
+            fold("arg + 0 - arg * 1 + arg + 1 + arg + 2 + arg + 3 - arg * 3 - 6"),
+            fold("arg + 1 + arg + 2 + arg + 3 - arg * 3 - 6")
+        );
+    }
+
+    #[test]
+    fn folds_if_else_with_a_literal_bool_condition() {
+        assert_eq!(fold("if true { 1 } else { 2 }"), fold("1"));
+        assert_eq!(fold("if false { 1 } else { 2 }"), fold("2"));
+        assert_eq!(fold("if false { x } else { y }"), fold("y"));
+    }
+
+    #[test]
+    fn does_not_fold_if_else_with_a_non_literal_condition() {
+        let mut interner = Interner::new();
+        let expected = parse_expr("if x { 1 } else { 2 }", &mut interner).unwrap();
+        assert_eq!(fold("if x { 1 } else { 2 }"), expected);
+    }
+}
@@ -1,16 +1,24 @@
-use crate::span::Span;
+use crate::span::{Loc, SourceMap, Span};
 
 /// A type of syntax error.
 #[derive(Debug, PartialEq, Clone)]
 pub enum ErrorKind {
     Delimiters,
+    /// Parsing ran out of tokens inside an open delimiter or other
+    /// unfinished construct, rather than hitting a token that doesn't
+    /// belong. A REPL can use this to tell "read another line" apart from
+    /// a hard syntax error.
+    Incomplete,
     InvalidChar,
     InvalidToken,
     IntInvalid,
     FloatInvalid,
     StringUnterminated,
     StringEscSeqInvalid,
+    Base64Invalid,
+    CommentUnterminated,
     NoUnboundExprAtModuleLevel,
+    RecursionLimitExceeded,
 }
 
 /// A syntax error with both type and location.
@@ -25,3 +33,65 @@ pub struct Error {
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
+
+impl ErrorKind {
+    /// A short human-readable description of this error kind, independent
+    /// of any source-specific detail.
+    fn describe(&self) -> &'static str {
+        match self {
+            ErrorKind::Delimiters => "mismatched or unexpected delimiter",
+            ErrorKind::Incomplete => "unexpected end of input",
+            ErrorKind::InvalidChar => "invalid character",
+            ErrorKind::InvalidToken => "unexpected token",
+            ErrorKind::IntInvalid => "invalid integer literal",
+            ErrorKind::FloatInvalid => "invalid floating point literal",
+            ErrorKind::StringUnterminated => "string literal is missing a closing quote",
+            ErrorKind::StringEscSeqInvalid => "invalid escape sequence in string literal",
+            ErrorKind::Base64Invalid => "invalid base64 literal",
+            ErrorKind::CommentUnterminated => "block comment is missing a closing `*/`",
+            ErrorKind::NoUnboundExprAtModuleLevel => {
+                "unbound expressions are not allowed at module level"
+            }
+            ErrorKind::RecursionLimitExceeded => "expression nesting exceeds the recursion limit",
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.detail {
+            Some(detail) => write!(f, "{}: {detail}", self.kind.describe()),
+            None => write!(f, "{}", self.kind.describe()),
+        }
+    }
+}
+
+/// A single parse error recovered from by [`parse_module_recover`] instead
+/// of aborting the whole parse, with its location resolved to 1-based
+/// line/column via a [`SourceMap`] built once for the whole source rather
+/// than per diagnostic.
+///
+/// [`parse_module_recover`]: crate::parser::parse_module_recover
+#[derive(Debug, PartialEq)]
+pub struct Diagnostic {
+    pub error: Error,
+    pub start: Loc,
+    pub end: Loc,
+}
+
+impl Diagnostic {
+    pub(crate) fn new(error: Error, map: &SourceMap) -> Diagnostic {
+        let (start, end) = map.span_lines(error.span);
+        Diagnostic { error, start, end }
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}",
+            self.start.line, self.start.column, self.error
+        )
+    }
+}
@@ -1,6 +1,9 @@
+use unicode_xid::UnicodeXID;
+
 use crate::{
     error::{Error, ErrorKind, Result},
     span::Span,
+    unescape::{decode_base64, unescape, unescape_bytes},
 };
 
 #[cfg(test)]
@@ -28,6 +31,7 @@ pub enum TokenKind {
     Percent,
     Eq,
     EqEq,
+    FatArrow,
     NotEq,
     Lt,
     LtLt,
@@ -42,13 +46,30 @@ pub enum TokenKind {
     OrOr,
     Caret,
     Tilde,
+    Backslash,
+
+    // Compound assignment
+    PlusEq,
+    MinusEq,
+    StarEq,
+    StarStarEq,
+    SlashEq,
+    PercentEq,
+    CaretEq,
+    LtLtEq,
+    GtGtEq,
+    AndEq,
+    OrEq,
 
     // Keywords
     Use,
+    As,
     Let,
     If,
     Else,
     For,
+    In,
+    Match,
 
     // Identifiers
     Ident,
@@ -57,16 +78,121 @@ pub enum TokenKind {
     // Literals
     Sym,
     Str,
+    /// A byte-string literal `b"..."`.
+    Bytes,
+    /// A base64 literal `b64"..."`.
+    Base64,
     Int,
     Float,
     True,
     False,
+
+    // Trivia (only emitted by `Tokens::with_trivia`)
+    Whitespace,
+    LineComment,
+    BlockComment,
+}
+
+impl TokenKind {
+    /// The binding power of a binary-operator token, or `None` if `self`
+    /// isn't one. Higher binds tighter: `Star`/`Slash`/`Percent` bind
+    /// tighter than `Plus`/`Minus`, which bind tighter than the shift,
+    /// bitwise, comparison, and logical tiers below them.
+    pub fn precedence(self) -> Option<u8> {
+        match self {
+            TokenKind::Period => Some(10),
+            TokenKind::StarStar => Some(9),
+            TokenKind::Star | TokenKind::Slash | TokenKind::Percent => Some(8),
+            TokenKind::Plus | TokenKind::Minus => Some(7),
+            TokenKind::LtLt | TokenKind::GtGt => Some(6),
+            TokenKind::And => Some(5),
+            TokenKind::Caret => Some(4),
+            TokenKind::Or => Some(3),
+            TokenKind::EqEq
+            | TokenKind::NotEq
+            | TokenKind::Lt
+            | TokenKind::LtEq
+            | TokenKind::Gt
+            | TokenKind::GtEq => Some(2),
+            TokenKind::AndAnd => Some(1),
+            TokenKind::OrOr => Some(0),
+            _ => None,
+        }
+    }
+
+    /// Maps a compound-assignment token back to its base binary operator,
+    /// e.g. `PlusEq -> Plus`.
+    pub fn assign_op(self) -> Option<TokenKind> {
+        match self {
+            TokenKind::PlusEq => Some(TokenKind::Plus),
+            TokenKind::MinusEq => Some(TokenKind::Minus),
+            TokenKind::StarEq => Some(TokenKind::Star),
+            TokenKind::StarStarEq => Some(TokenKind::StarStar),
+            TokenKind::SlashEq => Some(TokenKind::Slash),
+            TokenKind::PercentEq => Some(TokenKind::Percent),
+            TokenKind::CaretEq => Some(TokenKind::Caret),
+            TokenKind::LtLtEq => Some(TokenKind::LtLt),
+            TokenKind::GtGtEq => Some(TokenKind::GtGt),
+            TokenKind::AndEq => Some(TokenKind::And),
+            TokenKind::OrEq => Some(TokenKind::Or),
+            _ => None,
+        }
+    }
+
+    /// A short human-readable description for parser error messages:
+    /// either the token's fixed spelling in backticks, or a category name
+    /// for kinds whose text varies (identifiers, literals) or that cover
+    /// many distinct operator spellings.
+    pub fn describe(self) -> &'static str {
+        match self {
+            TokenKind::LParen => "`(`",
+            TokenKind::RParen => "`)`",
+            TokenKind::LBracket => "`[`",
+            TokenKind::RBracket => "`]`",
+            TokenKind::LBrace => "`{`",
+            TokenKind::RBrace => "`}`",
+            TokenKind::Comma => "`,`",
+            TokenKind::Colon => "`:`",
+            TokenKind::Period => "`.`",
+            TokenKind::Use => "`use`",
+            TokenKind::As => "`as`",
+            TokenKind::Let => "`let`",
+            TokenKind::If => "`if`",
+            TokenKind::Else => "`else`",
+            TokenKind::For => "`for`",
+            TokenKind::In => "`in`",
+            TokenKind::Match => "`match`",
+            TokenKind::FatArrow => "`=>`",
+            TokenKind::True => "`true`",
+            TokenKind::False => "`false`",
+            TokenKind::Ident => "an identifier",
+            TokenKind::CtxIdent => "a context identifier",
+            TokenKind::Sym => "a symbol literal",
+            TokenKind::Str => "a string literal",
+            TokenKind::Bytes => "a byte-string literal",
+            TokenKind::Base64 => "a base64 literal",
+            TokenKind::Int => "an integer literal",
+            TokenKind::Float => "a floating point literal",
+            TokenKind::Whitespace | TokenKind::LineComment | TokenKind::BlockComment => "trivia",
+            _ => "an operator",
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
 pub struct Token {
     pub kind: TokenKind,
     pub span: Span,
+    /// For an `Int`/`Float` token, the byte offset in the source where a
+    /// trailing type suffix (e.g. the `i32` in `10i32`) begins, if one is
+    /// present. `None` for every other token kind.
+    pub suffix_start: Option<usize>,
+    /// `true` for a `Comma` synthesized by automatic comma insertion (see
+    /// `auto_insert_comma`) rather than read from a literal `,` in the
+    /// source. Its span is always empty. A formatter working in
+    /// `Tokens::with_trivia` mode needs this to tell a real comma from one
+    /// it must decide whether to render.
+    pub synthetic: bool,
 }
 
 pub struct Tokens<'a> {
@@ -74,6 +200,16 @@ pub struct Tokens<'a> {
     span: Span,
     last_token_kind: Option<TokenKind>,
     auto_insert_comma: bool,
+    /// When set, lexical errors resynchronize instead of ending iteration:
+    /// an unterminated string stops at the end of its line rather than
+    /// devouring the rest of the source, and a run of invalid characters
+    /// is skipped up to the next whitespace/punctuation boundary.
+    recover: bool,
+    /// When set, whitespace and comments are emitted as `Whitespace`,
+    /// `LineComment`, and `BlockComment` tokens instead of being skipped,
+    /// so that concatenating every token's span reproduces `source`
+    /// exactly. Used by `Tokens::with_trivia` for lossless lexing.
+    trivia: bool,
 }
 
 impl<'a> Tokens<'a> {
@@ -83,6 +219,26 @@ impl<'a> Tokens<'a> {
             span: Span::default(),
             last_token_kind: None,
             auto_insert_comma: false,
+            recover: false,
+            trivia: false,
+        }
+    }
+
+    fn recovering(source: &str) -> Tokens {
+        Tokens {
+            recover: true,
+            ..Tokens::new(source)
+        }
+    }
+
+    /// A lossless tokenization mode for tools like a formatter that need
+    /// to re-emit untouched regions of source verbatim: whitespace and
+    /// comments come back as their own trivia tokens rather than being
+    /// silently skipped, so `source == concat(all token spans)`.
+    pub fn with_trivia(source: &str) -> Tokens {
+        Tokens {
+            trivia: true,
+            ..Tokens::new(source)
         }
     }
 
@@ -98,7 +254,7 @@ impl<'a> Tokens<'a> {
             .unwrap_or(&b'\0')
     }
 
-    fn bump(&mut self) {
+    fn bump_by(&mut self, width: usize) {
         if self.first() == b'\n' {
             self.auto_insert_comma = matches!(
                 self.last_token_kind,
@@ -111,10 +267,16 @@ impl<'a> Tokens<'a> {
                         | TokenKind::Int
                         | TokenKind::Float
                         | TokenKind::Str
+                        | TokenKind::Bytes
+                        | TokenKind::Base64
                 )
             );
         }
-        self.span.end += 1;
+        self.span.end += width;
+    }
+
+    fn bump(&mut self) {
+        self.bump_by(1);
     }
 
     #[inline]
@@ -134,12 +296,74 @@ impl<'a> Tokens<'a> {
         }
     }
 
+    /// Decodes the UTF-8 scalar starting at the current position without
+    /// consuming it, returning `('\0', 0)` at the end of the source.
+    fn peek_char(&self) -> (char, usize) {
+        match self.source[self.span.end..].chars().next() {
+            Some(c) => (c, c.len_utf8()),
+            None => ('\0', 0),
+        }
+    }
+
+    /// Like [`Tokens::bump_if`], but decodes a full UTF-8 scalar instead of
+    /// a single byte, advancing by the scalar's encoded width.
+    #[inline]
+    fn bump_char_if(&mut self, f: impl FnOnce(char) -> bool) -> bool {
+        let (c, width) = self.peek_char();
+        if width > 0 && f(c) {
+            self.bump_by(width);
+            true
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    fn bump_char_while(&mut self, f: impl Fn(char) -> bool) {
+        while self.bump_char_if(&f) {}
+    }
+
     #[inline]
     fn ok(&mut self, kind: TokenKind) -> Option<Result<Token>> {
+        self.ok_with_suffix(kind, None)
+    }
+
+    #[inline]
+    fn ok_with_suffix(&mut self, kind: TokenKind, suffix_start: Option<usize>) -> Option<Result<Token>> {
         self.last_token_kind = Some(kind);
         Some(Ok(Token {
             kind,
             span: self.span,
+            suffix_start,
+            synthetic: false,
+        }))
+    }
+
+    /// Builds a trivia token (`Whitespace`/`LineComment`/`BlockComment`)
+    /// without touching `last_token_kind`, so the auto-insert-comma
+    /// decision for the next real token still sees the last *real* token,
+    /// not the trivia that followed it.
+    #[inline]
+    fn ok_trivia(&self, kind: TokenKind) -> Option<Result<Token>> {
+        Some(Ok(Token {
+            kind,
+            span: self.span,
+            suffix_start: None,
+            synthetic: false,
+        }))
+    }
+
+    /// Builds the zero-width `Comma` token synthesized by automatic comma
+    /// insertion, flagged `synthetic` so a formatter can tell it apart
+    /// from a real `,` in the source.
+    #[inline]
+    fn synthetic_comma(&mut self) -> Option<Result<Token>> {
+        self.last_token_kind = Some(TokenKind::Comma);
+        Some(Ok(Token {
+            kind: TokenKind::Comma,
+            span: self.span,
+            suffix_start: None,
+            synthetic: true,
         }))
     }
 
@@ -152,15 +376,86 @@ impl<'a> Tokens<'a> {
         }))
     }
 
-    fn whitespace(&mut self) {
+    /// Skips whitespace, `#` line comments, and nested `#{ ... }#` block
+    /// comments. Returns an error if a block comment is still open at EOF.
+    fn whitespace(&mut self) -> Option<Error> {
         loop {
             self.bump_while(|c| c.is_ascii_whitespace());
             if self.bump_if(|c| c == b'#') {
-                self.bump_while(|c| c != b'\n');
+                if self.bump_if(|c| c == b'{') {
+                    if let Some(error) = self.block_comment() {
+                        return Some(error);
+                    }
+                } else {
+                    self.bump_while(|c| c != b'\n');
+                }
             } else {
                 break;
             }
         }
+        None
+    }
+
+    /// The `Tokens::with_trivia` counterpart to `whitespace`: instead of
+    /// silently skipping one run of whitespace or one comment, emits it as
+    /// its own token and returns. Called once per `next()`, so a source
+    /// region with several whitespace/comment runs in a row comes back as
+    /// that many separate trivia tokens, each still self-contained.
+    fn trivia_token(&mut self) -> Option<Result<Token>> {
+        self.span.start = self.span.end;
+        if self.bump_if(|c| c.is_ascii_whitespace()) {
+            self.bump_while(|c| c.is_ascii_whitespace());
+            return self.ok_trivia(TokenKind::Whitespace);
+        }
+        if self.first() == b'#' && self.second() == b'{' {
+            self.bump();
+            self.bump();
+            return match self.block_comment() {
+                Some(error) => Some(Err(error)),
+                None => self.ok_trivia(TokenKind::BlockComment),
+            };
+        }
+        if self.first() == b'#' {
+            self.bump();
+            self.bump_while(|c| c != b'\n');
+            return self.ok_trivia(TokenKind::LineComment);
+        }
+        None
+    }
+
+    /// Scans the body of a `#{ ... }#` block comment, whose opening
+    /// delimiter has already been consumed. Nested `#{ ... }#` comments
+    /// increase a depth counter so they close correctly; hitting EOF before
+    /// depth returns to zero reports `ErrorKind::CommentUnterminated` with
+    /// a span covering the whole unterminated comment.
+    fn block_comment(&mut self) -> Option<Error> {
+        let start = self.span.end - 2;
+        let mut depth = 1u32;
+        loop {
+            if self.first() == b'\0' {
+                return Some(Error {
+                    kind: ErrorKind::CommentUnterminated,
+                    detail: None,
+                    span: Span {
+                        start,
+                        end: self.span.end,
+                    },
+                });
+            } else if self.first() == b'#' && self.second() == b'{' {
+                self.bump();
+                self.bump();
+                depth += 1;
+            } else if self.first() == b'}' && self.second() == b'#' {
+                self.bump();
+                self.bump();
+                depth -= 1;
+                if depth == 0 {
+                    return None;
+                }
+            } else {
+                self.bump();
+            }
+        }
     }
 
     fn punctuation(&mut self) -> Option<Result<Token>> {
@@ -183,22 +478,46 @@ impl<'a> Tokens<'a> {
         } else if self.bump_if(|c| c == b'.') {
             self.ok(TokenKind::Period)
         } else if self.bump_if(|c| c == b'+') {
-            self.ok(TokenKind::Plus)
+            if self.bump_if(|c| c == b'=') {
+                self.ok(TokenKind::PlusEq)
+            } else {
+                self.ok(TokenKind::Plus)
+            }
         } else if self.bump_if(|c| c == b'-') {
-            self.ok(TokenKind::Minus)
+            if self.bump_if(|c| c == b'=') {
+                self.ok(TokenKind::MinusEq)
+            } else {
+                self.ok(TokenKind::Minus)
+            }
         } else if self.bump_if(|c| c == b'*') {
             if self.bump_if(|c| c == b'*') {
-                self.ok(TokenKind::StarStar)
+                if self.bump_if(|c| c == b'=') {
+                    self.ok(TokenKind::StarStarEq)
+                } else {
+                    self.ok(TokenKind::StarStar)
+                }
+            } else if self.bump_if(|c| c == b'=') {
+                self.ok(TokenKind::StarEq)
             } else {
                 self.ok(TokenKind::Star)
             }
         } else if self.bump_if(|c| c == b'/') {
-            self.ok(TokenKind::Slash)
+            if self.bump_if(|c| c == b'=') {
+                self.ok(TokenKind::SlashEq)
+            } else {
+                self.ok(TokenKind::Slash)
+            }
         } else if self.bump_if(|c| c == b'%') {
-            self.ok(TokenKind::Percent)
+            if self.bump_if(|c| c == b'=') {
+                self.ok(TokenKind::PercentEq)
+            } else {
+                self.ok(TokenKind::Percent)
+            }
         } else if self.bump_if(|c| c == b'=') {
             if self.bump_if(|c| c == b'=') {
                 self.ok(TokenKind::EqEq)
+            } else if self.bump_if(|c| c == b'>') {
+                self.ok(TokenKind::FatArrow)
             } else {
                 self.ok(TokenKind::Eq)
             }
@@ -210,7 +529,11 @@ impl<'a> Tokens<'a> {
             }
         } else if self.bump_if(|c| c == b'>') {
             if self.bump_if(|c| c == b'>') {
-                self.ok(TokenKind::GtGt)
+                if self.bump_if(|c| c == b'=') {
+                    self.ok(TokenKind::GtGtEq)
+                } else {
+                    self.ok(TokenKind::GtGt)
+                }
             } else if self.bump_if(|c| c == b'=') {
                 self.ok(TokenKind::GtEq)
             } else {
@@ -218,7 +541,11 @@ impl<'a> Tokens<'a> {
             }
         } else if self.bump_if(|c| c == b'<') {
             if self.bump_if(|c| c == b'<') {
-                self.ok(TokenKind::LtLt)
+                if self.bump_if(|c| c == b'=') {
+                    self.ok(TokenKind::LtLtEq)
+                } else {
+                    self.ok(TokenKind::LtLt)
+                }
             } else if self.bump_if(|c| c == b'=') {
                 self.ok(TokenKind::LtEq)
             } else {
@@ -227,33 +554,76 @@ impl<'a> Tokens<'a> {
         } else if self.bump_if(|c| c == b'&') {
             if self.bump_if(|c| c == b'&') {
                 self.ok(TokenKind::AndAnd)
+            } else if self.bump_if(|c| c == b'=') {
+                self.ok(TokenKind::AndEq)
             } else {
                 self.ok(TokenKind::And)
             }
         } else if self.bump_if(|c| c == b'|') {
             if self.bump_if(|c| c == b'|') {
                 self.ok(TokenKind::OrOr)
+            } else if self.bump_if(|c| c == b'=') {
+                self.ok(TokenKind::OrEq)
             } else {
                 self.ok(TokenKind::Or)
             }
         } else if self.bump_if(|c| c == b'^') {
-            self.ok(TokenKind::Caret)
+            if self.bump_if(|c| c == b'=') {
+                self.ok(TokenKind::CaretEq)
+            } else {
+                self.ok(TokenKind::Caret)
+            }
         } else if self.bump_if(|c| c == b'~') {
             self.ok(TokenKind::Tilde)
+        } else if self.bump_if(|c| c == b'\\') {
+            self.ok(TokenKind::Backslash)
         } else {
             None
         }
     }
 
     fn ident(&mut self) -> Option<Result<Token>> {
-        if self.bump_if(|c| matches!(c, b'A'..=b'Z' | b'a'..=b'z' | b'_')) {
-            self.bump_while(|c| matches!(c, b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'_'));
+        // A raw identifier (`r#let`) lets a reserved word be used as a
+        // plain identifier. It's only raw-ident syntax when exactly one
+        // `#` is followed by an identifier character; `r#"..."#` and
+        // `r##"..."##` are hash-delimited raw strings instead, handled by
+        // `symbol_or_string`.
+        if self.first() == b'r' && self.second() == b'#' {
+            let after_hash = self.source[self.span.end + 2..]
+                .chars()
+                .next()
+                .unwrap_or('\0');
+            return if after_hash == '_' || after_hash.is_xid_start() {
+                self.bump();
+                self.bump();
+                self.bump_char_while(|c| c == '_' || c.is_xid_continue());
+                self.ok(TokenKind::Ident)
+            } else {
+                None
+            };
+        }
+        if self.first() == b'r' && matches!(self.second(), b'"' | b'\'') {
+            return None;
+        }
+        // `b"..."` and `b64"..."` are byte-string/base64 literals, handled
+        // by `byte_string`, not plain identifiers.
+        if self.first() == b'b' && self.second() == b'"' {
+            return None;
+        }
+        if self.source[self.span.end..].starts_with("b64\"") {
+            return None;
+        }
+        if self.bump_char_if(|c| c == '_' || c.is_xid_start()) {
+            self.bump_char_while(|c| c == '_' || c.is_xid_continue());
             self.ok(match &self.source[self.span] {
                 "use" => TokenKind::Use,
+                "as" => TokenKind::As,
                 "let" => TokenKind::Let,
                 "if" => TokenKind::If,
                 "else" => TokenKind::Else,
                 "for" => TokenKind::For,
+                "in" => TokenKind::In,
+                "match" => TokenKind::Match,
                 "true" => TokenKind::True,
                 "false" => TokenKind::False,
                 _ => TokenKind::Ident,
@@ -272,7 +642,61 @@ impl<'a> Tokens<'a> {
         }
     }
 
+    /// Scans a raw string/symbol literal (`r"..."`, `r'...'`, or a
+    /// hash-delimited form like `r#"..."#`), whose closing delimiter must
+    /// be followed by the same number of `#`s as the opening one. Inside a
+    /// raw literal backslashes are ordinary characters. Returns `None`,
+    /// rewinding the cursor, if `r` plus a run of `#`s isn't followed by a
+    /// quote after all (e.g. the `r#let` raw-identifier case, already
+    /// handled by `ident`).
+    fn raw_symbol_or_string(&mut self) -> Option<Result<Token>> {
+        let start = self.span.end;
+        self.bump();
+        let mut hashes = 0u32;
+        while self.first() == b'#' {
+            self.bump();
+            hashes += 1;
+        }
+        let delimiter = self.first();
+        if delimiter != b'\'' && delimiter != b'"' {
+            self.span.end = start;
+            return None;
+        }
+        let kind = if delimiter == b'\'' {
+            TokenKind::Sym
+        } else {
+            TokenKind::Str
+        };
+        self.bump();
+        loop {
+            if self.first() == b'\0' || (self.recover && self.first() == b'\n') {
+                return self.err(ErrorKind::StringUnterminated, None);
+            } else if self.first() == delimiter {
+                let closing_start = self.span.end;
+                self.bump();
+                let mut closing_hashes = 0u32;
+                while closing_hashes < hashes && self.first() == b'#' {
+                    self.bump();
+                    closing_hashes += 1;
+                }
+                if closing_hashes == hashes {
+                    return self.ok(kind);
+                }
+                // Not enough hashes followed the delimiter: it was just
+                // ordinary content. Resume scanning right after it.
+                self.span.end = closing_start + 1;
+            } else {
+                self.bump();
+            }
+        }
+    }
+
     fn symbol_or_string(&mut self) -> Option<Result<Token>> {
+        if self.first() == b'r' && matches!(self.second(), b'"' | b'\'' | b'#') {
+            if let Some(result) = self.raw_symbol_or_string() {
+                return Some(result);
+            }
+        }
         if matches!(self.first(), b'\'' | b'"') {
             let delimiter = self.first();
             let kind = if delimiter == b'\'' {
@@ -282,13 +706,16 @@ impl<'a> Tokens<'a> {
             };
             self.bump();
             loop {
-                if self.first() == b'\0' {
+                if self.first() == b'\0' || (self.recover && self.first() == b'\n') {
                     return self.err(ErrorKind::StringUnterminated, None);
                 } else if self.bump_if(|c| c == b'\\') {
                     if !self.bump_if(|c| c != b'\0') {
                         return self.err(ErrorKind::StringUnterminated, None);
                     }
                 } else if self.bump_if(|c| c == delimiter) {
+                    if let Err(error) = unescape(&self.source[self.span], self.span.start) {
+                        return Some(Err(error));
+                    }
                     return self.ok(kind);
                 } else {
                     self.bump();
@@ -299,49 +726,152 @@ impl<'a> Tokens<'a> {
         }
     }
 
+    /// Scans a byte-string literal `b"..."` or a base64 literal
+    /// `b64"..."`, each producing its own token kind so the parser can
+    /// decode them differently. A byte-string's body is escape-aware like
+    /// a `Str`'s (a `\"` doesn't end it); a base64 payload has no escapes,
+    /// so any `"` ends it.
+    fn byte_string(&mut self) -> Option<Result<Token>> {
+        let (kind, prefix_len) = if self.source[self.span.end..].starts_with("b64\"") {
+            (TokenKind::Base64, 3)
+        } else if self.first() == b'b' && self.second() == b'"' {
+            (TokenKind::Bytes, 1)
+        } else {
+            return None;
+        };
+        self.bump_by(prefix_len);
+        self.bump();
+        loop {
+            if self.first() == b'\0' || (self.recover && self.first() == b'\n') {
+                return self.err(ErrorKind::StringUnterminated, None);
+            } else if kind == TokenKind::Bytes && self.bump_if(|c| c == b'\\') {
+                if !self.bump_if(|c| c != b'\0') {
+                    return self.err(ErrorKind::StringUnterminated, None);
+                }
+            } else if self.bump_if(|c| c == b'"') {
+                let prefix_end = self.span.start + prefix_len;
+                let body = &self.source[prefix_end..self.span.end];
+                let decoded = if kind == TokenKind::Bytes {
+                    unescape_bytes(body, prefix_end).map(|_| ())
+                } else {
+                    decode_base64(body, prefix_end).map(|_| ())
+                };
+                if let Err(error) = decoded {
+                    return Some(Err(error));
+                }
+                return self.ok(kind);
+            } else {
+                self.bump();
+            }
+        }
+    }
+
+    /// Scans a run of digits in some base, accepting `_` separators as
+    /// long as each one sits between two digits. `saw_digit` seeds whether
+    /// a digit has already been consumed before this call (e.g. a decimal
+    /// literal's leading digit, already consumed by the caller to detect
+    /// that this is a number at all); `require_digit` additionally fails
+    /// if the run turns out empty, which is wanted after a `0x`/`0b`/`0o`
+    /// prefix but not for an optional fractional part like the `.` in
+    /// `0.`.
+    fn digits(
+        &mut self,
+        is_digit: impl Fn(u8) -> bool,
+        mut saw_digit: bool,
+        require_digit: bool,
+    ) -> Option<Error> {
+        let mut trailing_underscore = false;
+        loop {
+            if is_digit(self.first()) {
+                self.bump();
+                saw_digit = true;
+                trailing_underscore = false;
+            } else if self.first() == b'_' {
+                if !saw_digit {
+                    return Some(Error {
+                        kind: ErrorKind::IntInvalid,
+                        detail: None,
+                        span: self.span,
+                    });
+                }
+                self.bump();
+                trailing_underscore = true;
+            } else {
+                break;
+            }
+        }
+        if trailing_underscore || (require_digit && !saw_digit) {
+            Some(Error {
+                kind: ErrorKind::IntInvalid,
+                detail: None,
+                span: self.span,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Scans an optional trailing type suffix (e.g. the `i32` in
+    /// `10i32`), returning the byte offset where it starts so a later
+    /// stage can read it back out of the source, as rustc's lexer does
+    /// with `suffix_start`.
+    fn suffix(&mut self) -> Option<usize> {
+        let start = self.span.end;
+        if self.bump_char_if(|c| c == '_' || c.is_xid_start()) {
+            self.bump_char_while(|c| c == '_' || c.is_xid_continue());
+            Some(start)
+        } else {
+            None
+        }
+    }
+
     fn int_or_float(&mut self) -> Option<Result<Token>> {
         if self.first() == b'0' {
-            match self.second() {
+            let digits: Option<fn(u8) -> bool> = match self.second() {
                 // Binary literal
-                b'b' => {
-                    self.bump();
-                    self.bump();
-                    self.bump_while(|c| matches!(c, b'0' | b'1'));
-                    return self.ok(TokenKind::Int);
-                }
+                b'b' => Some(|c: u8| matches!(c, b'0' | b'1')),
                 // Octal literal
-                b'o' => {
-                    self.bump();
-                    self.bump();
-                    self.bump_while(|c| matches!(c, b'0'..=b'7'));
-                    return self.ok(TokenKind::Int);
-                }
+                b'o' => Some(|c: u8| matches!(c, b'0'..=b'7')),
                 // Hexadecimal literal
-                b'x' => {
-                    self.bump();
-                    self.bump();
-                    self.bump_while(|c| matches!(c, b'A'..=b'F' | b'a'..=b'f' | b'0'..=b'9'));
-                    return self.ok(TokenKind::Int);
+                b'x' => Some(|c: u8| matches!(c, b'A'..=b'F' | b'a'..=b'f' | b'0'..=b'9')),
+                _ => None,
+            };
+            if let Some(is_digit) = digits {
+                self.bump();
+                self.bump();
+                if let Some(error) = self.digits(is_digit, false, true) {
+                    return Some(Err(error));
                 }
-                _ => {}
+                let suffix_start = self.suffix();
+                return self.ok_with_suffix(TokenKind::Int, suffix_start);
             }
         }
         if self.bump_if(|c| c.is_ascii_digit()) {
-            self.bump_while(|c| c.is_ascii_digit());
-            if self.bump_if(|c| c == b'.') {
-                self.bump_while(|c| c.is_ascii_digit());
-                if self.bump_if(|c| c == b'e') {
-                    self.bump_if(|c| c == b'-');
-                    self.bump_while(|c| c.is_ascii_digit());
+            if let Some(error) = self.digits(|c| c.is_ascii_digit(), true, true) {
+                return Some(Err(error));
+            }
+            let is_float = if self.bump_if(|c| c == b'.') {
+                if let Some(error) = self.digits(|c| c.is_ascii_digit(), false, false) {
+                    return Some(Err(error));
                 }
-                self.ok(TokenKind::Float)
-            } else if self.bump_if(|c| c == b'e') {
-                self.bump_if(|c| c == b'-');
-                self.bump_while(|c| c.is_ascii_digit());
-                self.ok(TokenKind::Float)
+                true
             } else {
-                self.ok(TokenKind::Int)
+                false
+            };
+            let has_exponent = self.bump_if(|c| matches!(c, b'e' | b'E'));
+            if has_exponent {
+                self.bump_if(|c| matches!(c, b'+' | b'-'));
+                if let Some(error) = self.digits(|c| c.is_ascii_digit(), false, false) {
+                    return Some(Err(error));
+                }
             }
+            let suffix_start = self.suffix();
+            let kind = if is_float || has_exponent {
+                TokenKind::Float
+            } else {
+                TokenKind::Int
+            };
+            self.ok_with_suffix(kind, suffix_start)
         } else {
             None
         }
@@ -354,38 +884,96 @@ impl<'a> Tokens<'a> {
             None
         }
     }
+
+    /// Resynchronizes after a lexical error so iteration can keep producing
+    /// tokens instead of ending. An unterminated string already stopped at
+    /// its line's end (see `symbol_or_string`); an invalid character run is
+    /// skipped up to the next plausible token boundary.
+    fn resync(&mut self, kind: ErrorKind) {
+        if kind == ErrorKind::InvalidChar {
+            self.bump_while(|c| !is_boundary(c));
+        }
+    }
 }
 
 impl<'a> Iterator for Tokens<'a> {
     type Item = Result<Token>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.whitespace();
+        if self.trivia {
+            if let Some(result) = self.trivia_token() {
+                return Some(result);
+            }
+        } else if let Some(error) = self.whitespace() {
+            return Some(Err(error));
+        }
         self.span.start = self.span.end;
 
         if self.auto_insert_comma {
-            let comma = self.ok(TokenKind::Comma);
+            let comma = self.synthetic_comma();
             self.auto_insert_comma = false;
             return comma;
         }
 
-        self.punctuation()
+        let result = self
+            .punctuation()
             .or_else(|| self.ident())
             .or_else(|| self.ctx_ident())
+            .or_else(|| self.byte_string())
             .or_else(|| self.symbol_or_string())
             .or_else(|| self.int_or_float())
-            .or_else(|| self.error())
+            .or_else(|| self.error());
+
+        if self.recover {
+            if let Some(Err(ref error)) = result {
+                self.resync(error.kind.clone());
+            }
+        }
+        result
     }
 }
 
+/// Characters that end a run of garbage bytes during `resync`: whitespace,
+/// the nul sentinel past end-of-source, and the first byte of every known
+/// punctuation/identifier/literal token, i.e. anywhere a fresh token could
+/// plausibly start.
+fn is_boundary(c: u8) -> bool {
+    c.is_ascii_whitespace()
+        || matches!(
+            c,
+            b'\0' | b'(' | b')' | b'[' | b']' | b'{' | b'}' | b',' | b':' | b'.' | b'+' | b'-'
+                | b'*' | b'/' | b'%' | b'=' | b'!' | b'<' | b'>' | b'&' | b'|' | b'^' | b'~'
+                | b'\\' | b'@' | b'\'' | b'"' | b'A'..=b'Z' | b'a'..=b'z' | b'_' | b'0'..=b'9'
+        )
+}
+
 // Average token length used to pre-allocate the token vector based on the
 // length of the source string.
 const AVERAGE_TOKEN_LEN: usize = 128;
 
 pub fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let (tokens, mut errors) = tokenize_recover(source);
+    let first_error = errors.drain(..).next();
+    match first_error {
+        Some(error) => Err(error),
+        None => Ok(tokens),
+    }
+}
+
+/// Like `tokenize`, but never bails on the first lexical error: it resyncs
+/// past invalid characters and unterminated strings, and keeps going after
+/// an unterminated block comment, so a source file with several unrelated
+/// lexical problems reports all of them — with accurate spans — in one
+/// pass. This is the entry point tooling like an LSP or a batch linter
+/// should use instead of `tokenize`.
+pub fn tokenize_recover(source: &str) -> (Vec<Token>, Vec<Error>) {
     let mut tokens = Vec::with_capacity(source.len() / AVERAGE_TOKEN_LEN);
-    for token in Tokens::new(source) {
-        tokens.push(token?)
+    let mut errors = Vec::new();
+    for result in Tokens::recovering(source) {
+        match result {
+            Ok(token) => tokens.push(token),
+            Err(error) => errors.push(error),
+        }
     }
-    Ok(tokens)
+    (tokens, errors)
 }
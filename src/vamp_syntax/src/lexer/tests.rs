@@ -4,7 +4,7 @@ fn token_slices(source: &str) -> Result<Vec<(TokenKind, &str)>> {
     let tokens = tokenize(source)?;
     Ok(tokens
         .into_iter()
-        .map(|Token { kind, span }| (kind, &source[span]))
+        .map(|Token { kind, span, .. }| (kind, &source[span]))
         .collect())
 }
 
@@ -17,6 +17,47 @@ fn whitespace() {
     );
 }
 
+#[test]
+fn block_comments() {
+    assert_eq!(token_slices("#{ a block comment }#"), Ok(vec![]));
+    assert_eq!(
+        token_slices("x #{ a #{ nested }# comment }# y"),
+        Ok(vec![(TokenKind::Ident, "x"), (TokenKind::Ident, "y")])
+    );
+}
+
+#[test]
+fn block_comment_newlines_cause_at_most_one_auto_inserted_comma() {
+    assert_eq!(
+        token_slices("x #{\nmulti\nline\n}#\ny"),
+        Ok(vec![
+            (TokenKind::Ident, "x"),
+            (TokenKind::Comma, ""),
+            (TokenKind::Ident, "y"),
+        ])
+    );
+}
+
+#[test]
+fn block_comment_unterminated() {
+    assert!(matches!(
+        token_slices("#{ never closed"),
+        Err(Error {
+            kind: ErrorKind::CommentUnterminated,
+            detail: None,
+            span: _,
+        })
+    ));
+    assert!(matches!(
+        token_slices("#{ outer #{ inner }# still open"),
+        Err(Error {
+            kind: ErrorKind::CommentUnterminated,
+            detail: None,
+            span: _,
+        })
+    ));
+}
+
 #[test]
 fn valid_tokens() {
     let cases = [
@@ -39,6 +80,7 @@ fn valid_tokens() {
         (TokenKind::Percent, "%"),
         (TokenKind::Eq, "="),
         (TokenKind::EqEq, "=="),
+        (TokenKind::FatArrow, "=>"),
         (TokenKind::NotEq, "!="),
         (TokenKind::Lt, "<"),
         (TokenKind::LtLt, "<<"),
@@ -53,12 +95,28 @@ fn valid_tokens() {
         (TokenKind::OrOr, "||"),
         (TokenKind::Caret, "^"),
         (TokenKind::Tilde, "~"),
+        (TokenKind::Backslash, "\\"),
+        // Compound assignment
+        (TokenKind::PlusEq, "+="),
+        (TokenKind::MinusEq, "-="),
+        (TokenKind::StarEq, "*="),
+        (TokenKind::StarStarEq, "**="),
+        (TokenKind::SlashEq, "/="),
+        (TokenKind::PercentEq, "%="),
+        (TokenKind::CaretEq, "^="),
+        (TokenKind::LtLtEq, "<<="),
+        (TokenKind::GtGtEq, ">>="),
+        (TokenKind::AndEq, "&="),
+        (TokenKind::OrEq, "|="),
         // Keywords
         (TokenKind::Use, "use"),
+        (TokenKind::As, "as"),
         (TokenKind::Let, "let"),
         (TokenKind::If, "if"),
         (TokenKind::Else, "else"),
         (TokenKind::For, "for"),
+        (TokenKind::In, "in"),
+        (TokenKind::Match, "match"),
         // Identifiers
         (TokenKind::Ident, "_"),
         (TokenKind::Ident, "t"),
@@ -70,6 +128,9 @@ fn valid_tokens() {
         (TokenKind::Ident, "X1"),
         (TokenKind::Ident, "Identifier"),
         (TokenKind::Ident, "SHIFT_RIGHT"),
+        (TokenKind::Ident, "café"),
+        (TokenKind::Ident, "Δt"),
+        (TokenKind::Ident, "名前"),
         // Context identifiers
         (TokenKind::CtxIdent, "@"),
         (TokenKind::CtxIdent, "@self"),
@@ -87,6 +148,20 @@ fn valid_tokens() {
             TokenKind::Str,
             r#""The quick brown fox jumps over the lazy dog.""#,
         ),
+        // Byte-string and base64 literals
+        (TokenKind::Bytes, r#"b"""#),
+        (TokenKind::Bytes, r#"b"abc""#),
+        (TokenKind::Bytes, r#"b"\xFF""#),
+        (TokenKind::Base64, r#"b64"""#),
+        (TokenKind::Base64, r#"b64"SGVsbG8=""#),
+        // Raw string/symbol literals
+        (TokenKind::Str, r#"r"no \escapes here""#),
+        (TokenKind::Sym, r#"r'no \escapes here'"#),
+        (TokenKind::Str, r###"r#"can contain "quotes""#"###),
+        (TokenKind::Str, r####"r##"and "#hashes" too"##"####),
+        // Raw identifiers
+        (TokenKind::Ident, "r#let"),
+        (TokenKind::Ident, "r#for"),
         // Int literals
         (TokenKind::Int, "0"),
         (TokenKind::Int, "12"),
@@ -94,6 +169,11 @@ fn valid_tokens() {
         (TokenKind::Int, "0777"),
         (TokenKind::Int, "0b1010"),
         (TokenKind::Int, "0xfAb93"),
+        (TokenKind::Int, "1_000_000"),
+        (TokenKind::Int, "0xFF_FF"),
+        (TokenKind::Int, "0b1010_0101"),
+        (TokenKind::Int, "0o17_17"),
+        (TokenKind::Int, "10i32"),
         // Float literals
         (TokenKind::Float, "0."),
         (TokenKind::Float, "0.5"),
@@ -101,6 +181,10 @@ fn valid_tokens() {
         (TokenKind::Float, "1e10"),
         (TokenKind::Float, "2.5e2"),
         (TokenKind::Float, "1e-10"),
+        (TokenKind::Float, "1E10"),
+        (TokenKind::Float, "1e+10"),
+        (TokenKind::Float, "1_000.000_1"),
+        (TokenKind::Float, "1.5f64"),
         // Boolean literals
         (TokenKind::True, "true"),
         (TokenKind::False, "false"),
@@ -110,6 +194,71 @@ fn valid_tokens() {
     }
 }
 
+#[test]
+fn numeric_suffix_start_points_at_the_suffix() {
+    let tokens = tokenize("10i32").unwrap();
+    assert_eq!(tokens[0].suffix_start, Some(2));
+    let tokens = tokenize("1.5f64").unwrap();
+    assert_eq!(tokens[0].suffix_start, Some(3));
+    let tokens = tokenize("0xFF").unwrap();
+    assert_eq!(tokens[0].suffix_start, None);
+}
+
+#[test]
+fn numeric_digit_separator_errors() {
+    assert!(matches!(
+        tokenize("1_000_"),
+        Err(Error {
+            kind: ErrorKind::IntInvalid,
+            ..
+        })
+    ));
+    assert!(matches!(
+        tokenize("_1000"),
+        Ok(_)
+    ));
+    assert!(matches!(
+        tokenize("0x_FF"),
+        Err(Error {
+            kind: ErrorKind::IntInvalid,
+            ..
+        })
+    ));
+    assert!(matches!(
+        tokenize("0x"),
+        Err(Error {
+            kind: ErrorKind::IntInvalid,
+            ..
+        })
+    ));
+    assert!(matches!(
+        tokenize("0b"),
+        Err(Error {
+            kind: ErrorKind::IntInvalid,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn precedence_orders_operator_tiers() {
+    assert!(TokenKind::StarStar.precedence() > TokenKind::Star.precedence());
+    assert!(TokenKind::Star.precedence() > TokenKind::Plus.precedence());
+    assert!(TokenKind::Plus.precedence() > TokenKind::LtLt.precedence());
+    assert!(TokenKind::LtLt.precedence() > TokenKind::EqEq.precedence());
+    assert!(TokenKind::EqEq.precedence() > TokenKind::AndAnd.precedence());
+    assert!(TokenKind::AndAnd.precedence() > TokenKind::OrOr.precedence());
+    assert_eq!(TokenKind::Comma.precedence(), None);
+}
+
+#[test]
+fn assign_op_maps_compound_assignment_to_its_base_operator() {
+    assert_eq!(TokenKind::PlusEq.assign_op(), Some(TokenKind::Plus));
+    assert_eq!(TokenKind::StarStarEq.assign_op(), Some(TokenKind::StarStar));
+    assert_eq!(TokenKind::GtGtEq.assign_op(), Some(TokenKind::GtGt));
+    assert_eq!(TokenKind::Plus.assign_op(), None);
+}
+
 #[test]
 fn auto_insert_comma() {
     assert_eq!(
@@ -131,6 +280,18 @@ fn auto_insert_comma() {
     );
 }
 
+#[test]
+fn unicode_identifier_spans_cover_the_whole_multibyte_name() {
+    assert_eq!(
+        token_slices("café = 1"),
+        Ok(vec![
+            (TokenKind::Ident, "café"),
+            (TokenKind::Eq, "="),
+            (TokenKind::Int, "1"),
+        ])
+    );
+}
+
 #[test]
 fn string_unterminated() {
     assert!(matches!(
@@ -142,3 +303,239 @@ fn string_unterminated() {
         })
     ));
 }
+
+#[test]
+fn raw_strings_do_not_process_escapes() {
+    assert_eq!(
+        token_slices(r#"r"\z""#),
+        Ok(vec![(TokenKind::Str, r#"r"\z""#)])
+    );
+}
+
+#[test]
+fn raw_string_unterminated() {
+    assert!(matches!(
+        token_slices("r\""),
+        Err(Error {
+            kind: ErrorKind::StringUnterminated,
+            detail: None,
+            span: _,
+        })
+    ));
+    assert!(matches!(
+        token_slices(r##"r#"no closing hashes""##),
+        Err(Error {
+            kind: ErrorKind::StringUnterminated,
+            detail: None,
+            span: _,
+        })
+    ));
+}
+
+#[test]
+fn raw_identifier_allows_a_reserved_word_as_plain_ident() {
+    assert_eq!(
+        token_slices("r#let = 1"),
+        Ok(vec![
+            (TokenKind::Ident, "r#let"),
+            (TokenKind::Eq, "="),
+            (TokenKind::Int, "1"),
+        ])
+    );
+}
+
+#[test]
+fn string_escape_invalid() {
+    assert!(matches!(
+        token_slices(r#""\z""#),
+        Err(Error {
+            kind: ErrorKind::StringEscSeqInvalid,
+            ..
+        })
+    ));
+    assert!(matches!(
+        token_slices(r#""\xFF""#),
+        Err(Error {
+            kind: ErrorKind::StringEscSeqInvalid,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn string_escape_invalid_span_points_at_the_escape_not_the_whole_token() {
+    assert_eq!(
+        token_slices(r#"x = "ab\z""#).unwrap_err().span,
+        Span { start: 7, end: 9 }
+    );
+}
+
+#[test]
+fn byte_string_unterminated() {
+    assert!(matches!(
+        token_slices("b\""),
+        Err(Error {
+            kind: ErrorKind::StringUnterminated,
+            detail: None,
+            span: _,
+        })
+    ));
+}
+
+#[test]
+fn byte_string_rejects_raw_non_ascii_source_bytes() {
+    assert!(matches!(
+        token_slices("b\"café\""),
+        Err(Error {
+            kind: ErrorKind::StringEscSeqInvalid,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn byte_string_allows_hex_escapes_above_0x7f() {
+    assert_eq!(
+        token_slices(r#"b"\xFF""#),
+        Ok(vec![(TokenKind::Bytes, r#"b"\xFF""#)])
+    );
+}
+
+#[test]
+fn base64_invalid() {
+    assert!(matches!(
+        token_slices(r#"b64"SGVsbG8!""#),
+        Err(Error {
+            kind: ErrorKind::Base64Invalid,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn identifiers_starting_with_b_are_not_mistaken_for_byte_strings() {
+    assert_eq!(
+        token_slices("b b64 bytes"),
+        Ok(vec![
+            (TokenKind::Ident, "b"),
+            (TokenKind::Ident, "b64"),
+            (TokenKind::Ident, "bytes"),
+        ])
+    );
+}
+
+#[test]
+fn string_unicode_escape() {
+    assert_eq!(token_slices(r#""\u{41}""#), Ok(vec![(TokenKind::Str, r#""\u{41}""#)]));
+}
+
+#[test]
+fn tokenize_recover_collects_every_lexical_error() {
+    let (tokens, errors) = tokenize_recover("x = § + \"unterminated\n y = 1");
+    let kinds: Vec<_> = errors.iter().map(|error| error.kind.clone()).collect();
+    assert_eq!(
+        kinds,
+        vec![ErrorKind::InvalidChar, ErrorKind::StringUnterminated]
+    );
+    let token_kinds: Vec<_> = tokens.iter().map(|token| token.kind).collect();
+    assert_eq!(
+        token_kinds,
+        vec![
+            TokenKind::Ident,
+            TokenKind::Eq,
+            TokenKind::Plus,
+            TokenKind::Ident,
+            TokenKind::Eq,
+            TokenKind::Int,
+        ]
+    );
+}
+
+#[test]
+fn tokenize_recover_resyncs_past_a_run_of_invalid_bytes() {
+    let (tokens, errors) = tokenize_recover("x = §§§ y");
+    assert_eq!(errors.len(), 1);
+    let token_kinds: Vec<_> = tokens.iter().map(|token| token.kind).collect();
+    assert_eq!(
+        token_kinds,
+        vec![TokenKind::Ident, TokenKind::Eq, TokenKind::Ident]
+    );
+}
+
+#[test]
+fn tokenize_recover_reports_an_unterminated_block_comment_alongside_earlier_errors() {
+    let (tokens, errors) = tokenize_recover("x = § #{ never closed");
+    let kinds: Vec<_> = errors.iter().map(|error| error.kind.clone()).collect();
+    assert_eq!(
+        kinds,
+        vec![ErrorKind::InvalidChar, ErrorKind::CommentUnterminated]
+    );
+    let token_kinds: Vec<_> = tokens.iter().map(|token| token.kind).collect();
+    assert_eq!(token_kinds, vec![TokenKind::Ident, TokenKind::Eq]);
+}
+
+#[test]
+fn tokenize_is_a_thin_wrapper_returning_the_first_recovered_error() {
+    assert!(matches!(
+        tokenize("§ + \"unterminated\n"),
+        Err(Error {
+            kind: ErrorKind::InvalidChar,
+            ..
+        })
+    ));
+}
+
+fn with_trivia_slices(source: &str) -> Result<Vec<(TokenKind, &str)>> {
+    Tokens::with_trivia(source)
+        .map(|result| result.map(|Token { kind, span, .. }| (kind, &source[span])))
+        .collect()
+}
+
+#[test]
+fn with_trivia_emits_whitespace_and_comments_as_tokens() {
+    assert_eq!(
+        with_trivia_slices("x #{ a block }# y # a line comment\n"),
+        Ok(vec![
+            (TokenKind::Ident, "x"),
+            (TokenKind::Whitespace, " "),
+            (TokenKind::BlockComment, "#{ a block }#"),
+            (TokenKind::Whitespace, " "),
+            (TokenKind::Ident, "y"),
+            (TokenKind::Whitespace, " "),
+            (TokenKind::LineComment, "# a line comment"),
+            (TokenKind::Whitespace, "\n"),
+            (TokenKind::Comma, ""),
+        ])
+    );
+}
+
+#[test]
+fn with_trivia_reproduces_the_source_exactly() {
+    let source = "x\n\ny #{ nested #{ comment }# }# = 1 # trailing\n";
+    let tokens = Tokens::with_trivia(source).collect::<Result<Vec<_>>>().unwrap();
+    let rebuilt: String = tokens.iter().map(|token| &source[token.span]).collect();
+    assert_eq!(rebuilt, source);
+}
+
+#[test]
+fn with_trivia_still_flags_a_synthetic_auto_inserted_comma() {
+    let tokens = Tokens::with_trivia("x\ny").collect::<Result<Vec<_>>>().unwrap();
+    let commas: Vec<_> = tokens
+        .iter()
+        .filter(|token| token.kind == TokenKind::Comma)
+        .collect();
+    assert_eq!(commas.len(), 1);
+    assert!(commas[0].synthetic);
+    assert_eq!(&"x\ny"[commas[0].span], "");
+}
+
+#[test]
+fn with_trivia_reports_an_unterminated_block_comment() {
+    assert!(matches!(
+        with_trivia_slices("x #{ never closed"),
+        Err(Error {
+            kind: ErrorKind::CommentUnterminated,
+            ..
+        })
+    ));
+}
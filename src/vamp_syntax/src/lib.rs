@@ -1,9 +1,18 @@
 pub mod ast;
+pub mod const_fold;
+pub use const_fold::fold_constants;
 pub mod error;
-pub use error::Error;
+pub use error::{Diagnostic, Error};
 pub mod lexer;
-pub use lexer::tokenize;
+pub use lexer::{tokenize, tokenize_recover};
 pub mod parser;
-pub use parser::{parse_expr, parse_module, parse_stmt};
+pub use parser::{
+    parse_expr, parse_expr_with_max_depth, parse_module, parse_module_recover,
+    parse_module_recover_with_max_depth, parse_module_with_max_depth, parse_stmt,
+    parse_stmt_incremental, parse_stmt_incremental_with_max_depth, parse_stmt_with_max_depth,
+    ParseOutcome, DEFAULT_MAX_DEPTH,
+};
 pub mod span;
-pub use span::Span;
+pub use span::{Loc, SourceMap, Span};
+pub mod unescape;
+pub use unescape::{decode_base64, unescape, unescape_bytes};
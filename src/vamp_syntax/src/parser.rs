@@ -1,38 +1,130 @@
 use crate::{
     ast::{BinOp, Dep, Expr, ExprKind, Mod, ModPath, Pat, Stmt, UnOp},
-    error::{Error, ErrorKind, Result},
-    lexer::{tokenize, Token, TokenKind},
-    span::Span,
+    error::{Diagnostic, Error, ErrorKind, Result},
+    lexer::{tokenize, tokenize_recover, Token, TokenKind},
+    span::{SourceMap, Span},
+    unescape::{decode_base64, unescape, unescape_bytes},
 };
+use std::{cell::Cell, rc::Rc};
 use vamp_sym::{Interner, Sym};
 use vamp_tuple::{Tuple, TupleEntry};
 
 #[cfg(test)]
 mod tests;
 
+/// Default limit on how deeply tuples, lists, blocks, and expressions may
+/// nest, used by [`parse_expr`], [`parse_stmt`], and [`parse_module`]. Use
+/// the `_with_max_depth` variants to override it.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
 pub struct Parser<'src, 'sym> {
     source: &'src str,
     tokens: Vec<Token>,
     index: usize,
     interner: &'sym mut Interner,
+    /// How many nested-grammar productions (tuple, list, block, or
+    /// expression) are currently being parsed. Held behind an `Rc<Cell<_>>`
+    /// rather than a bare field so a [`DepthGuard`] can own a handle to it
+    /// independent of any borrow of the rest of `Parser`: a guard borrowing
+    /// `&mut Parser` for its whole lifetime would make every later
+    /// `&mut self` call in the same scope a double-borrow.
+    depth: Rc<Cell<usize>>,
+    max_depth: usize,
+    /// Every token kind an `accept`/`accept_*` call has rejected since the
+    /// index last advanced, used to build `invalid_token`'s "expected one
+    /// of ..." message. Cleared on a successful accept.
+    expected_tokens: Vec<TokenKind>,
+}
+
+/// Decrements a [`Parser`]'s depth counter when dropped, so it stays
+/// balanced even when parsing bails out of a nested production early via
+/// `?`. Owns its own `Rc` clone of the counter rather than borrowing
+/// `Parser`, so holding a guard across the calls a nested production
+/// makes to `&mut self` doesn't double-borrow.
+struct DepthGuard {
+    depth: Rc<Cell<usize>>,
+}
+
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        self.depth.set(self.depth.get() - 1);
+    }
+}
+
+/// A checkpoint of a [`Parser`]'s token cursor, taken by [`Parser::fork`]
+/// before a speculative parse. Must be resolved with
+/// [`Parser::advance_to`] or [`Parser::rewind`].
+#[must_use]
+struct Fork {
+    index: usize,
 }
 
 impl<'src, 'sym> Parser<'src, 'sym> {
-    fn new(source: &'src str, tokens: Vec<Token>, interner: &'sym mut Interner) -> Self {
+    fn with_max_depth(
+        source: &'src str,
+        tokens: Vec<Token>,
+        interner: &'sym mut Interner,
+        max_depth: usize,
+    ) -> Self {
         Self {
             source,
             tokens,
             index: 0,
             interner,
+            depth: Rc::new(Cell::new(0)),
+            max_depth,
+            expected_tokens: Vec::new(),
+        }
+    }
+
+    /// Enters a nested-grammar production, failing with
+    /// `ErrorKind::RecursionLimitExceeded` at `span` (the production's
+    /// opening delimiter or first token) rather than recursing past
+    /// `max_depth`. The returned guard must be held for the production's
+    /// whole parse, and decrements the counter again on drop.
+    fn enter_nested(&self, span: Span) -> Result<DepthGuard> {
+        if self.depth.get() >= self.max_depth {
+            return Err(Error {
+                kind: ErrorKind::RecursionLimitExceeded,
+                detail: None,
+                span,
+            });
         }
+        self.depth.set(self.depth.get() + 1);
+        Ok(DepthGuard { depth: Rc::clone(&self.depth) })
+    }
+
+    /// Snapshots the current token cursor so a caller can attempt one
+    /// grammar alternative and, if it turns out to be the wrong one,
+    /// rewind and try another — replacing the hand-rolled `let i =
+    /// self.index; ...; self.index = i;` pattern used elsewhere in this
+    /// parser with a named, harder-to-misuse API. Must be resolved with
+    /// either [`advance_to`](Self::advance_to) (keep whatever progress the
+    /// speculative parse made) or [`rewind`](Self::rewind) (undo it).
+    fn fork(&self) -> Fork {
+        Fork { index: self.index }
+    }
+
+    /// Commits a speculative parse: the cursor stays wherever it advanced
+    /// to, and `fork` is simply discarded.
+    fn advance_to(&mut self, fork: Fork) {
+        let _ = fork;
+    }
+
+    /// Abandons a speculative parse, rewinding the cursor back to where
+    /// `fork` was taken.
+    fn rewind(&mut self, fork: Fork) {
+        self.index = fork.index;
     }
 
     fn accept(&mut self, kind: TokenKind) -> Option<Span> {
         if self.index < self.tokens.len() && self.tokens[self.index].kind == kind {
             let span = self.tokens[self.index].span;
             self.index += 1;
+            self.expected_tokens.clear();
             Some(span)
         } else {
+            self.expected_tokens.push(kind);
             None
         }
     }
@@ -42,21 +134,63 @@ impl<'src, 'sym> Parser<'src, 'sym> {
     }
 
     fn accept_sym(&mut self, kind: TokenKind) -> Option<Sym> {
-        self.accept_slice(kind)
-            .map(|slice| self.interner.intern(slice.into()))
+        self.accept_sym_span(kind).map(|(sym, _)| sym)
     }
 
-    fn accept_un_op(&mut self) -> Option<(UnOp, u8)> {
+    /// Like `accept_sym`, but also returns the accepted token's span, for
+    /// callers that need to attach a real location to the `Expr` they
+    /// build around it.
+    fn accept_sym_span(&mut self, kind: TokenKind) -> Option<(Sym, Span)> {
+        let span = self.accept(kind)?;
+        Some((self.interner.intern(&self.source[span]), span))
+    }
+
+    /// The token kinds `accept_un_op` recognizes, in the same order as its
+    /// `match`, for reporting in `invalid_token`'s "expected" message.
+    const UN_OP_TOKENS: [TokenKind; 3] = [TokenKind::Minus, TokenKind::Not, TokenKind::Tilde];
+
+    /// The token kinds `accept_bin_op` recognizes, in the same order as its
+    /// `match`, for reporting in `invalid_token`'s "expected" message.
+    const BIN_OP_TOKENS: [TokenKind; 19] = [
+        TokenKind::OrOr,
+        TokenKind::AndAnd,
+        TokenKind::EqEq,
+        TokenKind::NotEq,
+        TokenKind::Lt,
+        TokenKind::LtEq,
+        TokenKind::Gt,
+        TokenKind::GtEq,
+        TokenKind::Or,
+        TokenKind::Caret,
+        TokenKind::And,
+        TokenKind::LtLt,
+        TokenKind::GtGt,
+        TokenKind::Plus,
+        TokenKind::Minus,
+        TokenKind::Star,
+        TokenKind::Slash,
+        TokenKind::Percent,
+        TokenKind::StarStar,
+    ];
+
+    fn accept_un_op(&mut self) -> Option<(UnOp, u8, Span)> {
         if self.index < self.tokens.len() {
-            let result = match self.tokens[self.index].kind {
+            let token = &self.tokens[self.index];
+            let (un_op, r_prec) = match token.kind {
                 TokenKind::Minus => (UnOp::Neg, 20),
                 TokenKind::Not => (UnOp::Not, 20),
                 TokenKind::Tilde => (UnOp::BitNot, 20),
-                _ => return None,
+                _ => {
+                    self.expected_tokens.extend(Self::UN_OP_TOKENS);
+                    return None;
+                }
             };
+            let span = token.span;
             self.index += 1;
-            Some(result)
+            self.expected_tokens.clear();
+            Some((un_op, r_prec, span))
         } else {
+            self.expected_tokens.extend(Self::UN_OP_TOKENS);
             None
         }
     }
@@ -83,25 +217,60 @@ impl<'src, 'sym> Parser<'src, 'sym> {
                 TokenKind::Slash => (BinOp::Div, 16, 17),
                 TokenKind::Percent => (BinOp::Mod, 16, 17),
                 TokenKind::StarStar => (BinOp::Exp, 18, 19),
-                TokenKind::Period => (BinOp::Dot, 20, 21),
-                _ => return None,
+                _ => {
+                    self.expected_tokens.extend(Self::BIN_OP_TOKENS);
+                    return None;
+                }
             };
             self.index += 1;
+            self.expected_tokens.clear();
             Some(result)
         } else {
+            self.expected_tokens.extend(Self::BIN_OP_TOKENS);
             None
         }
     }
 
-    fn invalid_token(&self) -> Error {
+    /// The span of the token the parser is currently sitting on, the last
+    /// token in the stream if it's been fully consumed, or a zero-width
+    /// span at the start of the source if there are no tokens at all.
+    fn current_span(&self) -> Span {
+        self.tokens
+            .get(self.index)
+            .or_else(|| self.index.checked_sub(1).and_then(|i| self.tokens.get(i)))
+            .map_or(Span::default(), |token| token.span)
+    }
+
+    fn invalid_token(&mut self) -> Error {
+        let span = self.current_span();
+        let found = (self.index < self.tokens.len()).then(|| &self.source[span]);
+        let detail = Some(expected_message(&self.expected_tokens, found));
+        self.expected_tokens.clear();
         Error {
-            kind: ErrorKind::InvalidToken,
+            kind: if found.is_none() {
+                ErrorKind::Incomplete
+            } else {
+                ErrorKind::InvalidToken
+            },
+            detail,
+            span,
+        }
+    }
+
+    /// Builds the error for a missing closing delimiter opened at
+    /// `open_span`: `ErrorKind::Incomplete` if the token stream ran out
+    /// before the closer appeared (a REPL should read another line), or
+    /// `ErrorKind::Delimiters` if some other token showed up instead.
+    fn unclosed_delimiter(&self, open_span: Span) -> Error {
+        let kind = if self.index >= self.tokens.len() {
+            ErrorKind::Incomplete
+        } else {
+            ErrorKind::Delimiters
+        };
+        Error {
+            kind,
             detail: None,
-            span: self
-                .tokens
-                .get(self.index)
-                .unwrap_or(&self.tokens[self.index - 1])
-                .span,
+            span: open_span,
         }
     }
 
@@ -113,95 +282,37 @@ impl<'src, 'sym> Parser<'src, 'sym> {
         self.accept_sym(TokenKind::CtxIdent)
     }
 
-    fn unescape(&mut self, span: Span) -> Result<String> {
-        let slice = &self.source[span];
-        let mut string = String::with_capacity(slice.len());
-        let mut chars = slice[1..slice.len() - 1].chars();
-        while let Some(c) = chars.next() {
-            if c == '\\' {
-                let invalid_escape_sequence = || Error {
-                    kind: ErrorKind::StringEscSeqInvalid,
-                    detail: None,
-                    span,
-                };
-                // `unwrap()` here is safe because a string ending `\` such
-                // as `"\"` would fail with `UnterminatedString`.
-                let c = chars.next().unwrap();
-                match c {
-                    '\\' => string.push('\\'),
-                    '"' => string.push('"'),
-                    '\'' => string.push('\''),
-                    // Bell
-                    'a' => string.push('\x07'),
-                    // Backspace
-                    'b' => string.push('\x08'),
-                    // Horizontal tab
-                    't' => string.push('\t'),
-                    // Vertical tab
-                    'v' => string.push('\x0B'),
-                    // Form feed
-                    'f' => string.push('\x0C'),
-                    // Newline
-                    'n' => {
-                        string.push('\n');
-                    }
-                    // Carriage return
-                    'r' => {
-                        string.push('\r');
-                    }
-                    // Nul
-                    '0' => {
-                        string.push('\0');
-                    }
-                    // Hexidecimal
-                    'x' => {
-                        let a = chars.next().ok_or_else(invalid_escape_sequence)?;
-                        let b = chars.next().ok_or_else(invalid_escape_sequence)?;
-                        let value =
-                            16 * match a {
-                                '0'..='9' => a as u8 - b'0',
-                                'a'..='f' => 10 + a as u8 - b'a',
-                                'A'..='F' => 10 + a as u8 - b'A',
-                                _ => return Err(invalid_escape_sequence()),
-                            } + match b {
-                                '0'..='9' => b as u8 - b'0',
-                                'a'..='f' => 10 + b as u8 - b'a',
-                                'A'..='F' => 10 + b as u8 - b'A',
-                                _ => return Err(invalid_escape_sequence()),
-                            };
-                        if value > 127 {
-                            return Err(invalid_escape_sequence());
-                        }
-                        string.push(value as char);
-                    }
-                    _ => return Err(invalid_escape_sequence()),
-                }
-            } else {
-                string.push(c)
-            }
+    fn symbol(&mut self) -> Result<Option<(Sym, Span)>> {
+        if let Some(span) = self.accept(TokenKind::Sym) {
+            let unescaped = unescape(&self.source[span], span.start)?;
+            Ok(Some((self.interner.intern(&unescaped), span)))
+        } else {
+            Ok(None)
         }
-        Ok(string)
     }
 
-    fn symbol(&mut self) -> Result<Option<Sym>> {
-        if let Some(span) = self.accept(TokenKind::Sym) {
-            let unescaped = self.unescape(span)?;
-            Ok(Some(self.interner.intern(&unescaped)))
+    fn string(&mut self) -> Result<Option<(String, Span)>> {
+        if let Some(span) = self.accept(TokenKind::Str) {
+            let unescaped = unescape(&self.source[span], span.start)?;
+            Ok(Some((unescaped, span)))
         } else {
             Ok(None)
         }
     }
 
-    fn string(&mut self) -> Result<Option<String>> {
-        if let Some(span) = self.accept(TokenKind::Str) {
-            let unescaped = self.unescape(span)?;
-            Ok(Some(unescaped))
+    fn bytes(&mut self) -> Result<Option<(Vec<u8>, Span)>> {
+        if let Some(span) = self.accept(TokenKind::Bytes) {
+            let decoded = unescape_bytes(&self.source[span.start + 1..span.end], span.start + 1)?;
+            Ok(Some((decoded, span)))
+        } else if let Some(span) = self.accept(TokenKind::Base64) {
+            let decoded = decode_base64(&self.source[span.start + 3..span.end], span.start + 3)?;
+            Ok(Some((decoded, span)))
         } else {
             Ok(None)
         }
     }
 
-    fn int(&mut self) -> Result<Option<i64>> {
+    fn int(&mut self) -> Result<Option<(i64, Span)>> {
         if let Some(int_span) = self.accept(TokenKind::Int) {
             let int_invalid = || Error {
                 kind: ErrorKind::IntInvalid,
@@ -213,7 +324,7 @@ impl<'src, 'sym> Parser<'src, 'sym> {
             if slice.starts_with("0b") {
                 // Binary literal
                 // TODO: Optimize to use bit twiddling.
-                for digit in slice[2..].bytes() {
+                for digit in slice[2..].bytes().filter(|&d| d != b'_') {
                     value = value
                         .checked_mul(2)
                         .ok_or_else(int_invalid)?
@@ -223,7 +334,7 @@ impl<'src, 'sym> Parser<'src, 'sym> {
             } else if slice.starts_with("0o") {
                 // Octal literal
                 // TODO: Optimize to use bit twiddling.
-                for digit in slice[2..].bytes() {
+                for digit in slice[2..].bytes().filter(|&d| d != b'_') {
                     value = value
                         .checked_mul(8)
                         .ok_or_else(int_invalid)?
@@ -233,7 +344,7 @@ impl<'src, 'sym> Parser<'src, 'sym> {
             } else if slice.starts_with("0x") {
                 // Hexadecimal literal
                 // TODO: Optimize to use bit twiddling.
-                for digit in slice[2..].bytes() {
+                for digit in slice[2..].bytes().filter(|&d| d != b'_') {
                     let n = if matches!(digit, b'0'..=b'9') {
                         (digit - b'0') as i64
                     } else if matches!(digit, b'a'..=b'f') {
@@ -249,7 +360,7 @@ impl<'src, 'sym> Parser<'src, 'sym> {
                 }
             } else {
                 // Decimal literal
-                for digit in slice.bytes() {
+                for digit in slice.bytes().filter(|&d| d != b'_') {
                     value = value
                         .checked_mul(10)
                         .ok_or_else(int_invalid)?
@@ -257,47 +368,62 @@ impl<'src, 'sym> Parser<'src, 'sym> {
                         .ok_or_else(int_invalid)?;
                 }
             }
-            Ok(Some(value))
+            Ok(Some((value, int_span)))
         } else {
             Ok(None)
         }
     }
 
-    fn float(&mut self) -> Result<Option<f64>> {
+    fn float(&mut self) -> Result<Option<(f64, Span)>> {
         if let Some(float_span) = self.accept(TokenKind::Float) {
             // TODO: Write custom float parser.
-            let value = self.source[float_span].parse::<f64>().map_err(|_| Error {
+            let slice = &self.source[float_span];
+            let float_invalid = || Error {
                 kind: ErrorKind::FloatInvalid,
                 detail: None,
                 span: float_span,
-            })?;
-            Ok(Some(value))
+            };
+            // `str::parse` doesn't strip digit separators the way the
+            // lexer's number scanner accepts them, so only allocate a
+            // cleaned copy when one is actually present.
+            let value = if slice.contains('_') {
+                slice
+                    .chars()
+                    .filter(|&c| c != '_')
+                    .collect::<String>()
+                    .parse::<f64>()
+            } else {
+                slice.parse::<f64>()
+            }
+            .map_err(|_| float_invalid())?;
+            Ok(Some((value, float_span)))
         } else {
             Ok(None)
         }
     }
 
-    fn bool(&mut self) -> Result<Option<bool>> {
-        if self.accept(TokenKind::True).is_some() {
-            Ok(Some(true))
-        } else if self.accept(TokenKind::False).is_some() {
-            Ok(Some(false))
+    fn bool(&mut self) -> Result<Option<(bool, Span)>> {
+        if let Some(span) = self.accept(TokenKind::True) {
+            Ok(Some((true, span)))
+        } else if let Some(span) = self.accept(TokenKind::False) {
+            Ok(Some((false, span)))
         } else {
             Ok(None)
         }
     }
 
     fn tuple_entry(&mut self) -> Result<Option<TupleEntry<Expr>>> {
-        let i = self.index;
-        if let Some(identifier) = self.ident() {
+        let fork = self.fork();
+        if let Some((identifier, span)) = self.accept_sym_span(TokenKind::Ident) {
             if self.accept(TokenKind::Colon).is_some() {
                 let expr = self
                     .expr()?
-                    .unwrap_or_else(|| Expr::unknown(ExprKind::Ident(identifier)));
+                    .unwrap_or_else(|| Expr::new(ExprKind::Ident(identifier), span));
+                self.advance_to(fork);
                 return Ok(Some(TupleEntry::Named(identifier, expr)));
             }
-            self.index = i;
         }
+        self.rewind(fork);
         if let Some(expr) = self.expr()? {
             Ok(Some(TupleEntry::Pos(expr)))
         } else {
@@ -305,9 +431,10 @@ impl<'src, 'sym> Parser<'src, 'sym> {
         }
     }
 
-    fn tuple(&mut self) -> Result<Option<Tuple<Expr>>> {
+    fn tuple(&mut self) -> Result<Option<(Span, Tuple<Expr>)>> {
         let i = self.index;
         if let Some(lparen_span) = self.accept(TokenKind::LParen) {
+            let _guard = self.enter_nested(lparen_span)?;
             let mut entries = vec![];
             if let Some(entry) = self.tuple_entry()? {
                 entries.push(entry);
@@ -317,20 +444,19 @@ impl<'src, 'sym> Parser<'src, 'sym> {
                     }
                 }
             }
-            self.accept(TokenKind::RParen).ok_or_else(|| Error {
-                kind: ErrorKind::Delimiters,
-                detail: None,
-                span: lparen_span,
-            })?;
-            Ok(Some(Tuple::from_iter(entries)))
+            let rparen_span = self
+                .accept(TokenKind::RParen)
+                .ok_or_else(|| self.unclosed_delimiter(lparen_span))?;
+            Ok(Some((lparen_span + rparen_span, Tuple::from_iter(entries))))
         } else {
             self.index = i;
             Ok(None)
         }
     }
 
-    fn list(&mut self) -> Result<Option<Box<[Expr]>>> {
+    fn list(&mut self) -> Result<Option<(Span, Box<[Expr]>)>> {
         if let Some(left_bracket_span) = self.accept(TokenKind::LBracket) {
+            let _guard = self.enter_nested(left_bracket_span)?;
             let mut exprs = vec![];
             if let Some(expr) = self.expr()? {
                 exprs.push(expr);
@@ -340,75 +466,125 @@ impl<'src, 'sym> Parser<'src, 'sym> {
                     }
                 }
             }
-            self.accept(TokenKind::RBracket).ok_or_else(|| Error {
-                kind: ErrorKind::Delimiters,
-                detail: None,
-                span: left_bracket_span,
-            })?;
-            Ok(Some(exprs.into()))
+            let right_bracket_span = self
+                .accept(TokenKind::RBracket)
+                .ok_or_else(|| self.unclosed_delimiter(left_bracket_span))?;
+            Ok(Some((left_bracket_span + right_bracket_span, exprs.into())))
         } else {
             Ok(None)
         }
     }
 
-    fn pat_tuple_entry(&mut self) -> Option<TupleEntry<Pat>> {
+    fn pat_tuple_entry(&mut self) -> Result<Option<TupleEntry<Pat>>> {
+        let i = self.index;
         if let Some(identifier) = self.ident() {
             if self.accept(TokenKind::Colon).is_some() {
-                let pattern = self.pat().unwrap_or_else(|| Pat::Ident(identifier));
-                Some(TupleEntry::Named(identifier, pattern))
-            } else {
-                Some(TupleEntry::Pos(Pat::Ident(identifier)))
+                let pattern = match self.pat()? {
+                    Some(pattern) => pattern,
+                    None => Pat::Ident(identifier),
+                };
+                return Ok(Some(TupleEntry::Named(identifier, pattern)));
             }
-        } else if let Some(pattern) = self.pat() {
-            Some(TupleEntry::Pos(pattern))
-        } else {
-            None
+            self.index = i;
+        }
+        match self.pat()? {
+            Some(pattern) => Ok(Some(TupleEntry::Pos(pattern))),
+            None => Ok(None),
         }
     }
 
-    fn pat_tuple(&mut self) -> Option<Tuple<Pat>> {
+    fn pat_tuple(&mut self) -> Result<Option<Tuple<Pat>>> {
         let i = self.index;
-        if self.accept(TokenKind::LParen).is_some() {
+        if let Some(lparen_span) = self.accept(TokenKind::LParen) {
+            let _guard = self.enter_nested(lparen_span)?;
             let mut entries = vec![];
-            if let Some(entry) = self.pat_tuple_entry() {
+            if let Some(entry) = self.pat_tuple_entry()? {
                 entries.push(entry);
                 while self.accept(TokenKind::Comma).is_some() {
-                    if let Some(entry) = self.pat_tuple_entry() {
+                    if let Some(entry) = self.pat_tuple_entry()? {
                         entries.push(entry);
                     }
                 }
             }
-            self.accept(TokenKind::RParen)?;
-            Some(Tuple::from_iter(entries))
+            if self.accept(TokenKind::RParen).is_none() {
+                self.index = i;
+                return Ok(None);
+            }
+            Ok(Some(Tuple::from_iter(entries)))
         } else {
             self.index = i;
-            None
+            Ok(None)
         }
     }
 
-    fn pat(&mut self) -> Option<Pat> {
-        if let Some(members) = self.pat_tuple() {
-            Some(Pat::Tuple(members))
+    fn pat_list(&mut self) -> Result<Option<Box<[Pat]>>> {
+        let i = self.index;
+        if let Some(lbracket_span) = self.accept(TokenKind::LBracket) {
+            let _guard = self.enter_nested(lbracket_span)?;
+            let mut items = vec![];
+            if let Some(item) = self.pat()? {
+                items.push(item);
+                while self.accept(TokenKind::Comma).is_some() {
+                    if let Some(item) = self.pat()? {
+                        items.push(item);
+                    }
+                }
+            }
+            if self.accept(TokenKind::RBracket).is_none() {
+                self.index = i;
+                return Ok(None);
+            }
+            Ok(Some(items.into()))
+        } else {
+            self.index = i;
+            Ok(None)
+        }
+    }
+
+    /// Parses a single pattern: a tuple `(a, b)`, a list `[a, b]`, a
+    /// literal (symbol, string, number, or bool) to match by equality, the
+    /// wildcard `_`, a plain identifier binding, or a context identifier
+    /// binding.
+    fn pat(&mut self) -> Result<Option<Pat>> {
+        if let Some(members) = self.pat_tuple()? {
+            Ok(Some(Pat::Tuple(members)))
+        } else if let Some(items) = self.pat_list()? {
+            Ok(Some(Pat::List(items)))
+        } else if let Some((sym, _)) = self.symbol()? {
+            Ok(Some(Pat::Sym(sym)))
+        } else if let Some((str, _)) = self.string()? {
+            Ok(Some(Pat::Str(str)))
+        } else if let Some((value, _)) = self.float()? {
+            Ok(Some(Pat::Float(value)))
+        } else if let Some((value, _)) = self.int()? {
+            Ok(Some(Pat::Int(value)))
+        } else if let Some((value, _)) = self.bool()? {
+            Ok(Some(Pat::Bool(value)))
         } else if let Some(ident) = self.ident() {
-            Some(Pat::Ident(ident))
+            if self.interner.lookup(ident) == "_" {
+                Ok(Some(Pat::Wild))
+            } else {
+                Ok(Some(Pat::Ident(ident)))
+            }
         } else if let Some(ctx_ident) = self.ctx_ident() {
-            Some(Pat::CtxIdent(ctx_ident))
+            Ok(Some(Pat::CtxIdent(ctx_ident)))
         } else {
-            None
+            Ok(None)
         }
     }
 
     fn stmt(&mut self) -> Result<Option<Stmt>> {
         if self.accept(TokenKind::Let).is_some() {
-            let pattern = self.pat().ok_or_else(|| self.invalid_token())?;
-            let args = self.pat_tuple();
+            let pattern = self.pat()?.ok_or_else(|| self.invalid_token())?;
+            let args = self.pat_tuple()?;
             self.accept(TokenKind::Eq)
                 .ok_or_else(|| self.invalid_token())?;
             let expr = self.expr()?.ok_or_else(|| self.invalid_token())?;
             if let Some(args) = args {
+                let span = expr.span;
                 Ok(Some(Stmt::Let(
                     pattern,
-                    Expr::unknown(ExprKind::Fn(args, expr.into())),
+                    Expr::new(ExprKind::Fn(args, expr.into()), span),
                 )))
             } else {
                 Ok(Some(Stmt::Let(pattern, expr.into())))
@@ -435,18 +611,18 @@ impl<'src, 'sym> Parser<'src, 'sym> {
 
     fn block(&mut self) -> Result<Option<Expr>> {
         if let Some(left_brace_span) = self.accept(TokenKind::LBrace) {
+            let _guard = self.enter_nested(left_brace_span)?;
             let statements = self.stmts()?;
-            self.accept(TokenKind::RBrace).ok_or_else(|| Error {
-                kind: ErrorKind::Delimiters,
-                detail: None,
-                span: left_brace_span,
-            })?;
+            let right_brace_span = self
+                .accept(TokenKind::RBrace)
+                .ok_or_else(|| self.unclosed_delimiter(left_brace_span))?;
+            let span = left_brace_span + right_brace_span;
             if statements.len() == 0 {
-                Ok(Some(Expr::unknown(ExprKind::Void)))
+                Ok(Some(Expr::new(ExprKind::Void, span)))
             } else if let [Stmt::Expr(expr)] = statements.as_ref() {
-                Ok(Some(expr.clone()))
+                Ok(Some(Expr::new(expr.kind.clone(), span)))
             } else {
-                Ok(Some(Expr::unknown(ExprKind::Block(statements))))
+                Ok(Some(Expr::new(ExprKind::Block(statements), span)))
             }
         } else {
             Ok(None)
@@ -454,63 +630,114 @@ impl<'src, 'sym> Parser<'src, 'sym> {
     }
 
     fn atom(&mut self) -> Result<Option<Expr>> {
-        if let Some(tuple) = self.tuple()? {
-            Ok(Some(Expr::unknown(ExprKind::Tuple(tuple))))
-        } else if let Some(list) = self.list()? {
-            Ok(Some(Expr::unknown(ExprKind::List(list))))
+        if let Some((span, tuple)) = self.tuple()? {
+            Ok(Some(Expr::new(ExprKind::Tuple(tuple), span)))
+        } else if let Some((span, list)) = self.list()? {
+            Ok(Some(Expr::new(ExprKind::List(list), span)))
         } else if let Some(block) = self.block()? {
             Ok(Some(block))
-        } else if let Some(ident) = self.ident() {
-            Ok(Some(Expr::unknown(ExprKind::Ident(ident))))
-        } else if let Some(ctx_ident) = self.ctx_ident() {
-            Ok(Some(Expr::unknown(ExprKind::CtxIdent(ctx_ident))))
-        } else if let Some(sym) = self.symbol()? {
-            Ok(Some(Expr::unknown(ExprKind::Sym(sym))))
-        } else if let Some(str) = self.string()? {
-            Ok(Some(Expr::unknown(ExprKind::Str(str))))
-        } else if let Some(int) = self.int()? {
-            Ok(Some(Expr::unknown(ExprKind::Int(int))))
-        } else if let Some(float) = self.float()? {
-            Ok(Some(Expr::unknown(ExprKind::Float(float))))
-        } else if let Some(bool) = self.bool()? {
-            Ok(Some(Expr::unknown(ExprKind::Bool(bool))))
+        } else if let Some(section) = self.operator_section()? {
+            Ok(Some(section))
+        } else if let Some((ident, span)) = self.accept_sym_span(TokenKind::Ident) {
+            Ok(Some(Expr::new(ExprKind::Ident(ident), span)))
+        } else if let Some((ctx_ident, span)) = self.accept_sym_span(TokenKind::CtxIdent) {
+            Ok(Some(Expr::new(ExprKind::CtxIdent(ctx_ident), span)))
+        } else if let Some((sym, span)) = self.symbol()? {
+            Ok(Some(Expr::new(ExprKind::Sym(sym), span)))
+        } else if let Some((str, span)) = self.string()? {
+            Ok(Some(Expr::new(ExprKind::Str(str), span)))
+        } else if let Some((bytes, span)) = self.bytes()? {
+            Ok(Some(Expr::new(ExprKind::Bytes(bytes), span)))
+        } else if let Some((int, span)) = self.int()? {
+            Ok(Some(Expr::new(ExprKind::Int(int), span)))
+        } else if let Some((float, span)) = self.float()? {
+            Ok(Some(Expr::new(ExprKind::Float(float), span)))
+        } else if let Some((bool, span)) = self.bool()? {
+            Ok(Some(Expr::new(ExprKind::Bool(bool), span)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parses an operator section, a backslash immediately followed by a
+    /// binary or unary operator token, desugaring it into the equivalent
+    /// function literal: `\+` becomes `|a, b| a + b`, `\-` becomes `|a| -a`.
+    /// Reuses `accept_un_op`/`accept_bin_op` so the set of valid sections
+    /// stays in sync with the operators those accept; a backslash not
+    /// followed by one of them (e.g. `\.` or `\(`) is an invalid token.
+    fn operator_section(&mut self) -> Result<Option<Expr>> {
+        if let Some(backslash_span) = self.accept(TokenKind::Backslash) {
+            if let Some((un_op, _, op_span)) = self.accept_un_op() {
+                let a = self.interner.private();
+                let span = backslash_span + op_span;
+                let operand = Expr::new(ExprKind::Ident(a), span);
+                let body = Expr::new(ExprKind::UnOp(un_op, operand.into()), span);
+                Ok(Some(Expr::new(
+                    ExprKind::Fn(Tuple::from_iter([TupleEntry::Pos(Pat::Ident(a))]), body.into()),
+                    span,
+                )))
+            } else {
+                let op_span = self.current_span();
+                if let Some((bin_op, _, _)) = self.accept_bin_op() {
+                    let a = self.interner.private();
+                    let b = self.interner.private();
+                    let span = backslash_span + op_span;
+                    let left = Expr::new(ExprKind::Ident(a), span);
+                    let right = Expr::new(ExprKind::Ident(b), span);
+                    let body = Expr::new(ExprKind::BinOp(bin_op, left.into(), right.into()), span);
+                    Ok(Some(Expr::new(
+                        ExprKind::Fn(
+                            Tuple::from_iter([
+                                TupleEntry::Pos(Pat::Ident(a)),
+                                TupleEntry::Pos(Pat::Ident(b)),
+                            ]),
+                            body.into(),
+                        ),
+                        span,
+                    )))
+                } else {
+                    Err(self.invalid_token())
+                }
+            }
         } else {
             Ok(None)
         }
     }
 
-    fn function_args(&mut self) -> Result<Option<Tuple<Pat>>> {
-        if self.accept(TokenKind::Or).is_some() {
+    fn function_args(&mut self) -> Result<Option<(Span, Tuple<Pat>)>> {
+        if let Some(open_span) = self.accept(TokenKind::Or) {
             let mut args = Vec::new();
-            if let Some(arg) = self.pat_tuple_entry() {
+            if let Some(arg) = self.pat_tuple_entry()? {
                 args.push(arg);
                 while self.accept(TokenKind::Comma).is_some() {
-                    if let Some(arg) = self.pat_tuple_entry() {
+                    if let Some(arg) = self.pat_tuple_entry()? {
                         args.push(arg);
                     }
                 }
             }
-            self.accept(TokenKind::Or)
+            let close_span = self
+                .accept(TokenKind::Or)
                 .ok_or_else(|| self.invalid_token())?;
-            Ok(Some(Tuple::from_iter(args)))
+            Ok(Some((open_span + close_span, Tuple::from_iter(args))))
         } else {
             Ok(None)
         }
     }
 
     fn function(&mut self) -> Result<Option<Expr>> {
-        if let Some(args) = self.function_args()? {
+        if let Some((args_span, args)) = self.function_args()? {
             let expr = self
                 .expr()?
                 .unwrap_or_else(|| Expr::unknown(ExprKind::Void));
-            Ok(Some(Expr::unknown(ExprKind::Fn(args, expr.into()))))
+            let span = args_span + expr.span;
+            Ok(Some(Expr::new(ExprKind::Fn(args, expr.into()), span)))
         } else {
             Ok(None)
         }
     }
 
     fn if_else(&mut self) -> Result<Option<Expr>> {
-        if self.accept(TokenKind::If).is_some() {
+        if let Some(if_span) = self.accept(TokenKind::If) {
             let condition = self.expr()?.ok_or_else(|| self.invalid_token())?;
             let if_expr = self.block()?.ok_or_else(|| self.invalid_token())?;
             self.accept(TokenKind::Else)
@@ -522,36 +749,134 @@ impl<'src, 'sym> Parser<'src, 'sym> {
             } else {
                 return Err(self.invalid_token());
             };
-            Ok(Some(Expr::unknown(ExprKind::IfElse(
-                condition.into(),
-                if_expr.into(),
-                else_expr.into(),
-            ))))
+            let span = if_span + else_expr.span;
+            Ok(Some(Expr::new(
+                ExprKind::IfElse(condition.into(), if_expr.into(), else_expr.into()),
+                span,
+            )))
         } else {
             Ok(None)
         }
     }
 
     fn for_loop(&mut self) -> Result<Option<Expr>> {
-        if self.accept(TokenKind::For).is_some() {
-            todo!()
+        if let Some(for_span) = self.accept(TokenKind::For) {
+            let pat = self.pat()?.ok_or_else(|| self.invalid_token())?;
+            self.accept(TokenKind::In)
+                .ok_or_else(|| self.invalid_token())?;
+            let iter = self.expr()?.ok_or_else(|| self.invalid_token())?;
+            let guard = if self.accept(TokenKind::If).is_some() {
+                Some(self.expr()?.ok_or_else(|| self.invalid_token())?.into())
+            } else {
+                None
+            };
+            let body = self.block()?.ok_or_else(|| self.invalid_token())?;
+            let (else_body, span) = if self.accept(TokenKind::Else).is_some() {
+                let else_body = self.block()?.ok_or_else(|| self.invalid_token())?;
+                let span = for_span + else_body.span;
+                (Some(else_body.into()), span)
+            } else {
+                (None, for_span + body.span)
+            };
+            Ok(Some(Expr::new(
+                ExprKind::For {
+                    pat,
+                    iter: iter.into(),
+                    guard,
+                    body: body.into(),
+                    else_body,
+                },
+                span,
+            )))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parses a single `match` arm: `<pat> [if <cond>] => <expr>`. The
+    /// body is parsed with `expr()`, which already covers a `{...}` block
+    /// as an atom, so either form works on the right of `=>`.
+    fn match_arm(&mut self) -> Result<Option<(Pat, Option<Expr>, Expr)>> {
+        if let Some(pat) = self.pat()? {
+            let guard = if self.accept(TokenKind::If).is_some() {
+                Some(self.expr()?.ok_or_else(|| self.invalid_token())?)
+            } else {
+                None
+            };
+            self.accept(TokenKind::FatArrow)
+                .ok_or_else(|| self.invalid_token())?;
+            let body = self.expr()?.ok_or_else(|| self.invalid_token())?;
+            Ok(Some((pat, guard, body)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn match_expr(&mut self) -> Result<Option<Expr>> {
+        if let Some(match_span) = self.accept(TokenKind::Match) {
+            let scrutinee = self.expr()?.ok_or_else(|| self.invalid_token())?;
+            let left_brace_span = self
+                .accept(TokenKind::LBrace)
+                .ok_or_else(|| self.invalid_token())?;
+            let _guard = self.enter_nested(left_brace_span)?;
+            let mut arms = vec![];
+            if let Some(arm) = self.match_arm()? {
+                arms.push(arm);
+                while self.accept(TokenKind::Comma).is_some() {
+                    if let Some(arm) = self.match_arm()? {
+                        arms.push(arm);
+                    }
+                }
+            }
+            let right_brace_span = self
+                .accept(TokenKind::RBrace)
+                .ok_or_else(|| self.unclosed_delimiter(left_brace_span))?;
+            let span = match_span + right_brace_span;
+            Ok(Some(Expr::new(
+                ExprKind::Match(scrutinee.into(), arms.into()),
+                span,
+            )))
         } else {
             Ok(None)
         }
     }
 
     fn expr_with_precedence(&mut self, min_prec: u8) -> Result<Option<Expr>> {
+        let span = self.current_span();
+        let _guard = self.enter_nested(span)?;
+
         // Handle unary operators.
-        let left = if let Some((un_op, r_prec)) = self.accept_un_op() {
+        let left = if let Some((un_op, r_prec, op_span)) = self.accept_un_op() {
             if let Some(right) = self.expr_with_precedence(r_prec)? {
-                Some(Expr::unknown(ExprKind::UnOp(un_op, right.into())))
+                let span = op_span + right.span;
+                Some(Expr::new(ExprKind::UnOp(un_op, right.into()), span))
             } else {
                 return Err(self.invalid_token());
             }
         } else if let Some(mut left) = self.atom()? {
-            // Handle function calls.
-            while let Some(tuple) = self.tuple()? {
-                left = Expr::unknown(ExprKind::Call(left.into(), tuple));
+            // Handle postfix calls, field access, and index access,
+            // chaining left-to-right, e.g. `f(x).name[0]`.
+            loop {
+                if let Some((tuple_span, tuple)) = self.tuple()? {
+                    let span = left.span + tuple_span;
+                    left = Expr::new(ExprKind::Call(left.into(), tuple), span);
+                } else if self.accept(TokenKind::Period).is_some() {
+                    let (field, field_span) = self
+                        .accept_sym_span(TokenKind::Ident)
+                        .ok_or_else(|| self.invalid_token())?;
+                    let span = left.span + field_span;
+                    left = Expr::new(ExprKind::Field(left.into(), field), span);
+                } else if let Some(left_bracket_span) = self.accept(TokenKind::LBracket) {
+                    let _guard = self.enter_nested(left_bracket_span)?;
+                    let index = self.expr()?.ok_or_else(|| self.invalid_token())?;
+                    let right_bracket_span = self
+                        .accept(TokenKind::RBracket)
+                        .ok_or_else(|| self.unclosed_delimiter(left_bracket_span))?;
+                    let span = left.span + right_bracket_span;
+                    left = Expr::new(ExprKind::Index(left.into(), index.into()), span);
+                } else {
+                    break;
+                }
             }
             Some(left)
         } else {
@@ -567,7 +892,8 @@ impl<'src, 'sym> Parser<'src, 'sym> {
                         break;
                     }
                     if let Some(right) = self.expr_with_precedence(r_prec)? {
-                        left = Expr::unknown(ExprKind::BinOp(bin_op, left.into(), right.into()));
+                        let span = left.span + right.span;
+                        left = Expr::new(ExprKind::BinOp(bin_op, left.into(), right.into()), span);
                     } else {
                         return Err(self.invalid_token());
                     }
@@ -589,6 +915,8 @@ impl<'src, 'sym> Parser<'src, 'sym> {
             Ok(Some(expr))
         } else if let Some(expr) = self.for_loop()? {
             Ok(Some(expr))
+        } else if let Some(expr) = self.match_expr()? {
+            Ok(Some(expr))
         } else if let Some(expr) = self.expr_with_precedence(0)? {
             Ok(Some(expr))
         } else {
@@ -618,30 +946,82 @@ impl<'src, 'sym> Parser<'src, 'sym> {
         }
     }
 
-    fn bindings(&mut self) -> Result<Option<Box<[(Sym, Sym)]>>> {
-        if self.accept(TokenKind::LParen).is_some() {
-            let mut bindings = vec![];
-            if let Some(binding) = self.accept_sym(TokenKind::Ident) {
-                // TODO: Allow renames.
-                bindings.push((binding, binding));
-                while self.accept(TokenKind::Comma).is_some() {
-                    if let Some(binding) = self.accept_sym(TokenKind::Ident) {
-                        bindings.push((binding, binding));
-                    }
-                }
+    /// Parses a single import binding, e.g. `w` or a rename `w as v`.
+    fn binding(&mut self) -> Result<Option<(Sym, Sym)>> {
+        if let Some(source) = self.accept_sym(TokenKind::Ident) {
+            if self.accept(TokenKind::As).is_some() {
+                let destination = self
+                    .accept_sym(TokenKind::Ident)
+                    .ok_or_else(|| self.invalid_token())?;
+                Ok(Some((source, destination)))
+            } else {
+                Ok(Some((source, source)))
             }
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parses the leaf of an import: either a glob `(*)` or a parenthesized
+    /// binding list `(a, b as c)`.
+    fn dep_leaf(&mut self, path: ModPath) -> Result<Dep> {
+        self.accept(TokenKind::LParen)
+            .ok_or_else(|| self.invalid_token())?;
+        if self.accept(TokenKind::Star).is_some() {
             self.accept(TokenKind::RParen)
                 .ok_or_else(|| self.invalid_token())?;
-            Ok(Some(bindings.into()))
+            return Ok(Dep::Glob(path));
+        }
+        let mut bindings = vec![];
+        if let Some(binding) = self.binding()? {
+            bindings.push(binding);
+            while self.accept(TokenKind::Comma).is_some() {
+                if let Some(binding) = self.binding()? {
+                    bindings.push(binding);
+                }
+            }
+        }
+        self.accept(TokenKind::RParen)
+            .ok_or_else(|| self.invalid_token())?;
+        Ok(Dep::Named {
+            path,
+            bindings: bindings.into(),
+        })
+    }
+
+    /// Parses what follows a module path: either a leaf (glob or binding
+    /// list), or a brace group of nested sub-imports that extend `path`
+    /// with one more segment each, e.g. `x.y { z (a), w (b, c) }`.
+    fn dep_tail(&mut self, path: ModPath) -> Result<Vec<Dep>> {
+        if self.accept(TokenKind::LBrace).is_some() {
+            let mut deps = vec![];
+            loop {
+                let segment = match self.accept_sym(TokenKind::Ident) {
+                    Some(segment) => segment,
+                    None => break,
+                };
+                let mut segments = path.segments.to_vec();
+                segments.push(segment);
+                let nested_path = ModPath {
+                    local: path.local,
+                    segments: segments.into(),
+                };
+                deps.extend(self.dep_tail(nested_path)?);
+                if self.accept(TokenKind::Comma).is_none() {
+                    break;
+                }
+            }
+            self.accept(TokenKind::RBrace)
+                .ok_or_else(|| self.invalid_token())?;
+            Ok(deps)
         } else {
-            Ok(None)
+            Ok(vec![self.dep_leaf(path)?])
         }
     }
 
-    fn dep(&mut self) -> Result<Option<Dep>> {
+    fn dep(&mut self) -> Result<Option<Vec<Dep>>> {
         if let Some(path) = self.module_path()? {
-            let bindings = self.bindings()?.ok_or_else(|| self.invalid_token())?;
-            Ok(Some(Dep { path, bindings }))
+            Ok(Some(self.dep_tail(path)?))
         } else {
             Ok(None)
         }
@@ -653,10 +1033,10 @@ impl<'src, 'sym> Parser<'src, 'sym> {
             self.accept(TokenKind::LBrace)
                 .ok_or_else(|| self.invalid_token())?;
             if let Some(dep) = self.dep()? {
-                deps.push(dep);
+                deps.extend(dep);
                 while self.accept(TokenKind::Comma).is_some() {
                     if let Some(dep) = self.dep()? {
-                        deps.push(dep);
+                        deps.extend(dep);
                     }
                 }
             }
@@ -682,22 +1062,263 @@ impl<'src, 'sym> Parser<'src, 'sym> {
         let defs = self.defs()?;
         Ok(Mod { deps, defs })
     }
+
+    /// After a statement fails to parse, skips tokens until a safe point to
+    /// resume: a `Comma` (the real or automatic-comma-insertion statement
+    /// separator) seen with no delimiter open, or the end of input.
+    /// Delimiter depth is tracked, via the same open/close token kinds as
+    /// tuples/lists/blocks, so a `Comma` inside one doesn't end recovery
+    /// early; an unmatched closing delimiter left over from inside the
+    /// failed statement (its opener having already been consumed before
+    /// the error) is itself just skipped rather than mistaken for the end
+    /// of an enclosing construct, since `defs_recover` only ever runs at
+    /// the unbraced module top level. Doesn't consume the `Comma` recovery
+    /// stops on, so the caller's usual `accept(Comma)` still sees it.
+    fn synchronize(&mut self) {
+        let mut depth = 0usize;
+        loop {
+            match self.tokens.get(self.index).map(|token| token.kind) {
+                None => return,
+                Some(TokenKind::LParen | TokenKind::LBracket | TokenKind::LBrace) => {
+                    depth += 1;
+                    self.index += 1;
+                }
+                Some(TokenKind::RParen | TokenKind::RBracket | TokenKind::RBrace) => {
+                    depth = depth.saturating_sub(1);
+                    self.index += 1;
+                }
+                Some(TokenKind::Comma) if depth == 0 => return,
+                Some(_) => self.index += 1,
+            }
+        }
+    }
+
+    /// Like [`defs`](Self::defs), but a statement that fails to parse is
+    /// recorded as a [`Diagnostic`] and skipped via [`synchronize`](
+    /// Self::synchronize) rather than aborting the whole module, so one bad
+    /// definition doesn't hide the rest of the file.
+    fn defs_recover(&mut self, map: &SourceMap) -> (Box<[Stmt]>, Vec<Diagnostic>) {
+        let mut statements = vec![];
+        let mut diagnostics = vec![];
+        loop {
+            match self.stmt() {
+                Ok(Some(Stmt::Expr(expr))) => diagnostics.push(Diagnostic::new(
+                    Error {
+                        kind: ErrorKind::NoUnboundExprAtModuleLevel,
+                        detail: None,
+                        span: expr.span,
+                    },
+                    map,
+                )),
+                Ok(Some(statement)) => statements.push(statement),
+                Ok(None) => break,
+                Err(error) => {
+                    diagnostics.push(Diagnostic::new(error, map));
+                    self.synchronize();
+                }
+            }
+            if self.accept(TokenKind::Comma).is_none() {
+                break;
+            }
+        }
+        (statements.into(), diagnostics)
+    }
+}
+
+/// Builds an `invalid_token` message like "expected one of `)`, `,`, or an
+/// operator, found `..`" from the set of token kinds that were tried and
+/// rejected at the failing position, deduplicated by their `describe()`
+/// text, and what was actually found there.
+fn expected_message(expected: &[TokenKind], found: Option<&str>) -> String {
+    let mut descriptions = Vec::new();
+    for kind in expected {
+        let description = kind.describe();
+        if !descriptions.contains(&description) {
+            descriptions.push(description);
+        }
+    }
+    let expected = match descriptions.as_slice() {
+        [] => "expected a different token".to_string(),
+        [only] => format!("expected {only}"),
+        [init @ .., last] => format!("expected one of {}, or {last}", init.join(", ")),
+    };
+    match found {
+        Some(found) => format!("{expected}, found `{found}`"),
+        None => format!("{expected}, found end of input"),
+    }
 }
 
 pub fn parse_expr(source: &str, interner: &mut Interner) -> Result<Expr> {
-    let expr = Parser::new(source, tokenize(source)?, interner)
-        .expr()?
-        .unwrap_or(Expr::unknown(ExprKind::Void));
+    parse_expr_with_max_depth(source, interner, DEFAULT_MAX_DEPTH)
+}
+
+/// Like [`parse_expr`], but overrides the nesting-depth limit on tuples,
+/// lists, blocks, and expressions instead of using [`DEFAULT_MAX_DEPTH`].
+pub fn parse_expr_with_max_depth(
+    source: &str,
+    interner: &mut Interner,
+    max_depth: usize,
+) -> Result<Expr> {
+    let mut parser = Parser::with_max_depth(source, tokenize(source)?, interner, max_depth);
+    let expr = parser.expr()?.unwrap_or(Expr::unknown(ExprKind::Void));
+    assert_eq!(parser.depth.get(), 0, "parser depth counter did not return to zero");
     Ok(expr)
 }
 
 pub fn parse_stmt(source: &str, interner: &mut Interner) -> Result<Stmt> {
-    let stmt = Parser::new(source, tokenize(source)?, interner)
+    parse_stmt_with_max_depth(source, interner, DEFAULT_MAX_DEPTH)
+}
+
+/// Like [`parse_stmt`], but overrides the nesting-depth limit on tuples,
+/// lists, blocks, and expressions instead of using [`DEFAULT_MAX_DEPTH`].
+pub fn parse_stmt_with_max_depth(
+    source: &str,
+    interner: &mut Interner,
+    max_depth: usize,
+) -> Result<Stmt> {
+    let mut parser = Parser::with_max_depth(source, tokenize(source)?, interner, max_depth);
+    let stmt = parser
         .stmt()?
         .unwrap_or(Stmt::Expr(Expr::unknown(ExprKind::Void)));
+    assert_eq!(parser.depth.get(), 0, "parser depth counter did not return to zero");
     Ok(stmt)
 }
 
+/// The result of [`parse_stmt_incremental`]: a REPL reads another line and
+/// re-feeds the accumulated buffer on `Incomplete` instead of reporting an
+/// error, the way it would for `Error`.
+#[derive(Debug, PartialEq)]
+pub enum ParseOutcome {
+    Complete(Stmt),
+    /// The input ran out before a construct it was inside of closed, e.g.
+    /// an open `(`/`[`/`{` or an unterminated string/block comment spanning
+    /// what the user meant as multiple lines. `open_delims` lists every
+    /// bracket still open at the point input ran out, outermost first, for
+    /// a continuation prompt like `...(['`.
+    Incomplete { open_delims: Vec<TokenKind> },
+    Error(Diagnostic),
+}
+
+/// Whether `kind` means parsing stopped only because it ran out of input,
+/// rather than hitting a token or construct that's actually wrong.
+fn is_incomplete(kind: &ErrorKind) -> bool {
+    matches!(
+        kind,
+        ErrorKind::Incomplete | ErrorKind::StringUnterminated | ErrorKind::CommentUnterminated
+    )
+}
+
+/// The stack of `(`/`[`/`{` tokens in `tokens` that are still unmatched by
+/// the time the stream ends, outermost first.
+fn open_delims(tokens: &[Token]) -> Vec<TokenKind> {
+    let mut stack = Vec::new();
+    for token in tokens {
+        match token.kind {
+            TokenKind::LParen | TokenKind::LBracket | TokenKind::LBrace => stack.push(token.kind),
+            TokenKind::RParen | TokenKind::RBracket | TokenKind::RBrace => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+    stack
+}
+
+/// Parses one statement the way a REPL would type it in: tells apart a
+/// complete statement, an error worth reporting right away, and input
+/// that's merely incomplete so far, in which case the REPL should read
+/// another line and call this again with the two buffers concatenated.
+pub fn parse_stmt_incremental(source: &str, interner: &mut Interner) -> ParseOutcome {
+    parse_stmt_incremental_with_max_depth(source, interner, DEFAULT_MAX_DEPTH)
+}
+
+/// Like [`parse_stmt_incremental`], but overrides the nesting-depth limit
+/// on tuples, lists, blocks, and expressions instead of using
+/// [`DEFAULT_MAX_DEPTH`].
+pub fn parse_stmt_incremental_with_max_depth(
+    source: &str,
+    interner: &mut Interner,
+    max_depth: usize,
+) -> ParseOutcome {
+    let map = SourceMap::new(source);
+    let (tokens, mut lex_errors) = tokenize_recover(source);
+    if let Some(error) = lex_errors.drain(..).next() {
+        return if is_incomplete(&error.kind) {
+            ParseOutcome::Incomplete {
+                open_delims: open_delims(&tokens),
+            }
+        } else {
+            ParseOutcome::Error(Diagnostic::new(error, &map))
+        };
+    }
+    let delims = open_delims(&tokens);
+    let mut parser = Parser::with_max_depth(source, tokens, interner, max_depth);
+    match parser.stmt() {
+        Ok(Some(stmt)) => ParseOutcome::Complete(stmt),
+        Ok(None) => ParseOutcome::Complete(Stmt::Expr(Expr::unknown(ExprKind::Void))),
+        Err(error) if is_incomplete(&error.kind) => ParseOutcome::Incomplete {
+            open_delims: delims,
+        },
+        Err(error) => ParseOutcome::Error(Diagnostic::new(error, &map)),
+    }
+}
+
 pub fn parse_module(source: &str, interner: &mut Interner) -> Result<Mod> {
-    Parser::new(source, tokenize(source)?, interner).module()
+    parse_module_with_max_depth(source, interner, DEFAULT_MAX_DEPTH)
+}
+
+/// Like [`parse_module`], but overrides the nesting-depth limit on tuples,
+/// lists, blocks, and expressions instead of using [`DEFAULT_MAX_DEPTH`].
+pub fn parse_module_with_max_depth(
+    source: &str,
+    interner: &mut Interner,
+    max_depth: usize,
+) -> Result<Mod> {
+    let mut parser = Parser::with_max_depth(source, tokenize(source)?, interner, max_depth);
+    let module = parser.module()?;
+    assert_eq!(parser.depth.get(), 0, "parser depth counter did not return to zero");
+    Ok(module)
+}
+
+/// Like [`parse_module`], but a syntax error inside a definition doesn't
+/// abort the whole parse: it's recorded as a [`Diagnostic`] and parsing
+/// resumes at the next top-level definition, so the returned `Mod` still
+/// contains every definition that parsed successfully. A syntax error in
+/// the `use { ... }` header (or a tokenizer error over the whole source)
+/// still yields a `Mod` with no deps/defs, since there's no safe point to
+/// resynchronize within a header whose shape isn't known yet.
+pub fn parse_module_recover(source: &str, interner: &mut Interner) -> (Mod, Vec<Diagnostic>) {
+    parse_module_recover_with_max_depth(source, interner, DEFAULT_MAX_DEPTH)
+}
+
+/// Like [`parse_module_recover`], but overrides the nesting-depth limit on
+/// tuples, lists, blocks, and expressions instead of using
+/// [`DEFAULT_MAX_DEPTH`].
+pub fn parse_module_recover_with_max_depth(
+    source: &str,
+    interner: &mut Interner,
+    max_depth: usize,
+) -> (Mod, Vec<Diagnostic>) {
+    let map = SourceMap::new(source);
+    let empty = Mod {
+        deps: [].into(),
+        defs: [].into(),
+    };
+    let tokens = match tokenize(source) {
+        Ok(tokens) => tokens,
+        Err(error) => return (empty, vec![Diagnostic::new(error, &map)]),
+    };
+    let mut parser = Parser::with_max_depth(source, tokens, interner, max_depth);
+    let mut diagnostics = vec![];
+    let deps = match parser.deps() {
+        Ok(deps) => deps,
+        Err(error) => {
+            diagnostics.push(Diagnostic::new(error, &map));
+            return (empty, diagnostics);
+        }
+    };
+    parser.accept(TokenKind::Comma);
+    let (defs, def_diagnostics) = parser.defs_recover(&map);
+    diagnostics.extend(def_diagnostics);
+    (Mod { deps, defs }, diagnostics)
 }
@@ -107,6 +107,61 @@ fn test_str_esc_seq_invalid() {
     );
 }
 
+#[test]
+fn test_bytes() {
+    let mut interner = Interner::new();
+    assert_eq!(
+        parse_expr(r#"b"""#, &mut interner),
+        Ok(Expr::unknown(ExprKind::Bytes(vec![])))
+    );
+    assert_eq!(
+        parse_expr(r#"b"abc""#, &mut interner),
+        Ok(Expr::unknown(ExprKind::Bytes(b"abc".to_vec())))
+    );
+    // Unlike a `Str`, `\xNN` yields a raw byte above `0x7F`.
+    assert_eq!(
+        parse_expr(r#"b"\xFF""#, &mut interner),
+        Ok(Expr::unknown(ExprKind::Bytes(vec![0xFF])))
+    );
+}
+
+#[test]
+fn test_bytes_rejects_raw_non_ascii_source_bytes() {
+    let mut interner = Interner::new();
+    assert_eq!(
+        parse_expr("b\"café\"", &mut interner).unwrap_err().kind,
+        ErrorKind::StringEscSeqInvalid
+    );
+}
+
+#[test]
+fn test_base64() {
+    let mut interner = Interner::new();
+    assert_eq!(
+        parse_expr(r#"b64"SGVsbG8=""#, &mut interner),
+        Ok(Expr::unknown(ExprKind::Bytes(b"Hello".to_vec())))
+    );
+    assert_eq!(
+        parse_expr(r#"b64"""#, &mut interner),
+        Ok(Expr::unknown(ExprKind::Bytes(vec![])))
+    );
+}
+
+#[test]
+fn test_base64_invalid() {
+    let mut interner = Interner::new();
+    assert_eq!(
+        parse_expr(r#"b64"SGVsbG8!""#, &mut interner)
+            .unwrap_err()
+            .kind,
+        ErrorKind::Base64Invalid
+    );
+    assert_eq!(
+        parse_expr(r#"b64"A===""#, &mut interner).unwrap_err().kind,
+        ErrorKind::Base64Invalid
+    );
+}
+
 #[test]
 fn test_ints() {
     let mut interner = Interner::new();
@@ -126,6 +181,26 @@ fn test_ints() {
         parse_expr("0o747", &mut interner),
         Ok(Expr::unknown(ExprKind::Int(0o747)))
     );
+    assert_eq!(
+        parse_expr("0xFF", &mut interner),
+        Ok(Expr::unknown(ExprKind::Int(0xFF)))
+    );
+    assert_eq!(
+        parse_expr("0b1010", &mut interner),
+        Ok(Expr::unknown(ExprKind::Int(0b1010)))
+    );
+    assert_eq!(
+        parse_expr("1_000_000", &mut interner),
+        Ok(Expr::unknown(ExprKind::Int(1_000_000)))
+    );
+    assert_eq!(
+        parse_expr("0xFF_FF", &mut interner),
+        Ok(Expr::unknown(ExprKind::Int(0xFFFF)))
+    );
+    assert_eq!(
+        parse_expr("0b1010_0101", &mut interner),
+        Ok(Expr::unknown(ExprKind::Int(0b1010_0101)))
+    );
     assert_eq!(
         parse_expr("9223372036854775807", &mut interner),
         Ok(Expr::unknown(ExprKind::Int(9223372036854775807)))
@@ -136,6 +211,20 @@ fn test_ints() {
             .kind,
         ErrorKind::IntInvalid
     );
+    assert_eq!(
+        parse_expr("0xFFFFFFFFFFFFFFFF", &mut interner)
+            .unwrap_err()
+            .kind,
+        ErrorKind::IntInvalid
+    );
+    assert_eq!(
+        parse_expr("0x", &mut interner).unwrap_err().kind,
+        ErrorKind::IntInvalid
+    );
+    assert_eq!(
+        parse_expr("1_", &mut interner).unwrap_err().kind,
+        ErrorKind::IntInvalid
+    );
 }
 
 #[test]
@@ -153,6 +242,18 @@ fn test_floats() {
         parse_expr("3.141592", &mut interner),
         Ok(Expr::unknown(ExprKind::Float(3.141592)))
     );
+    assert_eq!(
+        parse_expr("6.022e23", &mut interner),
+        Ok(Expr::unknown(ExprKind::Float(6.022e23)))
+    );
+    assert_eq!(
+        parse_expr("1e-9", &mut interner),
+        Ok(Expr::unknown(ExprKind::Float(1e-9)))
+    );
+    assert_eq!(
+        parse_expr("1_000.000_1", &mut interner),
+        Ok(Expr::unknown(ExprKind::Float(1_000.000_1)))
+    );
 }
 
 #[test]
@@ -210,6 +311,20 @@ fn test_tuples() {
     );
 }
 
+#[test]
+fn test_tuple_positional_identifier_is_not_mistaken_for_a_named_entry() {
+    let mut interner = Interner::new();
+    let x = interner.intern("x");
+    let y = interner.intern("y");
+    assert_eq!(
+        parse_expr("(x, y)", &mut interner),
+        Ok(Expr::unknown(ExprKind::Tuple(Tuple::from_iter([
+            TupleEntry::Pos(Expr::unknown(ExprKind::Ident(x))),
+            TupleEntry::Pos(Expr::unknown(ExprKind::Ident(y))),
+        ]))))
+    );
+}
+
 #[test]
 fn test_lists() {
     let mut interner = Interner::new();
@@ -322,6 +437,39 @@ fn test_prec() {
             .into()
         ))),
     );
+    let a = interner.intern("a");
+    let b = interner.intern("b");
+    assert_eq!(
+        parse_expr("a.x + b.y", &mut interner),
+        Ok(Expr::unknown(ExprKind::BinOp(
+            BinOp::Add,
+            Expr::unknown(ExprKind::Field(
+                Expr::unknown(ExprKind::Ident(a)).into(),
+                x,
+            ))
+            .into(),
+            Expr::unknown(ExprKind::Field(
+                Expr::unknown(ExprKind::Ident(b)).into(),
+                y,
+            ))
+            .into()
+        ))),
+    );
+    assert_eq!(
+        parse_expr("f(x).y[0]", &mut interner),
+        Ok(Expr::unknown(ExprKind::Index(
+            Expr::unknown(ExprKind::Field(
+                Expr::unknown(ExprKind::Call(
+                    Expr::unknown(ExprKind::Ident(f)).into(),
+                    Tuple::from_iter([TupleEntry::Pos(Expr::unknown(ExprKind::Ident(x)))]),
+                ))
+                .into(),
+                y,
+            ))
+            .into(),
+            Expr::unknown(ExprKind::Int(0)).into(),
+        ))),
+    );
 }
 
 #[test]
@@ -357,6 +505,93 @@ fn test_functions() {
     )
 }
 
+#[test]
+fn test_operator_sections() {
+    let mut interner = Interner::new();
+    assert_eq!(
+        parse_expr(r"\+", &mut interner),
+        Ok(Expr::unknown(ExprKind::Fn(
+            Tuple::from_iter([
+                TupleEntry::Pos(Pat::Ident(Sym(0))),
+                TupleEntry::Pos(Pat::Ident(Sym(1))),
+            ]),
+            Expr::unknown(ExprKind::BinOp(
+                BinOp::Add,
+                Expr::unknown(ExprKind::Ident(Sym(0))).into(),
+                Expr::unknown(ExprKind::Ident(Sym(1))).into(),
+            ))
+            .into()
+        )))
+    );
+    let mut interner = Interner::new();
+    assert_eq!(
+        parse_expr(r"\<", &mut interner),
+        Ok(Expr::unknown(ExprKind::Fn(
+            Tuple::from_iter([
+                TupleEntry::Pos(Pat::Ident(Sym(0))),
+                TupleEntry::Pos(Pat::Ident(Sym(1))),
+            ]),
+            Expr::unknown(ExprKind::BinOp(
+                BinOp::Lt,
+                Expr::unknown(ExprKind::Ident(Sym(0))).into(),
+                Expr::unknown(ExprKind::Ident(Sym(1))).into(),
+            ))
+            .into()
+        )))
+    );
+    // `\-` is the unary case, not two-argument subtraction.
+    let mut interner = Interner::new();
+    assert_eq!(
+        parse_expr(r"\-", &mut interner),
+        Ok(Expr::unknown(ExprKind::Fn(
+            Tuple::from_iter([TupleEntry::Pos(Pat::Ident(Sym(0)))]),
+            Expr::unknown(ExprKind::UnOp(
+                UnOp::Neg,
+                Expr::unknown(ExprKind::Ident(Sym(0))).into(),
+            ))
+            .into()
+        )))
+    );
+    // Usable directly as a call argument, e.g. passed to a higher-order
+    // function like `reduce`.
+    let mut interner = Interner::new();
+    let xs = interner.intern("xs");
+    assert_eq!(
+        parse_expr(r"reduce(xs, \+)", &mut interner),
+        Ok(Expr::unknown(ExprKind::Call(
+            Expr::unknown(ExprKind::Ident(interner.intern("reduce"))).into(),
+            Tuple::from_iter([
+                TupleEntry::Pos(Expr::unknown(ExprKind::Ident(xs))),
+                TupleEntry::Pos(Expr::unknown(ExprKind::Fn(
+                    Tuple::from_iter([
+                        TupleEntry::Pos(Pat::Ident(Sym(2))),
+                        TupleEntry::Pos(Pat::Ident(Sym(3))),
+                    ]),
+                    Expr::unknown(ExprKind::BinOp(
+                        BinOp::Add,
+                        Expr::unknown(ExprKind::Ident(Sym(2))).into(),
+                        Expr::unknown(ExprKind::Ident(Sym(3))).into(),
+                    ))
+                    .into()
+                ))),
+            ])
+        )))
+    );
+}
+
+#[test]
+fn test_operator_section_rejects_dot_and_calls() {
+    let mut interner = Interner::new();
+    assert_eq!(
+        parse_expr(r"\.", &mut interner).unwrap_err().kind,
+        ErrorKind::InvalidToken
+    );
+    assert_eq!(
+        parse_expr(r"\(", &mut interner).unwrap_err().kind,
+        ErrorKind::InvalidToken
+    );
+}
+
 #[test]
 fn test_blocks() {
     let mut interner = Interner::new();
@@ -440,7 +675,238 @@ fn test_if_else() {
 }
 
 #[test]
-fn test_for() {}
+fn test_for() {
+    let mut interner = Interner::new();
+    let x = interner.intern("x");
+    let xs = interner.intern("xs");
+    assert_eq!(
+        parse_expr("for x in xs { x }", &mut interner),
+        Ok(Expr::unknown(ExprKind::For {
+            pat: Pat::Ident(x),
+            iter: Expr::unknown(ExprKind::Ident(xs)).into(),
+            guard: None,
+            body: Expr::unknown(ExprKind::Ident(x)).into(),
+            else_body: None,
+        }))
+    );
+    assert_eq!(
+        parse_expr("for x in xs if x > 0 { x } else { 0 }", &mut interner),
+        Ok(Expr::unknown(ExprKind::For {
+            pat: Pat::Ident(x),
+            iter: Expr::unknown(ExprKind::Ident(xs)).into(),
+            guard: Some(
+                Expr::unknown(ExprKind::BinOp(
+                    BinOp::Gt,
+                    Expr::unknown(ExprKind::Ident(x)).into(),
+                    Expr::unknown(ExprKind::Int(0)).into(),
+                ))
+                .into()
+            ),
+            body: Expr::unknown(ExprKind::Ident(x)).into(),
+            else_body: Some(Expr::unknown(ExprKind::Int(0)).into()),
+        }))
+    );
+}
+
+#[test]
+fn test_match() {
+    let mut interner = Interner::new();
+    let x = interner.intern("x");
+    let a = interner.intern("a");
+    let b = interner.intern("b");
+    let y = interner.intern("y");
+    assert_eq!(
+        parse_expr(
+            "match x { (a, b) if a > b => a, y => y }",
+            &mut interner
+        ),
+        Ok(Expr::unknown(ExprKind::Match(
+            Expr::unknown(ExprKind::Ident(x)).into(),
+            Box::new([
+                (
+                    Pat::Tuple(Tuple::from_iter([
+                        TupleEntry::Pos(Pat::Ident(a)),
+                        TupleEntry::Pos(Pat::Ident(b)),
+                    ])),
+                    Some(Expr::unknown(ExprKind::BinOp(
+                        BinOp::Gt,
+                        Expr::unknown(ExprKind::Ident(a)).into(),
+                        Expr::unknown(ExprKind::Ident(b)).into(),
+                    ))),
+                    Expr::unknown(ExprKind::Ident(a)),
+                ),
+                (Pat::Ident(y), None, Expr::unknown(ExprKind::Ident(y))),
+            ]),
+        )))
+    );
+}
+
+#[test]
+fn test_match_list_literal_and_wildcard_patterns() {
+    let mut interner = Interner::new();
+    let x = interner.intern("x");
+    let a = interner.intern("a");
+    assert_eq!(
+        parse_expr(
+            "match x { [a, 0] => a, \"hi\" => x, true => x, _ => x }",
+            &mut interner
+        ),
+        Ok(Expr::unknown(ExprKind::Match(
+            Expr::unknown(ExprKind::Ident(x)).into(),
+            Box::new([
+                (
+                    Pat::List(Box::new([Pat::Ident(a), Pat::Int(0)])),
+                    None,
+                    Expr::unknown(ExprKind::Ident(a)),
+                ),
+                (
+                    Pat::Str("hi".into()),
+                    None,
+                    Expr::unknown(ExprKind::Ident(x)),
+                ),
+                (Pat::Bool(true), None, Expr::unknown(ExprKind::Ident(x))),
+                (Pat::Wild, None, Expr::unknown(ExprKind::Ident(x))),
+            ]),
+        )))
+    );
+}
+
+#[test]
+fn test_invalid_token_expected_message() {
+    let mut interner = Interner::new();
+    let error = parse_expr("for x 5", &mut interner).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::InvalidToken);
+    assert_eq!(error.detail, Some("expected `in`, found `5`".to_string()));
+}
+
+#[test]
+fn test_invalid_token_expected_message_lists_alternatives() {
+    let mut interner = Interner::new();
+    let error = parse_stmt("let x =", &mut interner).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::Incomplete);
+    let detail = error.detail.unwrap();
+    assert!(
+        detail.starts_with("expected one of "),
+        "unexpected detail: {detail}"
+    );
+    assert!(
+        detail.ends_with(", found end of input"),
+        "unexpected detail: {detail}"
+    );
+}
+
+#[test]
+fn test_incomplete_vs_invalid_token() {
+    let mut interner = Interner::new();
+    // Ran out of tokens before a closing delimiter: incomplete, so a REPL
+    // knows to read another line.
+    assert_eq!(
+        parse_expr("(1, 2", &mut interner).unwrap_err().kind,
+        ErrorKind::Incomplete
+    );
+    assert_eq!(
+        parse_expr("[1, 2", &mut interner).unwrap_err().kind,
+        ErrorKind::Incomplete
+    );
+    assert_eq!(
+        parse_expr("{ 1", &mut interner).unwrap_err().kind,
+        ErrorKind::Incomplete
+    );
+    assert_eq!(
+        parse_expr(r"|x, y", &mut interner).unwrap_err().kind,
+        ErrorKind::Incomplete
+    );
+    // A wrong token in the closing position is a hard syntax error, not
+    // more input to read.
+    assert_eq!(
+        parse_expr("(1, 2]", &mut interner).unwrap_err().kind,
+        ErrorKind::Delimiters
+    );
+}
+
+#[test]
+fn spans_cover_an_atom_s_own_token() {
+    let mut interner = Interner::new();
+    let expr = parse_expr("  42  ", &mut interner).unwrap();
+    assert_eq!(expr.span, Span { start: 2, end: 4 });
+}
+
+#[test]
+fn spans_merge_binary_operands() {
+    let mut interner = Interner::new();
+    let expr = parse_expr("x + 1", &mut interner).unwrap();
+    assert_eq!(expr.span, Span { start: 0, end: 5 });
+    if let ExprKind::BinOp(_, left, right) = expr.kind {
+        assert_eq!(left.span, Span { start: 0, end: 1 });
+        assert_eq!(right.span, Span { start: 4, end: 5 });
+    } else {
+        panic!("expected a BinOp");
+    }
+}
+
+#[test]
+fn spans_merge_a_call_s_callee_and_closing_paren() {
+    let mut interner = Interner::new();
+    let expr = parse_expr("f(1)", &mut interner).unwrap();
+    assert_eq!(expr.span, Span { start: 0, end: 4 });
+}
+
+#[test]
+fn spans_cover_a_unary_operator_and_its_operand() {
+    let mut interner = Interner::new();
+    let expr = parse_expr("-x", &mut interner).unwrap();
+    assert_eq!(expr.span, Span { start: 0, end: 2 });
+}
+
+#[test]
+fn spans_cover_an_if_else_from_if_to_the_final_branch() {
+    let mut interner = Interner::new();
+    let expr = parse_expr("if x { 1 } else { 2 }", &mut interner).unwrap();
+    assert_eq!(expr.span, Span { start: 0, end: 21 });
+}
+
+#[test]
+fn spans_cover_a_tuple_and_a_list_including_their_delimiters() {
+    let mut interner = Interner::new();
+    let tuple = parse_expr("(1, 2)", &mut interner).unwrap();
+    assert_eq!(tuple.span, Span { start: 0, end: 6 });
+    let list = parse_expr("[1, 2]", &mut interner).unwrap();
+    assert_eq!(list.span, Span { start: 0, end: 6 });
+}
+
+#[test]
+fn unknown_exprs_still_compare_equal_to_parsed_exprs_with_real_spans() {
+    let mut interner = Interner::new();
+    let x = interner.intern("x");
+    assert_eq!(
+        parse_expr("x", &mut interner),
+        Ok(Expr::unknown(ExprKind::Ident(x)))
+    );
+}
+
+#[test]
+fn recursion_limit_is_exceeded_by_pathological_nesting() {
+    let mut interner = Interner::new();
+    let source = "(".repeat(200) + &")".repeat(200);
+    let err = parse_expr_with_max_depth(&source, &mut interner, 128).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::RecursionLimitExceeded);
+}
+
+#[test]
+fn recursion_limit_is_configurable() {
+    let mut interner = Interner::new();
+    let source = "(".repeat(10) + &")".repeat(10);
+    assert!(parse_expr_with_max_depth(&source, &mut interner, 4).is_err());
+    assert!(parse_expr_with_max_depth(&source, &mut interner, 128).is_ok());
+}
+
+#[test]
+fn recursion_limit_is_exceeded_by_pathological_nesting_in_a_pattern() {
+    let mut interner = Interner::new();
+    let source = "let ".to_string() + &"(".repeat(200) + &")".repeat(200) + " = 1";
+    let err = parse_stmt_with_max_depth(&source, &mut interner, 128).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::RecursionLimitExceeded);
+}
 
 #[test]
 fn test_modules() {
@@ -461,7 +927,7 @@ fn test_modules() {
             &mut interner
         ),
         Ok(Mod {
-            deps: [Dep {
+            deps: [Dep::Named {
                 path: ModPath {
                     local: false,
                     segments: [x, y, z].into(),
@@ -473,3 +939,217 @@ fn test_modules() {
         })
     );
 }
+
+#[test]
+fn test_module_renamed_binding() {
+    let mut interner = Interner::new();
+    let x = interner.intern("x");
+    let y = interner.intern("y");
+    let z = interner.intern("z");
+    let w = interner.intern("w");
+    let v = interner.intern("v");
+    assert_eq!(
+        parse_module("use { x.y.z (w as v) }", &mut interner),
+        Ok(Mod {
+            deps: [Dep::Named {
+                path: ModPath {
+                    local: false,
+                    segments: [x, y, z].into(),
+                },
+                bindings: [(w, v)].into(),
+            }]
+            .into(),
+            defs: [].into(),
+        })
+    );
+}
+
+#[test]
+fn test_module_glob() {
+    let mut interner = Interner::new();
+    let x = interner.intern("x");
+    let y = interner.intern("y");
+    let z = interner.intern("z");
+    assert_eq!(
+        parse_module("use { x.y.z (*) }", &mut interner),
+        Ok(Mod {
+            deps: [Dep::Glob(ModPath {
+                local: false,
+                segments: [x, y, z].into(),
+            })]
+            .into(),
+            defs: [].into(),
+        })
+    );
+}
+
+#[test]
+fn test_module_nested_group() {
+    let mut interner = Interner::new();
+    let x = interner.intern("x");
+    let y = interner.intern("y");
+    let z = interner.intern("z");
+    let w = interner.intern("w");
+    let a = interner.intern("a");
+    let b = interner.intern("b");
+    let c = interner.intern("c");
+    assert_eq!(
+        parse_module("use { x.y { z (a), w (b, c) } }", &mut interner),
+        Ok(Mod {
+            deps: [
+                Dep::Named {
+                    path: ModPath {
+                        local: false,
+                        segments: [x, y, z].into(),
+                    },
+                    bindings: [(a, a)].into(),
+                },
+                Dep::Named {
+                    path: ModPath {
+                        local: false,
+                        segments: [x, y, w].into(),
+                    },
+                    bindings: [(b, b), (c, c)].into(),
+                },
+            ]
+            .into(),
+            defs: [].into(),
+        })
+    );
+}
+
+#[test]
+fn test_module_relative_import() {
+    let mut interner = Interner::new();
+    let sibling = interner.intern("sibling");
+    let thing = interner.intern("thing");
+    assert_eq!(
+        parse_module("use { .sibling (thing) }", &mut interner),
+        Ok(Mod {
+            deps: [Dep::Named {
+                path: ModPath {
+                    local: true,
+                    segments: [sibling].into(),
+                },
+                bindings: [(thing, thing)].into(),
+            }]
+            .into(),
+            defs: [].into(),
+        })
+    );
+}
+
+#[test]
+fn test_module_glob_cannot_be_mixed_with_named_bindings() {
+    let mut interner = Interner::new();
+    assert!(parse_module("use { x.y.z (*, w) }", &mut interner).is_err());
+    assert!(parse_module("use { x.y.z (w, *) }", &mut interner).is_err());
+}
+
+fn let_binding_name(stmt: &Stmt) -> Sym {
+    match stmt {
+        Stmt::Let(Pat::Ident(sym), _) => *sym,
+        other => panic!("expected a let-binding, got {other:?}"),
+    }
+}
+
+#[test]
+fn recover_keeps_every_definition_around_a_syntax_error() {
+    let mut interner = Interner::new();
+    let (module, diagnostics) =
+        parse_module_recover("let a = 1\nlet b = )\nlet c = 3", &mut interner);
+    assert_eq!(diagnostics.len(), 1);
+    let a = interner.intern("a");
+    let c = interner.intern("c");
+    assert_eq!(
+        module.defs.iter().map(let_binding_name).collect::<Vec<_>>(),
+        vec![a, c]
+    );
+}
+
+#[test]
+fn recover_reports_one_diagnostic_per_bad_definition() {
+    let mut interner = Interner::new();
+    let (module, diagnostics) =
+        parse_module_recover("let a = )\nlet b = )\nlet c = 3", &mut interner);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(module.defs.len(), 1);
+}
+
+#[test]
+fn recover_resynchronizes_past_an_error_inside_a_nested_block() {
+    let mut interner = Interner::new();
+    let (module, diagnostics) =
+        parse_module_recover("let a = { let x = ) }\nlet b = 2", &mut interner);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(module.defs.len(), 1);
+}
+
+#[test]
+fn recover_reports_the_diagnostics_line_and_column() {
+    let mut interner = Interner::new();
+    let (_, diagnostics) = parse_module_recover("let a = 1\nlet b = )", &mut interner);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].start.line, 2);
+    assert_eq!(diagnostics[0].start.column, 9);
+}
+
+#[test]
+fn recover_succeeds_on_a_module_with_no_errors() {
+    let mut interner = Interner::new();
+    let (module, diagnostics) = parse_module_recover("let a = 1", &mut interner);
+    assert_eq!(diagnostics.len(), 0);
+    assert_eq!(module.defs.len(), 1);
+}
+
+#[test]
+fn incremental_parses_a_complete_statement() {
+    let mut interner = Interner::new();
+    let x = interner.intern("x");
+    assert_eq!(
+        parse_stmt_incremental("let x = 1", &mut interner),
+        ParseOutcome::Complete(Stmt::Let(Pat::Ident(x), Expr::unknown(ExprKind::Int(1))))
+    );
+}
+
+#[test]
+fn incremental_reports_an_open_paren_as_incomplete() {
+    let mut interner = Interner::new();
+    assert_eq!(
+        parse_stmt_incremental("let x = (1 + ", &mut interner),
+        ParseOutcome::Incomplete {
+            open_delims: vec![TokenKind::LParen],
+        }
+    );
+}
+
+#[test]
+fn incremental_reports_an_unterminated_string_as_incomplete() {
+    let mut interner = Interner::new();
+    assert_eq!(
+        parse_stmt_incremental(r#"let x = "unterminated"#, &mut interner),
+        ParseOutcome::Incomplete {
+            open_delims: vec![],
+        }
+    );
+}
+
+#[test]
+fn incremental_reports_an_invalid_token_as_an_error() {
+    let mut interner = Interner::new();
+    assert!(matches!(
+        parse_stmt_incremental("let x = )", &mut interner),
+        ParseOutcome::Error(_)
+    ));
+}
+
+#[test]
+fn incremental_tracks_nested_open_delimiters_outermost_first() {
+    let mut interner = Interner::new();
+    assert_eq!(
+        parse_stmt_incremental("let x = (1, [2, ", &mut interner),
+        ParseOutcome::Incomplete {
+            open_delims: vec![TokenKind::LParen, TokenKind::LBracket],
+        }
+    );
+}
@@ -1,7 +1,10 @@
-use std::ops::Index;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, Index};
 
 /// A span of characters in source code.
 #[derive(Debug, Default, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Span {
     /// The inclusive start byte-offset in the source code.
     pub start: usize,
@@ -9,6 +12,29 @@ pub struct Span {
     pub end: usize,
 }
 
+impl Span {
+    /// Slices `input` to the substring this span covers.
+    pub fn of<'a>(&self, input: &'a str) -> &'a str {
+        &input[self.start..self.end]
+    }
+}
+
+impl Add for Span {
+    type Output = Span;
+
+    /// Merges two spans into one covering both, e.g. a call expression's
+    /// callee merged with its closing paren, or a binary expression's left
+    /// operand merged with its right. `self` must start no later than
+    /// `rhs` ends.
+    fn add(self, rhs: Span) -> Span {
+        assert!(self.start <= rhs.end);
+        Span {
+            start: self.start,
+            end: rhs.end,
+        }
+    }
+}
+
 impl Index<Span> for str {
     type Output = str;
 
@@ -18,14 +44,162 @@ impl Index<Span> for str {
     }
 }
 
+/// A one-based line and column, where column counts Unicode scalar values
+/// rather than bytes.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Loc {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Resolves byte offsets in a source string to [`Loc`]s.
+///
+/// Built once per source string by scanning for `\n`, a `SourceMap` lets
+/// diagnostics turn a [`Span`]'s raw offsets into a human-readable
+/// "line N, column M" without re-scanning the source on every lookup.
+#[derive(Debug, Clone)]
+pub struct SourceMap<'a> {
+    source: &'a str,
+    /// Byte offset of the start of each line, in ascending order.
+    line_starts: Vec<usize>,
+}
+
+impl<'a> SourceMap<'a> {
+    /// Scans `source` once for line breaks and builds a map from byte
+    /// offset to line/column.
+    pub fn new(source: &'a str) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(source.match_indices('\n').map(|(i, _)| i + 1));
+        SourceMap { source, line_starts }
+    }
+
+    /// Finds the line and column containing byte offset `offset`.
+    ///
+    /// The column is the number of Unicode scalar values between the
+    /// start of the line and `offset`, not the number of bytes.
+    pub fn locate(&self, offset: usize) -> Loc {
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(i) => i,
+            Err(i) => i - 1,
+        };
+        let line_start = self.line_starts[line];
+        let column = self.source[line_start..offset].chars().count();
+        Loc {
+            line: line + 1,
+            column: column + 1,
+        }
+    }
+
+    /// Resolves both ends of `span` to their [`Loc`]s.
+    pub fn span_lines(&self, span: Span) -> (Loc, Loc) {
+        (self.locate(span.start), self.locate(span.end))
+    }
+
+    /// Renders the source line containing `loc` with a `^` caret under the
+    /// offending column, for use in diagnostic output.
+    pub fn render_line(&self, loc: Loc) -> String {
+        let line_start = self.line_starts[loc.line - 1];
+        let line_end = self.source[line_start..]
+            .find('\n')
+            .map_or(self.source.len(), |i| line_start + i);
+        let line = &self.source[line_start..line_end];
+        let caret = " ".repeat(loc.column - 1) + "^";
+        format!("{line}\n{caret}")
+    }
+
+    /// Renders the source line containing the start of `span`, underlined
+    /// with a run of `^` as wide as `span`, for use in diagnostic output.
+    /// A span that continues past the end of its first line is underlined
+    /// only to the end of that line.
+    pub fn render_span(&self, span: Span) -> String {
+        let start = self.locate(span.start);
+        let line_start = self.line_starts[start.line - 1];
+        let line_end = self.source[line_start..]
+            .find('\n')
+            .map_or(self.source.len(), |i| line_start + i);
+        let line = &self.source[line_start..line_end];
+        let width = self.source[span.start..span.end.min(line_end)]
+            .chars()
+            .count()
+            .max(1);
+        let caret = " ".repeat(start.column - 1) + &"^".repeat(width);
+        format!("{line}\n{caret}")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn of_slices_the_covered_substring() {
+        let source = "Slice me up.";
+        let span = Span { start: 6, end: 8 };
+        assert_eq!(span.of(source), "me");
+    }
+
+    #[test]
+    fn add_merges_two_spans_left_to_right() {
+        let left = Span { start: 0, end: 4 };
+        let right = Span { start: 10, end: 14 };
+        assert_eq!(left + right, Span { start: 0, end: 14 });
+    }
+
+    #[test]
+    #[should_panic]
+    fn add_rejects_a_span_that_ends_before_the_left_span_starts() {
+        let left = Span { start: 10, end: 14 };
+        let right = Span { start: 0, end: 4 };
+        let _ = left + right;
+    }
+
     #[test]
     fn index() {
         let slice = "Slice me up.";
         let span = Span { start: 6, end: 8 };
         assert_eq!(&slice[span], "me");
     }
+
+    #[test]
+    fn locate_single_line() {
+        let map = SourceMap::new("let x = 1");
+        assert_eq!(map.locate(4), Loc { line: 1, column: 5 });
+    }
+
+    #[test]
+    fn locate_multi_line() {
+        let source = "let x = 1\nlet y = 2\nlet z = 3";
+        let map = SourceMap::new(source);
+        assert_eq!(map.locate(0), Loc { line: 1, column: 1 });
+        assert_eq!(map.locate(10), Loc { line: 2, column: 1 });
+        assert_eq!(map.locate(24), Loc { line: 3, column: 5 });
+    }
+
+    #[test]
+    fn locate_counts_unicode_scalars_not_bytes() {
+        let source = "let émoji = \"🎉\"";
+        let map = SourceMap::new(source);
+        // "émoji" starts after "let ", and é is 2 bytes but 1 scalar value.
+        let byte_offset = source.find("émoji").unwrap();
+        assert_eq!(map.locate(byte_offset), Loc { line: 1, column: 5 });
+    }
+
+    #[test]
+    fn span_lines_and_render() {
+        let source = "let x = 1\nlet y = bad";
+        let map = SourceMap::new(source);
+        let span = Span { start: 18, end: 21 };
+        let (start, end) = map.span_lines(span);
+        assert_eq!(start, Loc { line: 2, column: 9 });
+        assert_eq!(end, Loc { line: 2, column: 12 });
+        assert_eq!(map.render_line(start), "let y = bad\n        ^");
+    }
+
+    #[test]
+    fn render_span_underlines_the_full_width() {
+        let source = "let x = 1\nlet y = bad";
+        let map = SourceMap::new(source);
+        let span = Span { start: 18, end: 21 };
+        assert_eq!(map.render_span(span), "let y = bad\n        ^^^");
+    }
 }
@@ -0,0 +1,414 @@
+//! Decodes the escape sequences inside a `Str`/`Sym` literal.
+//!
+//! Factored out (in the spirit of rustc_lexer's `unescape` module) so both
+//! the lexer and the parser can share one implementation: the lexer only
+//! needs to validate escapes and report precise per-escape diagnostics,
+//! while the parser also wants the fully decoded `String`.
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::span::Span;
+
+/// Decodes the escape sequences in `slice`, a literal's full source text
+/// including its surrounding quote (or apostrophe) characters. `offset` is
+/// `slice`'s start position in the original source, used to compute a
+/// precise error span for an individual invalid escape rather than the
+/// whole literal.
+///
+/// Recognizes `\\ \" \' \a \b \t \v \f \n \r \0`, `\xNN` (exactly two hex
+/// digits, value at most `0x7F`), and `\u{...}` (one to six hex digits
+/// forming a valid `char`).
+pub fn unescape(slice: &str, offset: usize) -> Result<String> {
+    let body = &slice[1..slice.len() - 1];
+    let mut string = String::with_capacity(body.len());
+    let mut i = 0;
+    while i < body.len() {
+        let c = body[i..].chars().next().unwrap();
+        if c != '\\' {
+            string.push(c);
+            i += c.len_utf8();
+            continue;
+        }
+        let escape_start = offset + 1 + i;
+        let invalid_escape = |end: usize| Error {
+            kind: ErrorKind::StringEscSeqInvalid,
+            detail: None,
+            span: Span {
+                start: escape_start,
+                end: offset + 1 + end,
+            },
+        };
+        let escape = body[i + 1..]
+            .chars()
+            .next()
+            .ok_or_else(|| invalid_escape(body.len()))?;
+        let after = i + 1 + escape.len_utf8();
+        match escape {
+            '\\' => {
+                string.push('\\');
+                i = after;
+            }
+            '"' => {
+                string.push('"');
+                i = after;
+            }
+            '\'' => {
+                string.push('\'');
+                i = after;
+            }
+            'a' => {
+                string.push('\x07');
+                i = after;
+            }
+            'b' => {
+                string.push('\x08');
+                i = after;
+            }
+            't' => {
+                string.push('\t');
+                i = after;
+            }
+            'v' => {
+                string.push('\x0B');
+                i = after;
+            }
+            'f' => {
+                string.push('\x0C');
+                i = after;
+            }
+            'n' => {
+                string.push('\n');
+                i = after;
+            }
+            'r' => {
+                string.push('\r');
+                i = after;
+            }
+            '0' => {
+                string.push('\0');
+                i = after;
+            }
+            'x' => {
+                let hex = body
+                    .get(after..after + 2)
+                    .ok_or_else(|| invalid_escape(body.len()))?;
+                let value = u8::from_str_radix(hex, 16).map_err(|_| invalid_escape(after + 2))?;
+                if value > 0x7F {
+                    return Err(invalid_escape(after + 2));
+                }
+                string.push(value as char);
+                i = after + 2;
+            }
+            'u' => {
+                if body.as_bytes().get(after) != Some(&b'{') {
+                    return Err(invalid_escape(after));
+                }
+                let hex_start = after + 1;
+                let close = body[hex_start..]
+                    .find('}')
+                    .ok_or_else(|| invalid_escape(body.len()))?;
+                let hex = &body[hex_start..hex_start + close];
+                let ch = (!hex.is_empty() && hex.len() <= 6 && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                    .then(|| u32::from_str_radix(hex, 16).ok())
+                    .flatten()
+                    .and_then(char::from_u32);
+                match ch {
+                    Some(ch) => {
+                        string.push(ch);
+                        i = hex_start + close + 1;
+                    }
+                    None => return Err(invalid_escape(hex_start + close + 1)),
+                }
+            }
+            _ => return Err(invalid_escape(after)),
+        }
+    }
+    Ok(string)
+}
+
+/// Decodes the escape sequences in `slice`, a byte-string literal's quoted
+/// text (its `b` prefix already stripped, the surrounding quotes still
+/// present). Accepts the same escapes as [`unescape`], except `\xNN`
+/// yields a raw byte `0..=255` instead of being restricted to a valid
+/// `char`, and `\u{...}` pushes its UTF-8 encoding rather than a single
+/// `char`. Any source byte outside an escape must be ASCII.
+pub fn unescape_bytes(slice: &str, offset: usize) -> Result<Vec<u8>> {
+    let body = &slice.as_bytes()[1..slice.len() - 1];
+    let mut bytes = Vec::with_capacity(body.len());
+    let mut i = 0;
+    while i < body.len() {
+        let b = body[i];
+        let invalid_at = |start: usize, end: usize| Error {
+            kind: ErrorKind::StringEscSeqInvalid,
+            detail: None,
+            span: Span {
+                // `body` starts at `offset + 1` (the opening quote is
+                // skipped), so a `body`-relative offset needs that `+ 1`
+                // to land on the actual offending byte.
+                start: offset + 1 + start,
+                end: offset + 1 + end,
+            },
+        };
+        if b != b'\\' {
+            if !b.is_ascii() {
+                return Err(invalid_at(i, i + 1));
+            }
+            bytes.push(b);
+            i += 1;
+            continue;
+        }
+        let escape_start = i;
+        let invalid_escape = |end: usize| invalid_at(escape_start, end);
+        let escape = *body.get(i + 1).ok_or_else(|| invalid_escape(body.len()))?;
+        let after = i + 2;
+        match escape {
+            b'\\' => {
+                bytes.push(b'\\');
+                i = after;
+            }
+            b'"' => {
+                bytes.push(b'"');
+                i = after;
+            }
+            b'\'' => {
+                bytes.push(b'\'');
+                i = after;
+            }
+            b'a' => {
+                bytes.push(0x07);
+                i = after;
+            }
+            b'b' => {
+                bytes.push(0x08);
+                i = after;
+            }
+            b't' => {
+                bytes.push(b'\t');
+                i = after;
+            }
+            b'v' => {
+                bytes.push(0x0B);
+                i = after;
+            }
+            b'f' => {
+                bytes.push(0x0C);
+                i = after;
+            }
+            b'n' => {
+                bytes.push(b'\n');
+                i = after;
+            }
+            b'r' => {
+                bytes.push(b'\r');
+                i = after;
+            }
+            b'0' => {
+                bytes.push(0);
+                i = after;
+            }
+            b'x' => {
+                let hex = body
+                    .get(after..after + 2)
+                    .ok_or_else(|| invalid_escape(body.len()))?;
+                let hex = std::str::from_utf8(hex).map_err(|_| invalid_escape(after + 2))?;
+                let value = u8::from_str_radix(hex, 16).map_err(|_| invalid_escape(after + 2))?;
+                bytes.push(value);
+                i = after + 2;
+            }
+            b'u' => {
+                if body.get(after) != Some(&b'{') {
+                    return Err(invalid_escape(after));
+                }
+                let hex_start = after + 1;
+                let close = body[hex_start..]
+                    .iter()
+                    .position(|&b| b == b'}')
+                    .ok_or_else(|| invalid_escape(body.len()))?;
+                let hex = std::str::from_utf8(&body[hex_start..hex_start + close])
+                    .map_err(|_| invalid_escape(hex_start + close + 1))?;
+                let ch = (!hex.is_empty()
+                    && hex.len() <= 6
+                    && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                .then(|| u32::from_str_radix(hex, 16).ok())
+                .flatten()
+                .and_then(char::from_u32);
+                match ch {
+                    Some(ch) => {
+                        let mut buf = [0u8; 4];
+                        bytes.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+                        i = hex_start + close + 1;
+                    }
+                    None => return Err(invalid_escape(hex_start + close + 1)),
+                }
+            }
+            _ => return Err(invalid_escape(after)),
+        }
+    }
+    Ok(bytes)
+}
+
+/// Decodes a base64 literal's quoted text (its `b64` prefix already
+/// stripped, the surrounding quotes still present) using the standard
+/// alphabet (`A-Z`, `a-z`, `0-9`, `+`, `/`) with `=` padding, e.g.
+/// `"SGVsbG8="` decodes to the bytes of `"Hello"`.
+pub fn decode_base64(slice: &str, offset: usize) -> Result<Vec<u8>> {
+    let body = &slice.as_bytes()[1..slice.len() - 1];
+    let invalid = || Error {
+        kind: ErrorKind::Base64Invalid,
+        detail: None,
+        span: Span {
+            start: offset,
+            end: offset + slice.len(),
+        },
+    };
+    fn sextet(b: u8) -> Option<u8> {
+        match b {
+            b'A'..=b'Z' => Some(b - b'A'),
+            b'a'..=b'z' => Some(b - b'a' + 26),
+            b'0'..=b'9' => Some(b - b'0' + 52),
+            b'+' => Some(62),
+            b'/' => Some(63),
+            _ => None,
+        }
+    }
+    if body.len() % 4 != 0 {
+        return Err(invalid());
+    }
+    let padding = body.iter().rev().take_while(|&&b| b == b'=').count();
+    if padding > 2 {
+        return Err(invalid());
+    }
+    let data = &body[..body.len() - padding];
+    if data.iter().any(|&b| sextet(b).is_none()) {
+        return Err(invalid());
+    }
+    let mut bytes = Vec::with_capacity(body.len() / 4 * 3);
+    for chunk in body.chunks(4) {
+        let sextets: Vec<u8> = chunk
+            .iter()
+            .map(|&b| if b == b'=' { 0 } else { sextet(b).unwrap() })
+            .collect();
+        let n = (sextets[0] as u32) << 18
+            | (sextets[1] as u32) << 12
+            | (sextets[2] as u32) << 6
+            | (sextets[3] as u32);
+        let decoded = [(n >> 16) as u8, (n >> 8) as u8, n as u8];
+        let chunk_padding = chunk.iter().rev().take_while(|&&b| b == b'=').count();
+        bytes.extend_from_slice(&decoded[..3 - chunk_padding]);
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_simple_escapes() {
+        assert_eq!(
+            unescape(r#""\0\a\b\t\v\f\n\r""#, 0),
+            Ok(String::from("\0\x07\x08\t\x0B\x0C\n\r"))
+        );
+        assert_eq!(unescape(r#""\\""#, 0), Ok(String::from("\\")));
+        assert_eq!(unescape(r#""\"""#, 0), Ok(String::from("\"")));
+    }
+
+    #[test]
+    fn decodes_hex_escapes() {
+        assert_eq!(
+            unescape(r#""\x00\x41\x7F""#, 0),
+            Ok(String::from("\0A\x7F"))
+        );
+    }
+
+    #[test]
+    fn decodes_unicode_escapes() {
+        assert_eq!(unescape(r#""\u{41}""#, 0), Ok(String::from("A")));
+        assert_eq!(unescape(r#""\u{1F600}""#, 0), Ok(String::from("\u{1F600}")));
+    }
+
+    #[test]
+    fn rejects_hex_escapes_above_0x7f() {
+        assert_eq!(
+            unescape(r#""\xFF""#, 0).unwrap_err().kind,
+            ErrorKind::StringEscSeqInvalid
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_escapes() {
+        assert_eq!(
+            unescape(r#""\z""#, 0).unwrap_err().kind,
+            ErrorKind::StringEscSeqInvalid
+        );
+    }
+
+    #[test]
+    fn invalid_escape_span_points_at_the_offending_bytes() {
+        // `"ab\xZZ"` - the `\xZZ` escape starts at byte offset 3.
+        let error = unescape(r#""ab\xZZ""#, 0).unwrap_err();
+        assert_eq!(error.span, Span { start: 3, end: 7 });
+    }
+
+    #[test]
+    fn unescape_bytes_decodes_simple_and_hex_escapes() {
+        assert_eq!(
+            unescape_bytes(r#""\0\a\b\t\v\f\n\r""#, 0),
+            Ok(b"\0\x07\x08\t\x0B\x0C\n\r".to_vec())
+        );
+        assert_eq!(unescape_bytes(r#""abc""#, 0), Ok(b"abc".to_vec()));
+        assert_eq!(unescape_bytes(r#""\xFF""#, 0), Ok(vec![0xFF]));
+    }
+
+    #[test]
+    fn unescape_bytes_encodes_unicode_escapes_as_utf8() {
+        assert_eq!(
+            unescape_bytes(r#""\u{1F600}""#, 0),
+            Ok("\u{1F600}".as_bytes().to_vec())
+        );
+    }
+
+    #[test]
+    fn invalid_escape_span_points_at_the_offending_bytes_in_a_byte_string() {
+        // `"ab\xZZ"` - the `\xZZ` escape starts at byte offset 3, same as
+        // the analogous `unescape` case above.
+        let error = unescape_bytes(r#""ab\xZZ""#, 0).unwrap_err();
+        assert_eq!(error.span, Span { start: 3, end: 7 });
+    }
+
+    #[test]
+    fn unescape_bytes_rejects_raw_non_ascii_source_bytes() {
+        assert_eq!(
+            unescape_bytes("\"café\"", 0).unwrap_err().kind,
+            ErrorKind::StringEscSeqInvalid
+        );
+    }
+
+    #[test]
+    fn decode_base64_decodes_standard_payloads() {
+        assert_eq!(decode_base64(r#""SGVsbG8=""#, 0), Ok(b"Hello".to_vec()));
+        assert_eq!(decode_base64(r#""TWFu""#, 0), Ok(b"Man".to_vec()));
+        assert_eq!(decode_base64(r#""""#, 0), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn decode_base64_rejects_invalid_characters() {
+        assert_eq!(
+            decode_base64(r#""SGVsbG8!""#, 0).unwrap_err().kind,
+            ErrorKind::Base64Invalid
+        );
+    }
+
+    #[test]
+    fn decode_base64_rejects_incorrect_padding_length() {
+        // A payload whose length isn't a multiple of 4.
+        assert_eq!(
+            decode_base64(r#""SGVsbG8""#, 0).unwrap_err().kind,
+            ErrorKind::Base64Invalid
+        );
+        // More than two trailing padding characters.
+        assert_eq!(
+            decode_base64(r#""A===""#, 0).unwrap_err().kind,
+            ErrorKind::Base64Invalid
+        );
+    }
+}
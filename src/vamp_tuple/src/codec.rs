@@ -0,0 +1,365 @@
+//! An order-preserving, self-describing binary encoding for `Tuple<T>`.
+//!
+//! Every entry is written as a one-byte type tag followed by a payload
+//! whose byte order matches the value's logical order, following the
+//! typed-prefix scheme used by embedded key-value tuple formats: integers
+//! are big-endian with the sign bit flipped so negatives sort before
+//! positives, floats are remapped to an order-preserving bit pattern, and
+//! strings are `0x00`-terminated with embedded `0x00` bytes escaped rather
+//! than length-prefixed, so a string sorts before any longer string it's a
+//! prefix of. This lets an encoded `Tuple` double as a byte-comparable
+//! sort key, and lets it round-trip through `encode`/`decode` byte-for-byte.
+
+use crate::{Tuple, TupleEntry};
+use vamp_sym::Sym;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Tag {
+    Null = 0,
+    Int = 1,
+    Float = 2,
+    Str = 3,
+    Sym = 4,
+    Tuple = 5,
+}
+
+impl Tag {
+    fn from_byte(byte: u8) -> Result<Tag> {
+        match byte {
+            0 => Ok(Tag::Null),
+            1 => Ok(Tag::Int),
+            2 => Ok(Tag::Float),
+            3 => Ok(Tag::Str),
+            4 => Ok(Tag::Sym),
+            5 => Ok(Tag::Tuple),
+            _ => Err(Error::InvalidTag(byte)),
+        }
+    }
+}
+
+/// An error decoding a `Tuple`'s binary encoding.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Error {
+    /// The byte slice ended before a complete value could be read.
+    UnexpectedEnd,
+    /// A type tag byte that isn't one of the known `Tag` values.
+    InvalidTag(u8),
+    /// A string payload that isn't valid UTF-8.
+    InvalidUtf8,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// A value that can be written to and read back from `Tuple`'s
+/// order-preserving binary encoding.
+pub trait Encode: Sized {
+    /// Appends `self`'s tag and payload to `buf`.
+    fn encode(&self, buf: &mut Vec<u8>);
+
+    /// Reads a value from the front of `bytes`, returning it alongside the
+    /// number of bytes consumed.
+    fn decode(bytes: &[u8]) -> Result<(Self, usize)>;
+}
+
+fn take(bytes: &[u8], len: usize) -> Result<&[u8]> {
+    bytes.get(..len).ok_or(Error::UnexpectedEnd)
+}
+
+fn expect_tag(bytes: &[u8], tag: Tag) -> Result<()> {
+    let byte = *bytes.first().ok_or(Error::UnexpectedEnd)?;
+    if Tag::from_byte(byte)? == tag {
+        Ok(())
+    } else {
+        Err(Error::InvalidTag(byte))
+    }
+}
+
+impl Encode for () {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.push(Tag::Null as u8);
+    }
+
+    fn decode(bytes: &[u8]) -> Result<(Self, usize)> {
+        expect_tag(bytes, Tag::Null)?;
+        Ok(((), 1))
+    }
+}
+
+impl Encode for i64 {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.push(Tag::Int as u8);
+        // Flipping the sign bit turns two's-complement big-endian bytes
+        // into bytes that sort the same as the signed value.
+        let flipped = (*self as u64) ^ (1 << 63);
+        buf.extend_from_slice(&flipped.to_be_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Result<(Self, usize)> {
+        expect_tag(bytes, Tag::Int)?;
+        let payload = take(bytes, 9)?;
+        let flipped = u64::from_be_bytes(payload[1..9].try_into().unwrap());
+        Ok(((flipped ^ (1 << 63)) as i64, 9))
+    }
+}
+
+/// Remaps an `f64`'s bit pattern so unsigned big-endian comparison matches
+/// IEEE 754 total order: flip the sign bit for non-negative values, flip
+/// every bit for negative ones.
+fn order_preserving_bits(value: f64) -> u64 {
+    let bits = value.to_bits();
+    if bits & (1 << 63) == 0 {
+        bits | (1 << 63)
+    } else {
+        !bits
+    }
+}
+
+fn float_from_order_preserving_bits(bits: u64) -> f64 {
+    let original = if bits & (1 << 63) != 0 {
+        bits & !(1 << 63)
+    } else {
+        !bits
+    };
+    f64::from_bits(original)
+}
+
+impl Encode for f64 {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.push(Tag::Float as u8);
+        buf.extend_from_slice(&order_preserving_bits(*self).to_be_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Result<(Self, usize)> {
+        expect_tag(bytes, Tag::Float)?;
+        let payload = take(bytes, 9)?;
+        let bits = u64::from_be_bytes(payload[1..9].try_into().unwrap());
+        Ok((float_from_order_preserving_bits(bits), 9))
+    }
+}
+
+impl Encode for String {
+    /// Writes the string's bytes with every `0x00` escaped to `0x00 0xFF`,
+    /// terminated by an unescaped `0x00`, the way FoundationDB's tuple
+    /// layer encodes strings: unlike a length prefix, this keeps a
+    /// shorter string sorting before any longer string it's a prefix of.
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.push(Tag::Str as u8);
+        for &byte in self.as_bytes() {
+            buf.push(byte);
+            if byte == 0x00 {
+                buf.push(0xFF);
+            }
+        }
+        buf.push(0x00);
+    }
+
+    fn decode(bytes: &[u8]) -> Result<(Self, usize)> {
+        expect_tag(bytes, Tag::Str)?;
+        let mut data = Vec::new();
+        let mut offset = 1;
+        loop {
+            let byte = *bytes.get(offset).ok_or(Error::UnexpectedEnd)?;
+            offset += 1;
+            if byte == 0x00 {
+                if bytes.get(offset) == Some(&0xFF) {
+                    data.push(0x00);
+                    offset += 1;
+                } else {
+                    break;
+                }
+            } else {
+                data.push(byte);
+            }
+        }
+        let string = String::from_utf8(data).map_err(|_| Error::InvalidUtf8)?;
+        Ok((string, offset))
+    }
+}
+
+impl Encode for Sym {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.push(Tag::Sym as u8);
+        buf.extend_from_slice(&self.0.to_be_bytes());
+    }
+
+    fn decode(bytes: &[u8]) -> Result<(Self, usize)> {
+        expect_tag(bytes, Tag::Sym)?;
+        let payload = take(bytes, 5)?;
+        Ok((Sym(u32::from_be_bytes(payload[1..5].try_into().unwrap())), 5))
+    }
+}
+
+impl<T: Encode> Encode for Tuple<T> {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.push(Tag::Tuple as u8);
+        buf.extend_from_slice(&(self.len() as u32).to_be_bytes());
+        buf.extend_from_slice(&(self.keys_len() as u32).to_be_bytes());
+        // `self.keys` is kept sorted, so iterating positional-then-named
+        // (as `iter` does) serializes deterministically.
+        for entry in self.iter() {
+            match entry {
+                TupleEntry::Pos(value) => value.encode(buf),
+                TupleEntry::Named(key, value) => {
+                    key.encode(buf);
+                    value.encode(buf);
+                }
+            }
+        }
+    }
+
+    fn decode(bytes: &[u8]) -> Result<(Self, usize)> {
+        expect_tag(bytes, Tag::Tuple)?;
+        let header = take(bytes, 9)?;
+        let len = u32::from_be_bytes(header[1..5].try_into().unwrap()) as usize;
+        let keys_len = u32::from_be_bytes(header[5..9].try_into().unwrap()) as usize;
+        let pos_len = len.checked_sub(keys_len).ok_or(Error::UnexpectedEnd)?;
+
+        let mut offset = 9;
+        let mut tuple = Tuple::new();
+        for _ in 0..pos_len {
+            let (value, consumed) = T::decode(&bytes[offset..])?;
+            tuple.push(value);
+            offset += consumed;
+        }
+        for _ in 0..keys_len {
+            let (key, consumed) = Sym::decode(&bytes[offset..])?;
+            offset += consumed;
+            let (value, consumed) = T::decode(&bytes[offset..])?;
+            offset += consumed;
+            tuple.insert(key, value);
+        }
+        Ok((tuple, offset))
+    }
+}
+
+impl<T: Encode> Tuple<T> {
+    /// Appends this tuple's order-preserving binary encoding to `buf`.
+    pub fn encode(&self, buf: &mut Vec<u8>) {
+        Encode::encode(self, buf)
+    }
+
+    /// Decodes a tuple previously written by `encode`.
+    pub fn decode(bytes: &[u8]) -> Result<Tuple<T>> {
+        Encode::decode(bytes).map(|(tuple, _)| tuple)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_positional_and_named_entries() {
+        let mut tuple: Tuple<i64> = Tuple::new();
+        tuple.push(1);
+        tuple.push(2);
+        tuple.insert(Sym(0), 3);
+
+        let mut buf = Vec::new();
+        tuple.encode(&mut buf);
+        assert_eq!(Tuple::<i64>::decode(&buf), Ok(tuple));
+    }
+
+    #[test]
+    fn round_trips_floats_and_strings() {
+        let mut tuple: Tuple<String> = Tuple::new();
+        tuple.push("a".into());
+        tuple.insert(Sym(1), "bcd".into());
+
+        let mut buf = Vec::new();
+        tuple.encode(&mut buf);
+        assert_eq!(Tuple::<String>::decode(&buf), Ok(tuple));
+
+        let values = [-1.5f64, 0.0, 1.5, f64::MIN, f64::MAX];
+        for value in values {
+            let mut buf = Vec::new();
+            value.encode(&mut buf);
+            assert_eq!(f64::decode(&buf), Ok((value, buf.len())));
+        }
+    }
+
+    #[test]
+    fn encoded_integers_sort_the_same_as_their_values() {
+        // Already in ascending value order: an order-preserving encoding
+        // means the byte-sorted encodings must come out in this same order.
+        let values = [i64::MIN, -1000, -1, 0, 1, 1000, i64::MAX];
+        let encoded: Vec<Vec<u8>> = values
+            .iter()
+            .map(|v| {
+                let mut buf = Vec::new();
+                v.encode(&mut buf);
+                buf
+            })
+            .collect();
+        let mut sorted = encoded.clone();
+        sorted.sort();
+        assert_eq!(encoded, sorted);
+        for (value, buf) in values.iter().zip(encoded.iter()) {
+            assert_eq!(i64::decode(buf), Ok((*value, buf.len())));
+        }
+    }
+
+    #[test]
+    fn encoded_strings_sort_the_same_as_their_values() {
+        // Already in ascending value order: an order-preserving encoding
+        // means the byte-sorted encodings must come out in this same order.
+        // A naive length-prefixed encoding would sort "aa" (len 2) after
+        // "b" (len 1) even though "aa" < "b" as strings.
+        let values = ["a", "aa", "b"];
+        let encoded: Vec<Vec<u8>> = values
+            .iter()
+            .map(|v| {
+                let mut buf = Vec::new();
+                v.to_string().encode(&mut buf);
+                buf
+            })
+            .collect();
+        let mut sorted = encoded.clone();
+        sorted.sort();
+        assert_eq!(encoded, sorted);
+        for (value, buf) in values.iter().zip(encoded.iter()) {
+            assert_eq!(String::decode(buf), Ok((value.to_string(), buf.len())));
+        }
+    }
+
+    #[test]
+    fn round_trips_a_string_with_an_embedded_nul_byte() {
+        let value = "a\0b".to_string();
+        let mut buf = Vec::new();
+        value.encode(&mut buf);
+        assert_eq!(String::decode(&buf), Ok((value, buf.len())));
+    }
+
+    #[test]
+    fn round_trips_null_entries() {
+        let mut tuple: Tuple<()> = Tuple::new();
+        tuple.push(());
+        tuple.insert(Sym(0), ());
+
+        let mut buf = Vec::new();
+        tuple.encode(&mut buf);
+        assert_eq!(Tuple::<()>::decode(&buf), Ok(tuple));
+    }
+
+    #[test]
+    fn nested_tuples_round_trip() {
+        let mut inner: Tuple<i64> = Tuple::new();
+        inner.push(42);
+        let mut outer: Tuple<Tuple<i64>> = Tuple::new();
+        outer.push(inner);
+
+        let mut buf = Vec::new();
+        outer.encode(&mut buf);
+        assert_eq!(Tuple::<Tuple<i64>>::decode(&buf), Ok(outer));
+    }
+
+    #[test]
+    fn rejects_truncated_input() {
+        let mut tuple: Tuple<i64> = Tuple::new();
+        tuple.push(1);
+        let mut buf = Vec::new();
+        tuple.encode(&mut buf);
+        buf.truncate(buf.len() - 1);
+        assert_eq!(Tuple::<i64>::decode(&buf), Err(Error::UnexpectedEnd));
+    }
+}
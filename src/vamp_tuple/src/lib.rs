@@ -1,6 +1,12 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use vamp_sym::Sym;
 
+pub mod codec;
+pub use codec::Encode;
+
 /// Represents a single positional or named entry in a tuple.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TupleEntry<T> {
     /// A positional tuple entry.
     Pos(T),
@@ -41,6 +47,7 @@ impl<T: std::fmt::Debug> std::fmt::Debug for Tuple<T> {
 }
 
 /// Represents a combination of positional and named members.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Tuple<T> {
     /// Sorted list of keys.
     pub(crate) keys: Vec<Sym>,
@@ -1,6 +1,9 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use vamp_tuple::Tuple;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Ty {
     /// The unknown type.
     Unknown,
@@ -24,4 +27,10 @@ pub enum Ty {
     /// The sum type. `Ty::Any` is inhabited by the union of all values in any
     /// of its types.
     Any(Box<[Ty]>),
+    /// A function type from a tuple of parameter types to a return type.
+    Fn(Box<Tuple<Ty>>, Box<Ty>),
+    /// An unbound type variable introduced during inference, identified by
+    /// a unique index. Resolved to a concrete type (or another variable)
+    /// through the inferring pass's substitution.
+    Var(u32),
 }
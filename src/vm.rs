@@ -1,8 +1,16 @@
+//! A flat bytecode format and stack machine, offered by `compiler::compile`
+//! alongside `eval::eval`'s tree-walk as a second execution path for the
+//! constant int/float arithmetic subset of `parse::Expr` the compiler can
+//! lower.
+
+use crate::eval::{EvalError, EvalErrorKind, Value};
+use crate::source::{Position, Span};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum Opcode {
     End,
     Push,
-    Pop,
     Add,
     Sub,
     Mul,
@@ -10,74 +18,225 @@ pub enum Opcode {
     Mod,
 }
 
+impl Opcode {
+    fn from_u8(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Opcode::End),
+            1 => Some(Opcode::Push),
+            2 => Some(Opcode::Add),
+            3 => Some(Opcode::Sub),
+            4 => Some(Opcode::Mul),
+            5 => Some(Opcode::Div),
+            6 => Some(Opcode::Mod),
+            _ => None,
+        }
+    }
+}
+
+/// The type tag written right after a `Push` opcode, identifying how many
+/// immediate bytes follow it and how to interpret them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum Optype {
-    U8,
-    U16,
-    U32,
-    U64,
-    I8,
-    I16,
-    I32,
-    I64,
-    F32,
-    F64,
-    Tuple,
+    Int,
+    Float,
 }
 
-struct Bytecode {
-    bytes: *const u8,
+impl Optype {
+    fn from_u8(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Optype::Int),
+            1 => Some(Optype::Float),
+            _ => None,
+        }
+    }
+}
+
+/// A safe read cursor over an in-memory bytecode stream. `next_i64`/
+/// `next_f64` copy `size_of::<T>()` bytes out of the slice and decode them
+/// with `from_le_bytes`, rather than casting a (possibly misaligned)
+/// pointer into the buffer, so there's no `unsafe` anywhere in here.
+pub struct Bytecode<'a> {
+    bytes: &'a [u8],
     offset: usize,
 }
 
 impl<'a> Bytecode<'a> {
-    fn new(bytes: *const u8) -> Self {
-        Instructions { bytes, offset: 0 } 
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Bytecode { bytes, offset: 0 }
+    }
+
+    pub fn next_u8(&mut self) -> Option<u8> {
+        let byte = *self.bytes.get(self.offset)?;
+        self.offset += 1;
+        Some(byte)
+    }
+
+    pub fn next_i64(&mut self) -> Option<i64> {
+        self.next_array::<8>().map(i64::from_le_bytes)
+    }
+
+    pub fn next_f64(&mut self) -> Option<f64> {
+        self.next_array::<8>().map(f64::from_le_bytes)
+    }
+
+    fn next_array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let slice = self.bytes.get(self.offset..self.offset + N)?;
+        self.offset += N;
+        Some(slice.try_into().expect("slice of length N"))
+    }
+
+    fn next_opcode(&mut self) -> Option<Opcode> {
+        Opcode::from_u8(self.next_u8()?)
+    }
+
+    fn next_optype(&mut self) -> Option<Optype> {
+        Optype::from_u8(self.next_u8()?)
     }
+}
+
+/// A zero-width placeholder span: bytecode has no source text of its own to
+/// point back at, so VM errors carry this instead (mirrors
+/// `convert::placeholder_span`).
+fn placeholder_span() -> Span {
+    let origin = Position {
+        offset: 0,
+        line: 1,
+        column: 1,
+    };
+    Span {
+        start: origin,
+        end: origin,
+    }
+}
 
-    fn next<T>() -> T {
-        let size = std::mem::size_of<T>();
-        let memory = unsafe {
-            *std::mem::transmute::<*const u8, *const T>(self.bytes)
-        };
-        offset += size;
-        memory
+fn malformed() -> EvalError {
+    EvalError {
+        kind: EvalErrorKind::Unsupported("malformed bytecode"),
+        span: placeholder_span(),
     }
 }
 
-pub struct Vm {
+fn type_mismatch() -> EvalError {
+    EvalError {
+        kind: EvalErrorKind::TypeMismatch,
+        span: placeholder_span(),
+    }
 }
 
+pub struct Vm;
+
 impl Vm {
-    fn run(bytecode: Bytecode) {
-        let stack = vec![];
+    /// Runs `bytes` to completion and returns the value left on top of the
+    /// stack. Arithmetic pops its two operands, applies them keeping the
+    /// int/float distinction (mixing the two is a `TypeMismatch`), and
+    /// pushes the result; `End` stops the loop and hands back the stack
+    /// top.
+    pub fn run(bytes: &[u8]) -> Result<Value, EvalError> {
+        let mut bytecode = Bytecode::new(bytes);
+        let mut stack: Vec<Value> = Vec::new();
         loop {
-            match bytecode.next::<Opcode>() {
-                Opcode::End => {
-                    break,
-                },
+            match bytecode.next_opcode().ok_or_else(malformed)? {
+                Opcode::End => break,
                 Opcode::Push => {
-                    match bytecode.next::<Optype>() {
-                        Optype::U8 => {
-                        },
-                    }
-                },
-                Opcode::Pop => {
-                    match bytecode.next::<Optype>() {
-
-                    }
-                },
-                Opcode::Add => {
-                },
-                Opcode::Sub => {
-                },
-                Opcode::Mul => {
-                },
-                Opcode::Div => {
-                },
-                Opcode::Mod => {
-                },
+                    let value = match bytecode.next_optype().ok_or_else(malformed)? {
+                        Optype::Int => Value::Integer(bytecode.next_i64().ok_or_else(malformed)?),
+                        Optype::Float => Value::Float(bytecode.next_f64().ok_or_else(malformed)?),
+                    };
+                    stack.push(value);
+                }
+                opcode => {
+                    let rhs = stack.pop().ok_or_else(type_mismatch)?;
+                    let lhs = stack.pop().ok_or_else(type_mismatch)?;
+                    stack.push(apply(opcode, lhs, rhs)?);
+                }
             }
         }
+        stack.pop().ok_or_else(type_mismatch)
+    }
+}
+
+fn apply(opcode: Opcode, lhs: Value, rhs: Value) -> Result<Value, EvalError> {
+    match (lhs, rhs) {
+        (Value::Integer(a), Value::Integer(b)) => Ok(Value::Integer(match opcode {
+            Opcode::Add => a + b,
+            Opcode::Sub => a - b,
+            Opcode::Mul => a * b,
+            Opcode::Div => a.checked_div(b).ok_or_else(|| EvalError {
+                kind: EvalErrorKind::DivisionByZero,
+                span: placeholder_span(),
+            })?,
+            Opcode::Mod => a.checked_rem(b).ok_or_else(|| EvalError {
+                kind: EvalErrorKind::DivisionByZero,
+                span: placeholder_span(),
+            })?,
+            Opcode::End | Opcode::Push => unreachable!("not an arithmetic opcode"),
+        })),
+        (Value::Float(a), Value::Float(b)) => Ok(Value::Float(match opcode {
+            Opcode::Add => a + b,
+            Opcode::Sub => a - b,
+            Opcode::Mul => a * b,
+            Opcode::Div => a / b,
+            Opcode::Mod => a % b,
+            Opcode::End | Opcode::Push => unreachable!("not an arithmetic opcode"),
+        })),
+        _ => Err(type_mismatch()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_int(bytes: &mut Vec<u8>, value: i64) {
+        bytes.push(Opcode::Push as u8);
+        bytes.push(Optype::Int as u8);
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    #[test]
+    fn test_push_and_end() {
+        let mut bytes = Vec::new();
+        push_int(&mut bytes, 42);
+        bytes.push(Opcode::End as u8);
+        assert_eq!(Vm::run(&bytes), Ok(Value::Integer(42)));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let mut bytes = Vec::new();
+        push_int(&mut bytes, 1);
+        push_int(&mut bytes, 2);
+        bytes.push(Opcode::Add as u8);
+        bytes.push(Opcode::End as u8);
+        assert_eq!(Vm::run(&bytes), Ok(Value::Integer(3)));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_division_by_zero() {
+        let mut bytes = Vec::new();
+        push_int(&mut bytes, 1);
+        push_int(&mut bytes, 0);
+        bytes.push(Opcode::Div as u8);
+        bytes.push(Opcode::End as u8);
+        assert_eq!(
+            Vm::run(&bytes).unwrap_err().kind,
+            EvalErrorKind::DivisionByZero
+        );
+    }
+
+    #[test]
+    fn test_type_mismatch_on_mixed_arithmetic() {
+        let mut bytes = Vec::new();
+        push_int(&mut bytes, 1);
+        bytes.push(Opcode::Push as u8);
+        bytes.push(Optype::Float as u8);
+        bytes.extend_from_slice(&2.0f64.to_le_bytes());
+        bytes.push(Opcode::Add as u8);
+        bytes.push(Opcode::End as u8);
+        assert_eq!(
+            Vm::run(&bytes).unwrap_err().kind,
+            EvalErrorKind::TypeMismatch
+        );
+    }
+}
@@ -1,6 +1,8 @@
+use crate::depgraph::DependencyGraph;
 use crate::source::SourceEvent;
 use notify::RecursiveMode;
 use notify_debouncer_mini::new_debouncer;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use std::{
     path::{Path, PathBuf},
@@ -14,7 +16,19 @@ fn find_source_paths(root_path: &Path) -> Option<Vec<PathBuf>> {
     return Some(source_paths);
 }
 
-pub fn watch(root_path: &Path, source_events: Sender<SourceEvent>) -> notify::Result<()> {
+/// Watches `root_path` for `.vamp` changes and emits a `SourceEvent::File`
+/// for every module that needs re-evaluating. A burst of saves debounced
+/// into a single `events` batch is resolved through `graph` into one
+/// invalidation wave — the changed files plus their transitive dependents —
+/// rather than one event per raw filesystem notification. `graph` is
+/// populated by whoever parses each module's `Module::imports`; until a
+/// path's imports have been recorded, invalidation falls back to just that
+/// path.
+pub fn watch(
+    root_path: &Path,
+    source_events: Sender<SourceEvent>,
+    graph: Arc<Mutex<DependencyGraph>>,
+) -> notify::Result<()> {
     let source_paths = find_source_paths(root_path).unwrap_or(vec![]);
     for path in source_paths {
         source_events.send(SourceEvent::File(path.clone())).unwrap();
@@ -27,12 +41,17 @@ pub fn watch(root_path: &Path, source_events: Sender<SourceEvent>) -> notify::Re
     for result in receiver {
         match result {
             Ok(events) => {
-                for event in events {
-                    if event.path.extension().and_then(|e| e.to_str()) == Some("vamp") {
-                        source_events
-                            .send(SourceEvent::File(event.path.clone()))
-                            .unwrap();
-                    }
+                let changed: Vec<PathBuf> = events
+                    .into_iter()
+                    .map(|event| event.path)
+                    .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("vamp"))
+                    .collect();
+                if changed.is_empty() {
+                    continue;
+                }
+                let wave = graph.lock().unwrap().invalidate(&changed);
+                for path in wave {
+                    source_events.send(SourceEvent::File(path)).unwrap();
                 }
             }
             Err(error) => {